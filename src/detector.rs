@@ -20,12 +20,20 @@ pub enum Detectors {
 
 /// chooses the detector
 pub fn detect_language(text: &str, detectors: &Detectors) -> Result<Language, Error> {
+    detect_language_confidence(text, detectors).map(|v| v.0)
+}
+
+/// chooses the detector and returns the language with the confidence between 0 and 1
+pub fn detect_language_confidence(
+    text: &str,
+    detectors: &Detectors,
+) -> Result<(Language, f64), Error> {
     //TODO: implement api detectors
     match detectors {
         #[cfg(feature = "lingua-detector")]
-        Detectors::Lingua => detect_language_lingua(text),
+        Detectors::Lingua => detect_language_lingua_confidence(text),
         #[cfg(feature = "whatlang-detector")]
-        Detectors::Whatlang => detect_language_whatlang(text),
+        Detectors::Whatlang => detect_language_whatlang_confidence(text),
         _ => Err(Error::new_option("No detector available")),
     }
 }
@@ -33,87 +41,100 @@ pub fn detect_language(text: &str, detectors: &Detectors) -> Result<Language, Er
 /// returns the LanguageCode enum of the text using lingua
 #[cfg(feature = "lingua-detector")]
 pub fn detect_language_lingua(text: &str) -> Result<Language, Error> {
+    detect_language_lingua_confidence(text).map(|v| v.0)
+}
+
+/// returns the LanguageCode enum and the confidence of the text using lingua
+#[cfg(feature = "lingua-detector")]
+pub fn detect_language_lingua_confidence(text: &str) -> Result<(Language, f64), Error> {
     let detector: LanguageDetector = LanguageDetectorBuilder::from_all_languages().build();
-    let detected_language: Option<LinguaLanguage> = detector.detect_language_of(text);
+    let detected_language: Option<(LinguaLanguage, f64)> = detector
+        .compute_language_confidence_values(text)
+        .into_iter()
+        .next()
+        .filter(|v| v.1 > 0.0);
 
     match &detected_language {
-        Some(lang) => Ok(match lang {
-            LinguaLanguage::Afrikaans => Language::Afrikaans,
-            LinguaLanguage::Albanian => Language::Albanian,
-            LinguaLanguage::Arabic => Language::Arabic,
-            LinguaLanguage::Armenian => Language::Armenian,
-            LinguaLanguage::Azerbaijani => Language::Azerbaijani,
-            LinguaLanguage::Basque => Language::Basque,
-            LinguaLanguage::Belarusian => Language::Belarusian,
-            LinguaLanguage::Bengali => Language::Bengali,
-            LinguaLanguage::Bokmal => Language::Bokmål,
-            LinguaLanguage::Bosnian => Language::Bosnian,
-            LinguaLanguage::Bulgarian => Language::Bulgarian,
-            LinguaLanguage::Catalan => Language::Catalan,
-            LinguaLanguage::Chinese => Language::Chinese,
-            LinguaLanguage::Croatian => Language::Croatian,
-            LinguaLanguage::Czech => Language::Czech,
-            LinguaLanguage::Danish => Language::Danish,
-            LinguaLanguage::Dutch => Language::Dutch,
-            LinguaLanguage::English => Language::English,
-            LinguaLanguage::Esperanto => Language::Spanish,
-            LinguaLanguage::Estonian => Language::Estonian,
-            LinguaLanguage::Finnish => Language::Finnish,
-            LinguaLanguage::French => Language::French,
-            LinguaLanguage::Ganda => Language::Ganda,
-            LinguaLanguage::Georgian => Language::Georgian,
-            LinguaLanguage::German => Language::German,
-            LinguaLanguage::Greek => Language::Greek,
-            LinguaLanguage::Gujarati => Language::Gujarati,
-            LinguaLanguage::Hebrew => Language::Hebrew,
-            LinguaLanguage::Hindi => Language::Hindi,
-            LinguaLanguage::Hungarian => Language::Hungarian,
-            LinguaLanguage::Icelandic => Language::Icelandic,
-            LinguaLanguage::Indonesian => Language::Indonesian,
-            LinguaLanguage::Irish => Language::Irish,
-            LinguaLanguage::Italian => Language::Italian,
-            LinguaLanguage::Japanese => Language::Japanese,
-            LinguaLanguage::Kazakh => Language::Kazakh,
-            LinguaLanguage::Korean => Language::Korean,
-            LinguaLanguage::Latin => Language::Latin,
-            LinguaLanguage::Latvian => Language::Latvian,
-            LinguaLanguage::Lithuanian => Language::Lithuanian,
-            LinguaLanguage::Macedonian => Language::Macedonian,
-            LinguaLanguage::Malay => Language::Malayalam,
-            LinguaLanguage::Maori => Language::Maori,
-            LinguaLanguage::Marathi => Language::Marathi,
-            LinguaLanguage::Mongolian => Language::Mongolian,
-            LinguaLanguage::Nynorsk => Language::Nynorsk,
-            LinguaLanguage::Persian => Language::Persian,
-            LinguaLanguage::Polish => Language::Polish,
-            LinguaLanguage::Portuguese => Language::Portuguese,
-            LinguaLanguage::Punjabi => Language::Panjabi,
-            LinguaLanguage::Romanian => Language::Romanian,
-            LinguaLanguage::Russian => Language::Russian,
-            LinguaLanguage::Serbian => Language::Serbian,
-            LinguaLanguage::Shona => Language::Shona,
-            LinguaLanguage::Slovak => Language::Slovak,
-            LinguaLanguage::Slovene => Language::Slovenian,
-            LinguaLanguage::Somali => Language::Somali,
-            LinguaLanguage::Sotho => Language::Sotho,
-            LinguaLanguage::Spanish => Language::Spanish,
-            LinguaLanguage::Swahili => Language::Swahili,
-            LinguaLanguage::Swedish => Language::Swedish,
-            LinguaLanguage::Tagalog => Language::Tagalog,
-            LinguaLanguage::Tamil => Language::Tamil,
-            LinguaLanguage::Telugu => Language::Telugu,
-            LinguaLanguage::Thai => Language::Thai,
-            LinguaLanguage::Tsonga => Language::Tsonga,
-            LinguaLanguage::Tswana => Language::Tswana,
-            LinguaLanguage::Turkish => Language::Turkish,
-            LinguaLanguage::Ukrainian => Language::Ukrainian,
-            LinguaLanguage::Urdu => Language::Urdu,
-            LinguaLanguage::Vietnamese => Language::Vietnamese,
-            LinguaLanguage::Welsh => Language::Welsh,
-            LinguaLanguage::Xhosa => Language::Xhosa,
-            LinguaLanguage::Yoruba => Language::Yoruba,
-            LinguaLanguage::Zulu => Language::Zulu,
-        }),
+        Some((lang, confidence)) => Ok((
+            match lang {
+                LinguaLanguage::Afrikaans => Language::Afrikaans,
+                LinguaLanguage::Albanian => Language::Albanian,
+                LinguaLanguage::Arabic => Language::Arabic,
+                LinguaLanguage::Armenian => Language::Armenian,
+                LinguaLanguage::Azerbaijani => Language::Azerbaijani,
+                LinguaLanguage::Basque => Language::Basque,
+                LinguaLanguage::Belarusian => Language::Belarusian,
+                LinguaLanguage::Bengali => Language::Bengali,
+                LinguaLanguage::Bokmal => Language::Bokmål,
+                LinguaLanguage::Bosnian => Language::Bosnian,
+                LinguaLanguage::Bulgarian => Language::Bulgarian,
+                LinguaLanguage::Catalan => Language::Catalan,
+                LinguaLanguage::Chinese => Language::Chinese,
+                LinguaLanguage::Croatian => Language::Croatian,
+                LinguaLanguage::Czech => Language::Czech,
+                LinguaLanguage::Danish => Language::Danish,
+                LinguaLanguage::Dutch => Language::Dutch,
+                LinguaLanguage::English => Language::English,
+                LinguaLanguage::Esperanto => Language::Spanish,
+                LinguaLanguage::Estonian => Language::Estonian,
+                LinguaLanguage::Finnish => Language::Finnish,
+                LinguaLanguage::French => Language::French,
+                LinguaLanguage::Ganda => Language::Ganda,
+                LinguaLanguage::Georgian => Language::Georgian,
+                LinguaLanguage::German => Language::German,
+                LinguaLanguage::Greek => Language::Greek,
+                LinguaLanguage::Gujarati => Language::Gujarati,
+                LinguaLanguage::Hebrew => Language::Hebrew,
+                LinguaLanguage::Hindi => Language::Hindi,
+                LinguaLanguage::Hungarian => Language::Hungarian,
+                LinguaLanguage::Icelandic => Language::Icelandic,
+                LinguaLanguage::Indonesian => Language::Indonesian,
+                LinguaLanguage::Irish => Language::Irish,
+                LinguaLanguage::Italian => Language::Italian,
+                LinguaLanguage::Japanese => Language::Japanese,
+                LinguaLanguage::Kazakh => Language::Kazakh,
+                LinguaLanguage::Korean => Language::Korean,
+                LinguaLanguage::Latin => Language::Latin,
+                LinguaLanguage::Latvian => Language::Latvian,
+                LinguaLanguage::Lithuanian => Language::Lithuanian,
+                LinguaLanguage::Macedonian => Language::Macedonian,
+                LinguaLanguage::Malay => Language::Malayalam,
+                LinguaLanguage::Maori => Language::Maori,
+                LinguaLanguage::Marathi => Language::Marathi,
+                LinguaLanguage::Mongolian => Language::Mongolian,
+                LinguaLanguage::Nynorsk => Language::Nynorsk,
+                LinguaLanguage::Persian => Language::Persian,
+                LinguaLanguage::Polish => Language::Polish,
+                LinguaLanguage::Portuguese => Language::Portuguese,
+                LinguaLanguage::Punjabi => Language::Panjabi,
+                LinguaLanguage::Romanian => Language::Romanian,
+                LinguaLanguage::Russian => Language::Russian,
+                LinguaLanguage::Serbian => Language::Serbian,
+                LinguaLanguage::Shona => Language::Shona,
+                LinguaLanguage::Slovak => Language::Slovak,
+                LinguaLanguage::Slovene => Language::Slovenian,
+                LinguaLanguage::Somali => Language::Somali,
+                LinguaLanguage::Sotho => Language::Sotho,
+                LinguaLanguage::Spanish => Language::Spanish,
+                LinguaLanguage::Swahili => Language::Swahili,
+                LinguaLanguage::Swedish => Language::Swedish,
+                LinguaLanguage::Tagalog => Language::Tagalog,
+                LinguaLanguage::Tamil => Language::Tamil,
+                LinguaLanguage::Telugu => Language::Telugu,
+                LinguaLanguage::Thai => Language::Thai,
+                LinguaLanguage::Tsonga => Language::Tsonga,
+                LinguaLanguage::Tswana => Language::Tswana,
+                LinguaLanguage::Turkish => Language::Turkish,
+                LinguaLanguage::Ukrainian => Language::Ukrainian,
+                LinguaLanguage::Urdu => Language::Urdu,
+                LinguaLanguage::Vietnamese => Language::Vietnamese,
+                LinguaLanguage::Welsh => Language::Welsh,
+                LinguaLanguage::Xhosa => Language::Xhosa,
+                LinguaLanguage::Yoruba => Language::Yoruba,
+                LinguaLanguage::Zulu => Language::Zulu,
+            },
+            *confidence,
+        )),
         None => Err(Error::new_option("Coudlnt detect language with lingua")),
     }
 }
@@ -121,11 +142,17 @@ pub fn detect_language_lingua(text: &str) -> Result<Language, Error> {
 /// returns LanguageCode enum generated from Whatlang
 #[cfg(feature = "whatlang-detector")]
 pub fn detect_language_whatlang(text: &str) -> Result<Language, Error> {
+    detect_language_whatlang_confidence(text).map(|v| v.0)
+}
+
+/// returns LanguageCode enum and the confidence generated from Whatlang
+#[cfg(feature = "whatlang-detector")]
+pub fn detect_language_whatlang_confidence(text: &str) -> Result<(Language, f64), Error> {
     let info = match detect(text) {
         Some(info) => Ok(info),
         None => Err(Error::new_option("Unknown iso")),
     }?;
-    Ok(match info.lang() {
+    let lang = match info.lang() {
         Lang::Epo => Language::Spanish,
         Lang::Eng => Language::English,
         Lang::Rus => Language::Russian,
@@ -195,5 +222,6 @@ pub fn detect_language_whatlang(text: &str) -> Result<Language, Error> {
         Lang::Cat => Language::Catalan,
         Lang::Tgl => Language::Tagalog,
         Lang::Hye => Language::Armenian,
-    })
+    };
+    Ok((lang, info.confidence()))
 }
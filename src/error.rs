@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use crate::translators::api::baidu::BaiduApiError;
 
 #[derive(Clone, Debug)]
@@ -7,6 +9,7 @@ pub enum Error {
     Fetch(String),
     BaiduError(String),
     MissingToken(String),
+    Checksum(ChecksumError),
 }
 
 #[derive(Clone, Debug)]
@@ -15,6 +18,15 @@ pub struct TextError {
     pub error: Option<String>,
 }
 
+/// A model file whose sha256 doesnt match the registry
+#[derive(Clone, Debug)]
+pub struct ChecksumError {
+    pub model: String,
+    pub file: PathBuf,
+    pub expected: String,
+    pub actual: String,
+}
+
 impl Error {
     pub fn new(message: impl ToString, error: impl ToString) -> Self {
         Error::Text(TextError {
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::formats::subtitles::is_cjk;
+use crate::languages::Language;
+use crate::translators::context::{Context, HistoryEntry};
+use crate::translators::options::TranslationOptions;
+use crate::translators::Translators;
+
+/// Position of a text region in pixels, the origin is the top left corner of the page
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl BoundingBox {
+    fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+
+    fn center_y(&self) -> f32 {
+        self.y + self.height / 2.0
+    }
+}
+
+/// Order of the regions in a row
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingDirection {
+    /// Manga
+    #[default]
+    RightToLeft,
+    /// Comics and webtoons
+    LeftToRight,
+}
+
+/// A text bubble or caption of a page
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TextRegion {
+    pub id: String,
+    pub bbox: BoundingBox,
+    /// Position in the reading order, starting at 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reading_order: Option<usize>,
+    /// Text is written in columns
+    #[serde(default)]
+    pub vertical: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    pub source_text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_text: Option<String>,
+}
+
+impl TextRegion {
+    /// Joins the lines of the bubble, cjk text is joined without spaces
+    fn query(&self) -> String {
+        let mut query = String::new();
+        for line in self
+            .source_text
+            .lines()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            match (query.chars().last(), line.chars().next()) {
+                (Some(a), Some(b)) if !is_cjk(a) && !is_cjk(b) => query.push(' '),
+                _ => {}
+            }
+            query.push_str(line);
+        }
+        query
+    }
+}
+
+/// A page with its text regions
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Page {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub direction: ReadingDirection,
+    pub regions: Vec<TextRegion>,
+}
+
+impl Page {
+    pub fn from_json(text: &str) -> Result<Self, Error> {
+        serde_json::from_str(text).map_err(|e| Error::new("Failed to parse page", e))
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::new("Failed to serialize page", e))
+    }
+
+    /// Returns the indices of the regions in reading order.
+    /// The reading order of the regions is used if every region has one, otherwise the regions are grouped into rows
+    /// from top to bottom and every row is read in the direction of the page.
+    pub fn reading_order(&self) -> Vec<usize> {
+        let mut order = (0..self.regions.len()).collect::<Vec<_>>();
+        if self.regions.iter().all(|v| v.reading_order.is_some()) {
+            order.sort_by_key(|v| self.regions[*v].reading_order);
+            return order;
+        }
+        order.sort_by(|a, b| self.regions[*a].bbox.y.total_cmp(&self.regions[*b].bbox.y));
+        let mut rows: Vec<Vec<usize>> = vec![];
+        let mut bottom = f32::NEG_INFINITY;
+        for index in order {
+            let bbox = &self.regions[index].bbox;
+            match rows.last_mut() {
+                Some(row) if bbox.center_y() < bottom => {
+                    row.push(index);
+                    bottom = bottom.max(bbox.bottom());
+                }
+                _ => {
+                    rows.push(vec![index]);
+                    bottom = bbox.bottom();
+                }
+            }
+        }
+        for row in rows.iter_mut() {
+            row.sort_by(|a, b| {
+                let (a, b) = (&self.regions[*a].bbox, &self.regions[*b].bbox);
+                match self.direction {
+                    ReadingDirection::RightToLeft => b.right().total_cmp(&a.right()),
+                    ReadingDirection::LeftToRight => a.x.total_cmp(&b.x),
+                }
+            });
+        }
+        rows.concat()
+    }
+
+    /// Translated regions in reading order, to pass the page as `Context::History` to the next page
+    pub fn history(&self) -> Vec<HistoryEntry> {
+        self.reading_order()
+            .into_iter()
+            .filter_map(|v| {
+                let region = &self.regions[v];
+                region.target_text.as_ref().map(|translation| HistoryEntry {
+                    source: region.query(),
+                    translation: translation.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Translators {
+    /// Translates the regions of a page in reading order and sets the target text.
+    /// All regions are translated in one request, so the translator sees the whole page.
+    /// The speakers are passed as `Context::Speakers`, regions without a reading order receive the computed one.
+    pub fn translate_page(
+        &self,
+        page: &mut Page,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<(), Error> {
+        let order = page.reading_order();
+        if page.regions.iter().any(|v| v.reading_order.is_none()) {
+            for (position, index) in order.iter().enumerate() {
+                page.regions[*index].reading_order = Some(position);
+            }
+        }
+        let pending = order
+            .into_iter()
+            .filter(|v| !page.regions[*v].query().is_empty())
+            .collect::<Vec<_>>();
+        let mut context = context_data.to_vec();
+        if pending.iter().any(|v| page.regions[*v].speaker.is_some()) {
+            context.push(Context::Speakers(
+                pending
+                    .iter()
+                    .map(|v| page.regions[*v].speaker.clone())
+                    .collect(),
+            ));
+        }
+        let translations = self.translate_segments(
+            pending.iter().map(|v| page.regions[*v].query()).collect(),
+            from,
+            &context,
+            options,
+        )?;
+        for (index, translation) in pending.into_iter().zip(translations) {
+            page.regions[index].target_text = Some(translation.trim().to_string());
+        }
+        Ok(())
+    }
+}
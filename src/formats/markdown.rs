@@ -0,0 +1,683 @@
+use std::ops::Range;
+
+use crate::error::Error;
+use crate::formats::markup::entity_at;
+use crate::formats::placeholders::{restore_values, split, Piece, Protector};
+use crate::formats::resources::{lines_with_offsets, Resource, ResourceEntry, ResourceFormat};
+use crate::languages::Language;
+use crate::translators::context::Context;
+use crate::translators::options::TranslationOptions;
+use crate::translators::Translators;
+
+/// Html elements that start a html block
+const BLOCK_ELEMENTS: [&str; 45] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "dd",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "iframe",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "script",
+    "section",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "textarea",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// Settings for the markdown translation
+#[derive(Clone, Debug)]
+pub struct MarkdownOptions {
+    /// Keys of the yaml front matter whose values are translated, like title
+    pub front_matter_keys: Vec<String>,
+    /// Segments that are translated in one request
+    pub batch_size: usize,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            front_matter_keys: vec![
+                "title".to_string(),
+                "description".to_string(),
+                "summary".to_string(),
+            ],
+            batch_size: 50,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum SegmentKind {
+    Text,
+    TableCell,
+    FrontMatter(ResourceEntry),
+}
+
+/// A translatable part of the document
+#[derive(Clone, Debug)]
+struct Segment {
+    range: Range<usize>,
+    text: Protector<String>,
+    kind: SegmentKind,
+}
+
+impl Segment {
+    fn render(&self, translation: &str) -> String {
+        match &self.kind {
+            SegmentKind::FrontMatter(entry) => entry.render(translation),
+            SegmentKind::Text => {
+                restore_values(&self.text, translation.trim(), |v| v.replace('\n', " "))
+            }
+            SegmentKind::TableCell => restore_values(&self.text, translation.trim(), |v| {
+                v.replace('\n', " ").replace('|', "\\|")
+            }),
+        }
+    }
+}
+
+/// Part of a line that is handled on its own
+enum Inline {
+    /// text that is kept like it is
+    Text(usize),
+    /// markup that is replaced by a placeholder
+    Value(usize),
+    /// link or image where only the label is translated
+    Link { label: Range<usize>, end: usize },
+}
+
+/// A parsed CommonMark or GFM document. Code, html blocks and urls are never changed.
+#[derive(Clone, Debug)]
+pub struct MarkdownDocument {
+    text: String,
+    segments: Vec<Segment>,
+}
+
+impl MarkdownDocument {
+    pub fn parse(text: &str, markdown_options: &MarkdownOptions) -> Self {
+        let lines = lines_with_offsets(text);
+        let mut segments = vec![];
+        let mut start = 0;
+        if let Some((end, yaml)) = front_matter_end(&lines) {
+            let offset = lines[1].0;
+            let front_matter = &text[offset..lines[end].0];
+            if let (true, Ok(resource)) =
+                (yaml, Resource::parse(front_matter, ResourceFormat::Yaml))
+            {
+                for mut entry in resource.entries {
+                    if markdown_options.front_matter_keys.contains(&entry.key)
+                        && entry.is_translatable()
+                    {
+                        entry.range = entry.range.start + offset..entry.range.end + offset;
+                        segments.push(Segment {
+                            range: entry.range.clone(),
+                            text: entry.text.clone(),
+                            kind: SegmentKind::FrontMatter(entry),
+                        });
+                    }
+                }
+            }
+            start = end + 1;
+        }
+        segments.extend(parse_blocks(text, &lines[start.min(lines.len())..]));
+        segments.sort_by_key(|v| v.range.start);
+        Self {
+            text: text.to_string(),
+            segments,
+        }
+    }
+
+    /// Returns the translatable segments, inline markup is replaced by placeholders
+    pub fn segments(&self) -> Vec<&str> {
+        self.segments.iter().map(|v| v.text.text.as_str()).collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        self.text.to_string()
+    }
+
+    /// Replaces the segments, translations are in the order of the segments
+    fn apply(&mut self, translations: Vec<String>) {
+        let mut text = String::with_capacity(self.text.len());
+        let mut last = 0;
+        for (segment, translation) in self.segments.iter().zip(translations) {
+            text.push_str(&self.text[last..segment.range.start]);
+            text.push_str(&segment.render(&translation));
+            last = segment.range.end;
+        }
+        text.push_str(&self.text[last..]);
+        self.text = text;
+    }
+}
+
+impl Translators {
+    /// Translates the paragraphs, headings, list items, table cells and image descriptions of a markdown document.
+    /// Inline markup is replaced by placeholders, code, html blocks, urls and front matter keys are kept.
+    pub fn translate_markdown(
+        &self,
+        text: &str,
+        markdown_options: &MarkdownOptions,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<String, Error> {
+        let mut document = MarkdownDocument::parse(text, markdown_options);
+        let queries = document
+            .segments
+            .iter()
+            .map(|v| v.text.text.to_string())
+            .collect::<Vec<_>>();
+        let mut translations = Vec::with_capacity(queries.len());
+        for batch in queries.chunks(markdown_options.batch_size.max(1)) {
+            translations.extend(self.translate_segments(
+                batch.to_vec(),
+                from,
+                context_data,
+                options,
+            )?);
+        }
+        document.apply(translations);
+        Ok(document.to_text())
+    }
+}
+
+/// Returns the line that closes the front matter and if it is yaml
+fn front_matter_end(lines: &[(usize, &str)]) -> Option<(usize, bool)> {
+    let first = lines.first()?.1.trim_end();
+    let closing: &[&str] = match first {
+        "---" => &["---", "..."],
+        "+++" => &["+++"],
+        _ => return None,
+    };
+    let end = lines
+        .iter()
+        .skip(1)
+        .position(|v| closing.contains(&v.1.trim_end()))?;
+    Some((end + 1, first == "---"))
+}
+
+/// Finds the segments of the block structure
+fn parse_blocks(text: &str, lines: &[(usize, &str)]) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut paragraph: Vec<Range<usize>> = vec![];
+    let mut fence: Option<(char, usize)> = None;
+    let mut html = false;
+    let mut table = false;
+    let mut blank = true;
+    let mut list_indent: Option<usize> = None;
+    let mut index = 0;
+    while index < lines.len() {
+        let (offset, line) = lines[index];
+        index += 1;
+        let prefix = quote_prefix(line);
+        let content = &line[prefix..];
+        let trimmed = content.trim_start();
+        let indent = content.len() - trimmed.len();
+        let start = offset + prefix + indent;
+        if let Some((c, len)) = fence {
+            if is_fence_close(trimmed, c, len) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.is_empty() {
+            push_segment(
+                &mut segments,
+                text,
+                std::mem::take(&mut paragraph),
+                SegmentKind::Text,
+            );
+            html = false;
+            table = false;
+            blank = true;
+            continue;
+        }
+        if html {
+            continue;
+        }
+        if std::mem::replace(&mut blank, false) && list_indent.is_some_and(|v| indent < v) {
+            list_indent = None;
+        }
+        if paragraph.is_empty() && !table && indent >= list_indent.unwrap_or(0) + 4 {
+            continue;
+        }
+        if let Some(v) = fence_open(trimmed) {
+            push_segment(
+                &mut segments,
+                text,
+                std::mem::take(&mut paragraph),
+                SegmentKind::Text,
+            );
+            fence = Some(v);
+            continue;
+        }
+        if table {
+            if trimmed.contains('|') {
+                push_table_row(&mut segments, text, start, trimmed.trim_end());
+                continue;
+            }
+            table = false;
+        }
+        if paragraph.is_empty() && is_html_block(trimmed) {
+            html = true;
+            continue;
+        }
+        if let Some(heading) = atx_heading(trimmed) {
+            push_segment(
+                &mut segments,
+                text,
+                std::mem::take(&mut paragraph),
+                SegmentKind::Text,
+            );
+            let range = start + heading.start..start + heading.end;
+            push_segment(&mut segments, text, vec![range], SegmentKind::Text);
+            continue;
+        }
+        if is_break(trimmed, !paragraph.is_empty()) {
+            push_segment(
+                &mut segments,
+                text,
+                std::mem::take(&mut paragraph),
+                SegmentKind::Text,
+            );
+            continue;
+        }
+        if paragraph.is_empty()
+            && trimmed.contains('|')
+            && lines
+                .get(index)
+                .is_some_and(|v| is_delimiter_row(&v.1[quote_prefix(v.1)..]))
+        {
+            push_table_row(&mut segments, text, start, trimmed.trim_end());
+            index += 1;
+            table = true;
+            continue;
+        }
+        if paragraph.is_empty() {
+            if let Some((label, end)) = definition(trimmed) {
+                if label.starts_with('^') {
+                    paragraph.push(start + end..offset + line.len());
+                }
+                continue;
+            }
+        }
+        if let Some(marker) = list_marker(trimmed) {
+            push_segment(
+                &mut segments,
+                text,
+                std::mem::take(&mut paragraph),
+                SegmentKind::Text,
+            );
+            list_indent = Some(indent + marker);
+            if !trimmed[marker..].trim().is_empty() {
+                paragraph.push(start + marker..offset + line.len());
+            }
+            continue;
+        }
+        paragraph.push(start..offset + line.len());
+    }
+    push_segment(&mut segments, text, paragraph, SegmentKind::Text);
+    segments
+}
+
+/// Length of the blockquote markers at the start of the line
+fn quote_prefix(line: &str) -> usize {
+    let mut end = 0;
+    loop {
+        let rest = &line[end..];
+        let trimmed = rest.trim_start_matches(' ');
+        if rest.len() - trimmed.len() > 3 || !trimmed.starts_with('>') {
+            return end;
+        }
+        end += rest.len() - trimmed.len() + 1;
+        if line[end..].starts_with(' ') {
+            end += 1;
+        }
+    }
+}
+
+fn fence_open(trimmed: &str) -> Option<(char, usize)> {
+    let c = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.len() - trimmed.trim_start_matches(c).len();
+    match len >= 3 && (c == '~' || !trimmed[len..].contains('`')) {
+        true => Some((c, len)),
+        false => None,
+    }
+}
+
+fn is_fence_close(trimmed: &str, c: char, len: usize) -> bool {
+    let rest = trimmed.trim_start_matches(c);
+    trimmed.len() - rest.len() >= len && rest.trim().is_empty()
+}
+
+fn is_html_block(trimmed: &str) -> bool {
+    let Some(rest) = trimmed.strip_prefix('<') else {
+        return false;
+    };
+    if rest.starts_with('!') || rest.starts_with('?') {
+        return true;
+    }
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    let name = rest
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return false;
+    }
+    BLOCK_ELEMENTS.contains(&name.as_str())
+        || (trimmed.ends_with('>') && !trimmed[1..].contains('<'))
+}
+
+/// Returns the range of the heading text
+fn atx_heading(trimmed: &str) -> Option<Range<usize>> {
+    let level = trimmed.len() - trimmed.trim_start_matches('#').len();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let start = trimmed.len() - rest.trim_start().len();
+    let mut content = trimmed[start..].trim_end();
+    let closed = content.trim_end_matches('#');
+    if closed.is_empty() || closed.ends_with([' ', '\t']) {
+        content = closed.trim_end();
+    }
+    Some(start..start + content.len())
+}
+
+/// Thematic breaks and setext underlines
+fn is_break(trimmed: &str, paragraph: bool) -> bool {
+    let mut chars = trimmed.chars().filter(|c| !c.is_whitespace());
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let count = 1 + chars.clone().count();
+    let same = chars.all(|c| c == first);
+    match first {
+        '=' => same && paragraph && !trimmed.trim_end().contains(' '),
+        '-' | '*' | '_' => same && count >= 3,
+        _ => false,
+    }
+}
+
+fn is_delimiter_row(line: &str) -> bool {
+    let line = line.trim();
+    line.contains('|') && line.contains('-') && line.chars().all(|c| "|:- \t".contains(c))
+}
+
+/// Returns the label and the end of a link reference or footnote definition like [id]: url
+fn definition(trimmed: &str) -> Option<(&str, usize)> {
+    let rest = trimmed.strip_prefix('[')?;
+    let end = rest.find("]:")?;
+    let label = &rest[..end];
+    match !label.is_empty() && !label.contains('[') {
+        true => {
+            let after = end + 3;
+            Some((
+                label,
+                after + trimmed[after..].len() - trimmed[after..].trim_start().len(),
+            ))
+        }
+        false => None,
+    }
+}
+
+/// Returns the length of the list marker including a task box
+fn list_marker(trimmed: &str) -> Option<usize> {
+    let digits = trimmed.len()
+        - trimmed
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    let marker = match trimmed.chars().next()? {
+        '-' | '+' | '*' => 1,
+        _ if (1..=9).contains(&digits) && trimmed[digits..].starts_with(['.', ')']) => digits + 1,
+        _ => return None,
+    };
+    let rest = &trimmed[marker..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let spaces = rest.len() - rest.trim_start().len();
+    let mut end = marker + if spaces > 4 { 1 } else { spaces };
+    if ["[ ] ", "[x] ", "[X] "]
+        .iter()
+        .any(|v| trimmed[end..].starts_with(v))
+    {
+        end += 4;
+    }
+    Some(end.min(trimmed.len()))
+}
+
+fn push_table_row(segments: &mut Vec<Segment>, text: &str, start: usize, row: &str) {
+    let mut cell_start = 0;
+    let mut code = false;
+    let mut escaped = false;
+    let mut cells = vec![];
+    for (i, c) in row.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '`' => code = !code,
+            '|' if !code => {
+                cells.push(cell_start..i);
+                cell_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    cells.push(cell_start..row.len());
+    for cell in cells {
+        let value = &row[cell.clone()];
+        let offset = start + cell.start + value.len() - value.trim_start().len();
+        let range = offset..offset + value.trim().len();
+        push_segment(segments, text, vec![range], SegmentKind::TableCell);
+    }
+}
+
+/// Adds the lines as one segment if they contain text
+fn push_segment(
+    segments: &mut Vec<Segment>,
+    text: &str,
+    lines: Vec<Range<usize>>,
+    kind: SegmentKind,
+) {
+    let Some(first) = lines.first() else {
+        return;
+    };
+    let mut protector = Protector::new();
+    let mut end = first.start;
+    for (i, line) in lines.iter().enumerate() {
+        let raw = &text[line.clone()];
+        let mut content = raw.trim_end();
+        match lines.get(i + 1) {
+            Some(next) => {
+                let hard = raw.ends_with("  ") || content.ends_with('\\');
+                if hard {
+                    content = content.strip_suffix('\\').unwrap_or(content);
+                }
+                push_inline(&mut protector, content);
+                match hard {
+                    true => protector
+                        .push_value(text[line.start + content.len()..next.start].to_string()),
+                    false => protector.text.push(' '),
+                }
+            }
+            None => {
+                push_inline(&mut protector, content);
+                end = line.start + content.len();
+            }
+        }
+    }
+    let has_text = split(&protector.text)
+        .iter()
+        .any(|v| matches!(v, Piece::Text(v) if v.chars().any(|c| c.is_alphanumeric())));
+    if has_text {
+        segments.push(Segment {
+            range: first.start..end,
+            text: protector,
+            kind,
+        });
+    }
+}
+
+/// Adds the text of a line, inline markup is protected
+fn push_inline(protector: &mut Protector<String>, text: &str) {
+    let mut plain = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        match inline_at(text, pos) {
+            Some(Inline::Text(len)) => pos += len,
+            Some(Inline::Value(len)) => {
+                protector.push_text(&text[plain..pos], |v| v);
+                protector.push_value(text[pos..pos + len].to_string());
+                pos += len;
+                plain = pos;
+            }
+            Some(Inline::Link { label, end }) => {
+                protector.push_text(&text[plain..pos], |v| v);
+                protector.push_value(text[pos..label.start].to_string());
+                push_inline(protector, &text[label.clone()]);
+                protector.push_value(text[label.end..end].to_string());
+                pos = end;
+                plain = pos;
+            }
+            None => pos += text[pos..].chars().next().map_or(1, |c| c.len_utf8()),
+        }
+    }
+    protector.push_text(&text[plain..], |v| v);
+}
+
+fn inline_at(text: &str, pos: usize) -> Option<Inline> {
+    let rest = &text[pos..];
+    let before = text[..pos].chars().next_back();
+    match rest.chars().next()? {
+        '\\' => rest[1..]
+            .chars()
+            .next()
+            .filter(|c| c.is_ascii_punctuation())
+            .map(|_| Inline::Value(2)),
+        '`' => {
+            let len = rest.len() - rest.trim_start_matches('`').len();
+            let mut search = len;
+            while let Some(found) = rest[search..].find('`') {
+                let start = search + found;
+                let run = rest[start..].len() - rest[start..].trim_start_matches('`').len();
+                if run == len {
+                    return Some(Inline::Value(start + run));
+                }
+                search = start + run;
+            }
+            Some(Inline::Text(len))
+        }
+        '<' => {
+            let valid = rest[1..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!');
+            let end = rest.find('>')?;
+            match valid && !rest[1..end].contains('<') {
+                true => Some(Inline::Value(end + 1)),
+                false => None,
+            }
+        }
+        '!' if rest.starts_with("![") => link_at(text, pos + 1),
+        '[' if rest.starts_with("[^") => rest.find(']').map(|v| Inline::Value(v + 1)),
+        '[' => link_at(text, pos),
+        c @ ('*' | '_' | '~') => {
+            let len = rest.len() - rest.trim_start_matches(c).len();
+            let after = rest[len..].chars().next();
+            let space = |v: Option<char>| v.filter(|c| !c.is_whitespace()).is_none();
+            let word = |v: Option<char>| v.is_some_and(char::is_alphanumeric);
+            match (space(before) && space(after))
+                || (c == '_' && word(before) && word(after))
+                || (c == '~' && len < 2)
+            {
+                true => Some(Inline::Text(len)),
+                false => Some(Inline::Value(len)),
+            }
+        }
+        '&' => entity_at(rest).map(|v| Inline::Value(v.len())),
+        'h' | 'w'
+            if ["https://", "http://", "www."]
+                .iter()
+                .any(|v| rest.starts_with(v))
+                && !before.is_some_and(char::is_alphanumeric) =>
+        {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '<')
+                .unwrap_or(rest.len());
+            let url = rest[..end].trim_end_matches(['.', ',', ':', ';', '!', '?', ')', '\'', '"']);
+            Some(Inline::Value(url.len()))
+        }
+        _ => None,
+    }
+}
+
+/// Finds a link like [label](url) or [label][id], `bracket` is the position of [
+fn link_at(text: &str, bracket: usize) -> Option<Inline> {
+    let close = matching(text, bracket, '[', ']')?;
+    let end = match text[close + 1..].chars().next()? {
+        '(' => matching(text, close + 1, '(', ')')?,
+        '[' => close + 1 + text[close + 1..].find(']')?,
+        _ => return None,
+    };
+    Some(Inline::Link {
+        label: bracket + 1..close,
+        end: end + 1,
+    })
+}
+
+/// Returns the position of the closing bracket
+fn matching(text: &str, start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == open => depth += 1,
+            _ if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
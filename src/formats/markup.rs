@@ -0,0 +1,930 @@
+use log::warn;
+
+use crate::error::Error;
+use crate::formats::placeholders::{split, Piece, Protector};
+use crate::languages::Language;
+use crate::translators::context::Context;
+use crate::translators::options::{TextFormat, TranslationOption, TranslationOptions};
+use crate::translators::Translators;
+
+/// Elements without content in html
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements where the content is raw text in html
+const RAW_TEXT_ELEMENTS: [&str; 3] = ["script", "style", "textarea"];
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkupKind {
+    #[default]
+    Html,
+    Xml,
+}
+
+/// Defines which parts of the document are translated
+#[derive(Clone, Debug)]
+pub struct MarkupOptions {
+    pub kind: MarkupKind,
+    /// Elements that are part of the surrounding text, like <b>. All other elements split the text.
+    pub inline_elements: Vec<String>,
+    /// Elements where the content is never translated, like <code>
+    pub skipped_elements: Vec<String>,
+    /// Attributes that are translated, like alt
+    pub attributes: Vec<String>,
+}
+
+impl Default for MarkupOptions {
+    fn default() -> Self {
+        Self::html()
+    }
+}
+
+impl MarkupOptions {
+    /// Default settings for html
+    pub fn html() -> Self {
+        Self {
+            kind: MarkupKind::Html,
+            inline_elements: to_strings(&[
+                "a", "abbr", "b", "bdi", "bdo", "br", "cite", "code", "data", "dfn", "em", "font",
+                "i", "img", "kbd", "label", "mark", "q", "s", "samp", "small", "span", "strong",
+                "sub", "sup", "time", "u", "var", "wbr",
+            ]),
+            skipped_elements: to_strings(&[
+                "script", "style", "textarea", "code", "kbd", "samp", "var", "pre", "svg", "math",
+                "template",
+            ]),
+            attributes: to_strings(&["alt", "title", "placeholder", "aria-label"]),
+        }
+    }
+
+    /// Default settings for xml, the inline elements are based on docbook
+    pub fn xml() -> Self {
+        Self {
+            kind: MarkupKind::Xml,
+            inline_elements: to_strings(&[
+                "emphasis",
+                "phrase",
+                "link",
+                "ulink",
+                "xref",
+                "olink",
+                "literal",
+                "code",
+                "command",
+                "filename",
+                "option",
+                "parameter",
+                "replaceable",
+                "userinput",
+                "computeroutput",
+                "guilabel",
+                "guibutton",
+                "guimenu",
+                "guimenuitem",
+                "guisubmenu",
+                "keycap",
+                "keycombo",
+                "menuchoice",
+                "quote",
+                "trademark",
+                "superscript",
+                "subscript",
+                "acronym",
+                "abbrev",
+                "citetitle",
+                "firstterm",
+                "glossterm",
+                "foreignphrase",
+                "wordasword",
+                "inlinemediaobject",
+                "footnoteref",
+                "anchor",
+                "indexterm",
+            ]),
+            skipped_elements: to_strings(&[
+                "programlisting",
+                "screen",
+                "synopsis",
+                "code",
+                "literal",
+                "command",
+                "filename",
+                "computeroutput",
+                "userinput",
+                "option",
+                "indexterm",
+            ]),
+            attributes: to_strings(&["alt", "title", "xreflabel"]),
+        }
+    }
+
+    fn text_format(&self) -> TextFormat {
+        match self.kind {
+            MarkupKind::Html => TextFormat::Html,
+            MarkupKind::Xml => TextFormat::Xml,
+        }
+    }
+
+    fn is_html(&self) -> bool {
+        self.kind == MarkupKind::Html
+    }
+
+    fn contains(&self, list: &[String], name: &str) -> bool {
+        list.iter().any(|v| self.name_eq(v, name))
+    }
+
+    fn name_eq(&self, a: &str, b: &str) -> bool {
+        match self.kind {
+            MarkupKind::Html => a.eq_ignore_ascii_case(b),
+            MarkupKind::Xml => a == b,
+        }
+    }
+
+    fn is_void(&self, tag: &Tag) -> bool {
+        tag.kind == TagKind::SelfClosing
+            || (self.is_html()
+                && tag.kind == TagKind::Open
+                && VOID_ELEMENTS
+                    .iter()
+                    .any(|v| v.eq_ignore_ascii_case(&tag.name)))
+    }
+
+    fn is_inline(&self, tag: &Tag) -> bool {
+        self.contains(&self.inline_elements, &tag.name)
+    }
+
+    fn is_skipped(&self, tag: &Tag) -> bool {
+        let no_translate = tag.attributes.iter().any(|v| {
+            (v.name.eq_ignore_ascii_case("translate") && v.value.eq_ignore_ascii_case("no"))
+                || (self.is_html()
+                    && v.name.eq_ignore_ascii_case("class")
+                    && v.value.split_whitespace().any(|v| v == "notranslate"))
+        });
+        no_translate || self.contains(&self.skipped_elements, &tag.name)
+    }
+}
+
+fn to_strings(v: &[&str]) -> Vec<String> {
+    v.iter().map(|v| v.to_string()).collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TagKind {
+    Open,
+    Close,
+    SelfClosing,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Attribute {
+    pub(crate) name: String,
+    /// value as written in the document
+    pub(crate) value: String,
+    /// position of the value in the raw tag
+    pub(crate) range: std::ops::Range<usize>,
+    pub(crate) quoted: bool,
+    /// translated value, already escaped
+    pub(crate) translation: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Tag {
+    pub(crate) raw: String,
+    pub(crate) name: String,
+    pub(crate) kind: TagKind,
+    pub(crate) attributes: Vec<Attribute>,
+}
+
+impl Tag {
+    /// Returns the value of the attribute as written in the document
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|v| v.name == name)
+            .map(|v| v.value.as_str())
+    }
+
+    /// Name without the namespace prefix
+    pub(crate) fn local_name(&self) -> &str {
+        self.name.rsplit(':').next().unwrap_or_default()
+    }
+
+    pub(crate) fn to_markup(&self) -> String {
+        let mut raw = self.raw.to_string();
+        for attribute in self.attributes.iter().rev() {
+            if let Some(v) = &attribute.translation {
+                let value = match attribute.quoted {
+                    true => v.to_string(),
+                    false => format!("\"{}\"", v),
+                };
+                raw.replace_range(attribute.range.clone(), &value);
+            }
+        }
+        raw
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Node {
+    Text(String),
+    Tag(Tag),
+    Raw(String),
+}
+
+impl Node {
+    /// The node as written in the document
+    pub(crate) fn raw(&self) -> &str {
+        match self {
+            Node::Text(v) | Node::Raw(v) => v,
+            Node::Tag(v) => &v.raw,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Unit {
+    Text(String),
+    Tag(usize),
+    Raw(String),
+}
+
+#[derive(Clone, Debug)]
+enum Part {
+    Raw(String),
+    Tag(usize),
+    Segment(Vec<Unit>),
+}
+
+/// Value behind a placeholder
+#[derive(Clone, Debug)]
+enum Value {
+    Tag(usize),
+    Raw(String),
+}
+
+/// Text of a segment that is sent to the translator
+struct SourceSegment {
+    part: usize,
+    prefix: String,
+    suffix: String,
+    text: Protector<Value>,
+}
+
+/// A parsed html or xml document
+pub struct MarkupDocument {
+    options: MarkupOptions,
+    parts: Vec<Part>,
+    tags: Vec<Tag>,
+}
+
+impl MarkupDocument {
+    /// Parses the document and groups text and inline elements into segments
+    pub fn parse(text: &str, options: &MarkupOptions) -> Self {
+        let nodes = tokenize(text, options);
+        let mut document = Self {
+            options: options.clone(),
+            parts: vec![],
+            tags: vec![],
+        };
+        let mut current = vec![];
+        let mut i = 0;
+        while i < nodes.len() {
+            match &nodes[i] {
+                Node::Text(v) => current.push(Unit::Text(v.to_string())),
+                Node::Raw(v) => match current.is_empty() {
+                    true => document.parts.push(Part::Raw(v.to_string())),
+                    false => current.push(Unit::Raw(v.to_string())),
+                },
+                Node::Tag(tag) => {
+                    let inline = options.is_inline(tag);
+                    if tag.kind == TagKind::Open && options.is_skipped(tag) {
+                        let end = document.find_close(&nodes, i);
+                        let raw = nodes[i..=end]
+                            .iter()
+                            .map(|v| match v {
+                                Node::Text(v) | Node::Raw(v) => v.to_string(),
+                                Node::Tag(v) => v.raw.to_string(),
+                            })
+                            .collect::<String>();
+                        match inline {
+                            true => current.push(Unit::Raw(raw)),
+                            false => {
+                                document.flush(&mut current);
+                                document.parts.push(Part::Raw(raw));
+                            }
+                        }
+                        i = end + 1;
+                        continue;
+                    }
+                    document.tags.push(tag.clone());
+                    let index = document.tags.len() - 1;
+                    match inline {
+                        true => current.push(Unit::Tag(index)),
+                        false => {
+                            document.flush(&mut current);
+                            document.parts.push(Part::Tag(index));
+                        }
+                    }
+                }
+            }
+            i += 1;
+        }
+        document.flush(&mut current);
+        document
+    }
+
+    /// Returns the document as text
+    pub fn to_markup(&self) -> String {
+        self.parts
+            .iter()
+            .map(|v| match v {
+                Part::Raw(v) => v.to_string(),
+                Part::Tag(v) => self.tags[*v].to_markup(),
+                Part::Segment(units) => units
+                    .iter()
+                    .map(|v| self.unit_to_markup(v))
+                    .collect::<String>(),
+            })
+            .collect()
+    }
+
+    /// Returns the index of the closing tag that belongs to the opening tag at index
+    fn find_close(&self, nodes: &[Node], index: usize) -> usize {
+        let name = match &nodes[index] {
+            Node::Tag(v) if !self.options.is_void(v) => &v.name,
+            _ => return index,
+        };
+        let mut depth = 0;
+        for (i, node) in nodes.iter().enumerate().skip(index + 1) {
+            if let Node::Tag(tag) = node {
+                if !self.options.name_eq(&tag.name, name) {
+                    continue;
+                }
+                match tag.kind {
+                    TagKind::Open => depth += 1,
+                    TagKind::Close if depth == 0 => return i,
+                    TagKind::Close => depth -= 1,
+                    TagKind::SelfClosing => {}
+                }
+            }
+        }
+        index
+    }
+
+    /// Adds the collected units as segment, when they contain text
+    fn flush(&mut self, current: &mut Vec<Unit>) {
+        let units = std::mem::take(current);
+        let has_text = units.iter().any(|v| match v {
+            Unit::Text(v) => decode_entities(v).chars().any(|c| c.is_alphanumeric()),
+            _ => false,
+        });
+        if has_text {
+            self.parts.push(Part::Segment(units));
+            return;
+        }
+        for unit in units {
+            self.parts.push(match unit {
+                Unit::Text(v) | Unit::Raw(v) => Part::Raw(v),
+                Unit::Tag(v) => Part::Tag(v),
+            });
+        }
+    }
+
+    fn unit_to_markup(&self, unit: &Unit) -> String {
+        match unit {
+            Unit::Text(v) | Unit::Raw(v) => v.to_string(),
+            Unit::Tag(v) => self.tags[*v].to_markup(),
+        }
+    }
+
+    fn value_to_markup(&self, value: &Value) -> String {
+        match value {
+            Value::Tag(v) => self.tags[*v].to_markup(),
+            Value::Raw(v) => v.to_string(),
+        }
+    }
+
+    /// Generates the text of every segment.
+    /// With native markup support the segments contain the inline tags with the translated attributes,
+    /// otherwise they are replaced by placeholders.
+    fn source_segments(&self, native: bool) -> Vec<SourceSegment> {
+        let mut segments = vec![];
+        for (part, v) in self.parts.iter().enumerate() {
+            let units = match v {
+                Part::Segment(v) => v,
+                _ => continue,
+            };
+            let mut text = Protector::new();
+            for unit in units {
+                match (unit, native) {
+                    (Unit::Text(v), true) => text.text.push_str(&collapse_whitespace(v)),
+                    (Unit::Text(v), false) => push_decoded(&mut text, &collapse_whitespace(v)),
+                    (Unit::Tag(v), true) => text.text.push_str(&self.tags[*v].to_markup()),
+                    (Unit::Tag(v), false) => text.push_value(Value::Tag(*v)),
+                    (Unit::Raw(v), true) => text.text.push_str(v),
+                    (Unit::Raw(v), false) => text.push_value(Value::Raw(v.to_string())),
+                }
+            }
+            let trimmed = text
+                .text
+                .trim_start_matches(|c: char| c.is_ascii_whitespace());
+            let prefix = text.text[..text.text.len() - trimmed.len()].to_string();
+            let trimmed = trimmed.trim_end_matches(|c: char| c.is_ascii_whitespace());
+            let suffix = text.text[prefix.len() + trimmed.len()..].to_string();
+            text.text = trimmed.to_string();
+            segments.push(SourceSegment {
+                part,
+                prefix,
+                suffix,
+                text,
+            });
+        }
+        segments
+    }
+
+    /// Returns the position and decoded text of every attribute that should be translated
+    fn source_attributes(&self) -> Vec<(usize, usize, Protector<Value>)> {
+        let mut attributes = vec![];
+        for (t, tag) in self.tags.iter().enumerate() {
+            for (a, attribute) in tag.attributes.iter().enumerate() {
+                if !self
+                    .options
+                    .contains(&self.options.attributes, &attribute.name)
+                {
+                    continue;
+                }
+                if !attribute.value.chars().any(|c| c.is_alphanumeric()) {
+                    continue;
+                }
+                let mut text = Protector::new();
+                push_decoded(&mut text, &attribute.value);
+                attributes.push((t, a, text));
+            }
+        }
+        attributes
+    }
+
+    fn apply_attribute(
+        &mut self,
+        tag: usize,
+        attribute: usize,
+        text: &Protector<Value>,
+        translation: &str,
+    ) {
+        let pieces = split(translation);
+        let value = match text.is_complete(&pieces) {
+            true => pieces
+                .iter()
+                .map(|v| match v {
+                    Piece::Text(v) => escape_attribute(v),
+                    Piece::Placeholder(v) => self.value_to_markup(&text.values[*v]),
+                })
+                .collect(),
+            false => {
+                warn!(
+                    "Placeholders in attribute translation \"{}\" dont match",
+                    translation
+                );
+                escape_attribute(&remove_placeholders(&pieces))
+            }
+        };
+        self.tags[tag].attributes[attribute].translation = Some(value);
+    }
+
+    fn apply_attributes(
+        &mut self,
+        attributes: &[(usize, usize, Protector<Value>)],
+        translations: Vec<String>,
+    ) {
+        for ((tag, attribute, text), translation) in attributes.iter().zip(translations) {
+            self.apply_attribute(*tag, *attribute, text, &translation);
+        }
+    }
+
+    fn apply_segments(
+        &mut self,
+        segments: &[SourceSegment],
+        translations: Vec<String>,
+        native: bool,
+    ) {
+        for (segment, translation) in segments.iter().zip(translations) {
+            self.apply_segment(segment, &translation, native);
+        }
+    }
+
+    fn apply_segment(&mut self, segment: &SourceSegment, translation: &str, native: bool) {
+        let body = match native {
+            true => translation.to_string(),
+            false => self.restore_segment(segment, translation),
+        };
+        self.parts[segment.part] =
+            Part::Raw(format!("{}{}{}", segment.prefix, body, segment.suffix));
+    }
+
+    /// Replaces the placeholders with the tags.
+    /// When placeholders are missing or tags are in the wrong order, the tags keep their original order.
+    fn restore_segment(&self, segment: &SourceSegment, translation: &str) -> String {
+        let pieces = split(translation);
+        let order = |pieces: &[Piece]| -> Vec<usize> {
+            pieces
+                .iter()
+                .filter_map(|v| match v {
+                    Piece::Placeholder(v) => Some(*v),
+                    _ => None,
+                })
+                .collect()
+        };
+        let original_order = (0..segment.text.values.len()).collect::<Vec<_>>();
+        let translated_order = order(&pieces);
+        let valid = segment.text.is_complete(&pieces)
+            && match self.is_balanced(&segment.text.values, &original_order) {
+                true => self.is_balanced(&segment.text.values, &translated_order),
+                false => original_order == translated_order,
+            };
+        if valid {
+            return pieces
+                .iter()
+                .map(|v| match v {
+                    Piece::Text(v) => escape_text(v),
+                    Piece::Placeholder(v) => self.value_to_markup(&segment.text.values[*v]),
+                })
+                .collect();
+        }
+        warn!("Placeholders in translation \"{}\" dont match", translation);
+        let text = escape_text(&remove_placeholders(&pieces));
+        let first_text = split(&segment.text.text)
+            .iter()
+            .position(|v| matches!(v, Piece::Text(_)))
+            .unwrap_or(0);
+        let mut result = String::new();
+        for (i, value) in segment.text.values.iter().enumerate() {
+            if i == first_text {
+                result.push_str(&text);
+            }
+            result.push_str(&self.value_to_markup(value));
+        }
+        if first_text >= segment.text.values.len() {
+            result.push_str(&text);
+        }
+        result
+    }
+
+    /// Checks if the opening and closing tags are nested correctly
+    fn is_balanced(&self, values: &[Value], order: &[usize]) -> bool {
+        let mut stack = vec![];
+        for i in order {
+            let tag = match &values[*i] {
+                Value::Tag(v) => &self.tags[*v],
+                Value::Raw(_) => continue,
+            };
+            match tag.kind {
+                TagKind::Open if !self.options.is_void(tag) => stack.push(&tag.name),
+                TagKind::Close => match stack.pop() {
+                    Some(v) if self.options.name_eq(v, &tag.name) => {}
+                    _ => return false,
+                },
+                _ => {}
+            }
+        }
+        stack.is_empty()
+    }
+}
+
+impl Translators {
+    /// Translates the text and the translatable attributes of a html or xml document.
+    /// Text and inline elements are translated in a single batch. Translators that support html or xml
+    /// receive the inline tags directly, for all other translators the tags are replaced by placeholders.
+    pub fn translate_markup(
+        &self,
+        text: &str,
+        markup: &MarkupOptions,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<String, Error> {
+        let mut document = MarkupDocument::parse(text, markup);
+        let native_options = TranslationOptions {
+            format: Some(markup.text_format()),
+            ..options.clone()
+        };
+        let native = !self
+            .get_ignored_options(&native_options)
+            .iter()
+            .any(|v| v.1.contains(&TranslationOption::Format));
+        let attributes = document.source_attributes();
+        let attribute_queries = attributes
+            .iter()
+            .map(|v| v.2.text.to_string())
+            .collect::<Vec<_>>();
+        if native {
+            // the inline tags are sent as they are, so their attributes are translated first
+            let attribute_translations =
+                self.translate_segments(attribute_queries, from, context_data, options)?;
+            document.apply_attributes(&attributes, attribute_translations);
+            let segments = document.source_segments(true);
+            let segment_translations = self.translate_segments(
+                segments.iter().map(|v| v.text.text.to_string()).collect(),
+                from,
+                context_data,
+                &native_options,
+            )?;
+            document.apply_segments(&segments, segment_translations, true);
+        } else {
+            let segments = document.source_segments(false);
+            let count = segments.len();
+            let mut queries = segments
+                .iter()
+                .map(|v| v.text.text.to_string())
+                .collect::<Vec<_>>();
+            queries.extend(attribute_queries);
+            let mut segment_translations =
+                self.translate_segments(queries, from, context_data, options)?;
+            let attribute_translations = segment_translations.split_off(count);
+            document.apply_attributes(&attributes, attribute_translations);
+            document.apply_segments(&segments, segment_translations, false);
+        }
+        Ok(document.to_markup())
+    }
+}
+
+/// Splits the document into text, tags and raw parts like comments
+pub(crate) fn tokenize(text: &str, options: &MarkupOptions) -> Vec<Node> {
+    let mut nodes = vec![];
+    let mut pos = 0;
+    let mut text_start = 0;
+    while let Some(offset) = text[pos..].find('<') {
+        pos += offset;
+        let (node, end) = match parse_markup(text, pos) {
+            Some(v) => v,
+            None => {
+                pos += 1;
+                continue;
+            }
+        };
+        if text_start < pos {
+            nodes.push(Node::Text(text[text_start..pos].to_string()));
+        }
+        pos = end;
+        text_start = end;
+        let raw_text = match &node {
+            Node::Tag(tag) if options.is_html() && tag.kind == TagKind::Open => RAW_TEXT_ELEMENTS
+                .iter()
+                .find(|v| v.eq_ignore_ascii_case(&tag.name))
+                .map(|v| format!("</{}", v)),
+            _ => None,
+        };
+        nodes.push(node);
+        if let Some(close) = raw_text {
+            let end = text[pos..]
+                .to_ascii_lowercase()
+                .find(&close)
+                .map(|v| v + pos)
+                .unwrap_or(text.len());
+            if pos < end {
+                nodes.push(Node::Raw(text[pos..end].to_string()));
+            }
+            pos = end;
+            text_start = end;
+        }
+    }
+    if text_start < text.len() {
+        nodes.push(Node::Text(text[text_start..].to_string()));
+    }
+    nodes
+}
+
+/// Parses the markup at pos and returns the node and the end of the node
+fn parse_markup(text: &str, pos: usize) -> Option<(Node, usize)> {
+    let rest = &text[pos..];
+    let raw_until = |start: usize, end: &str| -> (Node, usize) {
+        let end = rest[start..]
+            .find(end)
+            .map(|v| pos + start + v + end.len())
+            .unwrap_or(text.len());
+        (Node::Raw(text[pos..end].to_string()), end)
+    };
+    if rest.starts_with("<!--") {
+        return Some(raw_until(4, "-->"));
+    }
+    if rest.starts_with("<![CDATA[") {
+        return Some(raw_until(9, "]]>"));
+    }
+    if rest.starts_with("<?") {
+        return Some(raw_until(2, "?>"));
+    }
+    if rest.starts_with("<!") {
+        return Some(raw_until(2, ">"));
+    }
+    let bytes = rest.as_bytes();
+    let close = bytes.get(1) == Some(&b'/');
+    let name_start = if close { 2 } else { 1 };
+    let is_name_char = |c: u8| c.is_ascii_alphanumeric() || matches!(c, b'_' | b':' | b'-' | b'.');
+    match bytes.get(name_start) {
+        Some(c) if c.is_ascii_alphabetic() || *c == b'_' || *c == b':' => {}
+        _ => return None,
+    }
+    let mut i = name_start;
+    while i < bytes.len() && is_name_char(bytes[i]) {
+        i += 1;
+    }
+    let name = rest[name_start..i].to_string();
+    let mut attributes = vec![];
+    let kind = loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        match bytes.get(i) {
+            None => return None,
+            Some(b'>') => {
+                i += 1;
+                break match close {
+                    true => TagKind::Close,
+                    false => TagKind::Open,
+                };
+            }
+            Some(b'/') if bytes.get(i + 1) == Some(&b'>') => {
+                i += 2;
+                break TagKind::SelfClosing;
+            }
+            _ => {}
+        }
+        let attribute_start = i;
+        while i < bytes.len()
+            && !bytes[i].is_ascii_whitespace()
+            && !matches!(bytes[i], b'=' | b'>' | b'/')
+        {
+            i += 1;
+        }
+        if attribute_start == i {
+            i += 1;
+            continue;
+        }
+        let attribute_name = rest[attribute_start..i].to_string();
+        let mut j = i;
+        while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        if bytes.get(j) != Some(&b'=') {
+            attributes.push(Attribute {
+                name: attribute_name,
+                value: String::new(),
+                range: i..i,
+                quoted: false,
+                translation: None,
+            });
+            continue;
+        }
+        i = j + 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let (range, quoted) = match bytes.get(i) {
+            Some(q) if *q == b'"' || *q == b'\'' => {
+                let end = rest[i + 1..].find(*q as char)? + i + 1;
+                let range = i + 1..end;
+                i = end + 1;
+                (range, true)
+            }
+            _ => {
+                let start = i;
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                    i += 1;
+                }
+                (start..i, false)
+            }
+        };
+        attributes.push(Attribute {
+            name: attribute_name,
+            value: rest[range.clone()].to_string(),
+            range,
+            quoted,
+            translation: None,
+        });
+    };
+    Some((
+        Node::Tag(Tag {
+            raw: rest[..i].to_string(),
+            name,
+            kind,
+            attributes,
+        }),
+        pos + i,
+    ))
+}
+
+/// Replaces every run of whitespace with a single space
+fn collapse_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_whitespace = false;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !last_whitespace {
+                result.push(' ');
+            }
+            last_whitespace = true;
+        } else {
+            result.push(c);
+            last_whitespace = false;
+        }
+    }
+    result
+}
+
+/// Decodes the entity without & and ;
+pub(crate) fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(v) = entity.strip_prefix('#') {
+        let code = match v.strip_prefix('x').or_else(|| v.strip_prefix('X')) {
+            Some(v) => u32::from_str_radix(v, 16).ok()?,
+            None => v.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "hellip" => '…',
+        "mdash" => '—',
+        "ndash" => '–',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "euro" => '€',
+        "middot" => '·',
+        "bull" => '•',
+        _ => return None,
+    })
+}
+
+/// Returns the entity at the start of the text, like &amp;
+pub(crate) fn entity_at(text: &str) -> Option<&str> {
+    let end = text.char_indices().take(12).find(|v| v.1 == ';')?.0;
+    let name = &text[1..end];
+    match !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '#') {
+        true => Some(&text[..=end]),
+        false => None,
+    }
+}
+
+/// Decodes all known entities, for checks only
+fn decode_entities(text: &str) -> String {
+    let mut protector: Protector<Value> = Protector::new();
+    push_decoded(&mut protector, text);
+    protector.text
+}
+
+/// Adds the text with decoded entities, unknown entities are protected
+fn push_decoded(protector: &mut Protector<Value>, text: &str) {
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        protector.push_text(&rest[..start], Value::Raw);
+        rest = &rest[start..];
+        match entity_at(rest) {
+            Some(entity) => {
+                match decode_entity(&entity[1..entity.len() - 1]) {
+                    Some(c) => protector.text.push(c),
+                    None => protector.push_value(Value::Raw(entity.to_string())),
+                }
+                rest = &rest[entity.len()..];
+            }
+            None => {
+                protector.text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    protector.push_text(rest, Value::Raw);
+}
+
+fn remove_placeholders(pieces: &[Piece]) -> String {
+    pieces
+        .iter()
+        .filter_map(|v| match v {
+            Piece::Text(v) => Some(v.as_str()),
+            Piece::Placeholder(_) => None,
+        })
+        .collect()
+}
+
+pub(crate) fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub(crate) fn escape_attribute(text: &str) -> String {
+    escape_text(text)
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
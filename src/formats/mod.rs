@@ -0,0 +1,8 @@
+pub mod manga;
+pub mod markdown;
+pub mod markup;
+pub mod placeholders;
+pub mod po;
+pub mod resources;
+pub mod subtitles;
+pub mod xliff;
@@ -0,0 +1,227 @@
+/// Part of a translated text with placeholders
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Piece {
+    Text(String),
+    Placeholder(usize),
+}
+
+/// Builds a text where protected values are replaced by placeholders like {0}.
+/// Text that already looks like a placeholder is protected as well, so it survives the round trip.
+#[derive(Clone, Debug)]
+pub struct Protector<T> {
+    pub text: String,
+    pub values: Vec<T>,
+}
+
+impl<T> Default for Protector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Protector<T> {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            values: vec![],
+        }
+    }
+
+    /// Adds text, existing placeholders in the text are converted into values with `literal`
+    pub fn push_text(&mut self, text: &str, literal: impl Fn(String) -> T) {
+        for piece in split(text) {
+            match piece {
+                Piece::Text(v) => self.text.push_str(&v),
+                Piece::Placeholder(v) => self.push_value(literal(placeholder(v))),
+            }
+        }
+    }
+
+    /// Adds a value that is replaced by a placeholder
+    pub fn push_value(&mut self, value: T) {
+        self.text.push_str(&placeholder(self.values.len()));
+        self.values.push(value);
+    }
+
+    /// Checks that every placeholder appears exactly once in the translation
+    pub fn is_complete(&self, pieces: &[Piece]) -> bool {
+        let mut seen = vec![false; self.values.len()];
+        for piece in pieces {
+            if let Piece::Placeholder(i) = piece {
+                match seen.get_mut(*i) {
+                    Some(v) if !*v => *v = true,
+                    _ => return false,
+                }
+            }
+        }
+        seen.into_iter().all(|v| v)
+    }
+}
+
+/// Generates the placeholder for the index
+pub fn placeholder(index: usize) -> String {
+    format!("{{{}}}", index)
+}
+
+/// Splits a text into text and placeholders
+pub fn split(text: &str) -> Vec<Piece> {
+    let mut pieces = vec![];
+    let mut current = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        current.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let digits = rest[1..]
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_digit())
+            .map(|(i, c)| (i, c == '}'));
+        match digits {
+            Some((len, true)) if len > 0 => {
+                if let Ok(index) = rest[1..len + 1].parse() {
+                    if !current.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut current)));
+                    }
+                    pieces.push(Piece::Placeholder(index));
+                    rest = &rest[len + 2..];
+                    continue;
+                }
+                current.push('{');
+                rest = &rest[1..];
+            }
+            _ => {
+                current.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    current.push_str(rest);
+    if !current.is_empty() {
+        pieces.push(Piece::Text(current));
+    }
+    pieces
+}
+
+/// Replaces the placeholders with the values and encodes the text in between.
+/// Values that are missing in the translation are appended.
+pub fn restore_values(
+    text: &Protector<String>,
+    translation: &str,
+    encode: impl Fn(&str) -> String,
+) -> String {
+    let mut used = vec![false; text.values.len()];
+    let mut result = split(translation)
+        .iter()
+        .map(|v| match v {
+            Piece::Text(v) => encode(v),
+            Piece::Placeholder(v) if *v < text.values.len() => {
+                used[*v] = true;
+                text.values[*v].to_string()
+            }
+            Piece::Placeholder(v) => encode(&placeholder(*v)),
+        })
+        .collect::<String>();
+    for (i, value) in text.values.iter().enumerate() {
+        if !used[i] {
+            result.push(' ');
+            result.push_str(value);
+        }
+    }
+    result
+}
+
+/// Adds the text and protects interpolations like {{name}}, {name}, %{name}, %1$s, $t(key), @:key and html tags.
+/// The values are stored encoded with `encode`.
+pub fn push_interpolated(
+    protector: &mut Protector<String>,
+    text: &str,
+    encode: impl Fn(&str) -> String,
+) {
+    let mut start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let c = rest.chars().next().unwrap_or_default();
+        match interpolation_end(rest) {
+            Some(end) => {
+                protector.push_text(&text[start..i], |v| encode(&v));
+                protector.push_value(encode(&rest[..end]));
+                i += end;
+                start = i;
+            }
+            None => i += c.len_utf8(),
+        }
+    }
+    protector.push_text(&text[start..], |v| encode(&v));
+}
+
+/// Returns the length of the interpolation at the start of the text
+fn interpolation_end(text: &str) -> Option<usize> {
+    let mut chars = text.chars();
+    let first = chars.next()?;
+    let second = chars.next();
+    match (first, second) {
+        ('{', Some('{')) => text.find("}}").map(|v| v + 2),
+        ('{', _) => brace_end(text),
+        ('%', Some('{')) => text.find('}').map(|v| v + 1),
+        ('%', Some('<')) => {
+            let end = text.find('>')? + 1;
+            let conversion = text[end..]
+                .chars()
+                .next()
+                .filter(|c| c.is_ascii_alphabetic())
+                .map(|_| 1)
+                .unwrap_or(0);
+            Some(end + conversion)
+        }
+        ('%', Some('#')) if text[1..].starts_with("#@") => text[3..].find('@').map(|v| v + 4),
+        ('%', _) => printf_end(text).filter(|v| !text[..*v].contains(' ')),
+        ('$', Some('t')) if text.starts_with("$t(") => text.find(')').map(|v| v + 1),
+        ('@', Some(':' | '.')) => {
+            let end = text
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !(c.is_alphanumeric() || matches!(c, '.' | ':' | '_' | '-')))
+                .map(|v| v.0)
+                .unwrap_or(text.len());
+            match text[..end].contains(':') && end > 2 {
+                true => Some(end),
+                false => None,
+            }
+        }
+        ('<', Some(c)) if c.is_ascii_alphabetic() || c == '/' => text.find('>').map(|v| v + 1),
+        _ => None,
+    }
+}
+
+/// Returns the length of a block in braces, nested braces are included
+fn brace_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(i + 1),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the length of the printf specifier at the start of the text, like %s or %1$d
+pub fn printf_end(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 1;
+    if bytes.get(i) == Some(&b'%') {
+        return Some(2);
+    }
+    if bytes.get(i) == Some(&b'(') {
+        i += text[i..].find(')')? + 1;
+    }
+    while i < bytes.len() && (bytes[i].is_ascii_digit() || b"$-+ #.*'hlLqjzt".contains(&bytes[i])) {
+        i += 1;
+    }
+    match bytes.get(i) {
+        Some(c) if b"diouxXeEfFgGaAcspn@".contains(c) => Some(i + 1),
+        _ => None,
+    }
+}
@@ -0,0 +1,480 @@
+use crate::error::Error;
+use crate::formats::placeholders::{printf_end, restore_values, Protector};
+use crate::languages::Language;
+use crate::translators::context::Context;
+use crate::translators::options::TranslationOptions;
+use crate::translators::Translators;
+
+/// A keyword like msgid with its value
+#[derive(Clone, Debug)]
+struct Field {
+    keyword: String,
+    /// lines as written in the file
+    raw: Vec<String>,
+    /// unescaped value
+    value: String,
+}
+
+/// A single message of a catalog
+#[derive(Clone, Debug)]
+pub struct PoEntry {
+    /// comment lines as written in the file
+    comments: Vec<String>,
+    fields: Vec<Field>,
+    /// new msgstr values, replaces the msgstr lines when set
+    translation: Option<Vec<String>>,
+    fuzzy: bool,
+}
+
+impl PoEntry {
+    fn field(&self, keyword: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|v| v.keyword == keyword)
+            .map(|v| v.value.as_str())
+    }
+
+    fn comments(&self, prefix: &str) -> Vec<&str> {
+        self.comments
+            .iter()
+            .filter_map(|v| {
+                let v = v.trim_end_matches(['\r', '\n']);
+                match prefix {
+                    "#" if v == "#" => Some(""),
+                    "#" if v.starts_with("# ") => Some(&v[2..]),
+                    "#" => None,
+                    _ => v.strip_prefix(prefix).map(|v| v.trim()),
+                }
+            })
+            .collect()
+    }
+
+    pub fn msgctxt(&self) -> Option<&str> {
+        self.field("msgctxt")
+    }
+
+    pub fn msgid(&self) -> &str {
+        self.field("msgid").unwrap_or_default()
+    }
+
+    pub fn msgid_plural(&self) -> Option<&str> {
+        self.field("msgid_plural")
+    }
+
+    /// Returns msgstr or all msgstr[n] of a plural entry
+    pub fn msgstr(&self) -> Vec<&str> {
+        match &self.translation {
+            Some(v) => v.iter().map(|v| v.as_str()).collect(),
+            None => self
+                .fields
+                .iter()
+                .filter(|v| v.keyword.starts_with("msgstr"))
+                .map(|v| v.value.as_str())
+                .collect(),
+        }
+    }
+
+    /// Comments of the translator, lines starting with "# "
+    pub fn translator_comments(&self) -> Vec<&str> {
+        self.comments("#")
+    }
+
+    /// Comments for the translator from the source code, lines starting with "#."
+    pub fn extracted_comments(&self) -> Vec<&str> {
+        self.comments("#.")
+    }
+
+    /// Flags like fuzzy or c-format
+    pub fn flags(&self) -> Vec<&str> {
+        let mut flags = self
+            .comments("#,")
+            .into_iter()
+            .flat_map(|v| v.split(','))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>();
+        if self.fuzzy && !flags.contains(&"fuzzy") {
+            flags.insert(0, "fuzzy");
+        }
+        flags
+    }
+
+    /// Checks if the strings contain format specifiers, like c-format or python-format
+    fn is_format(&self) -> bool {
+        self.flags()
+            .iter()
+            .any(|v| v.ends_with("-format") && !v.starts_with("no-"))
+    }
+
+    /// The header is the entry with an empty msgid
+    pub fn is_header(&self) -> bool {
+        self.msgid().is_empty() && self.msgctxt().is_none()
+    }
+
+    /// Checks if the entry has no translation yet
+    pub fn is_untranslated(&self) -> bool {
+        !self.is_header() && self.msgstr().iter().all(|v| v.is_empty())
+    }
+
+    /// Note for chatbots with the msgctxt and the comments
+    fn note(&self) -> Option<String> {
+        let mut notes = vec![];
+        if let Some(v) = self.msgctxt() {
+            notes.push(format!("context: {}", v));
+        }
+        let comments = self
+            .extracted_comments()
+            .into_iter()
+            .chain(self.translator_comments())
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>();
+        if !comments.is_empty() {
+            notes.push(format!("comment: {}", comments.join(" ")));
+        }
+        match notes.is_empty() {
+            true => None,
+            false => Some(notes.join(", ")),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let translation = match &self.translation {
+            Some(v) => v,
+            None => {
+                return self
+                    .comments
+                    .iter()
+                    .chain(self.fields.iter().flat_map(|v| v.raw.iter()))
+                    .map(|v| v.as_str())
+                    .collect()
+            }
+        };
+        let mut text = String::new();
+        let flag_line = match self.flags().is_empty() {
+            true => None,
+            false => Some(format!("#, {}\n", self.flags().join(", "))),
+        };
+        let mut flag_written = false;
+        for comment in &self.comments {
+            if comment.starts_with("#,") {
+                if let Some(v) = flag_line.as_ref().filter(|_| !flag_written) {
+                    text.push_str(v);
+                }
+                flag_written = true;
+                continue;
+            }
+            if comment.starts_with("#|") && !flag_written {
+                if let Some(v) = &flag_line {
+                    text.push_str(v);
+                }
+                flag_written = true;
+            }
+            text.push_str(comment);
+        }
+        if let Some(v) = flag_line.filter(|_| !flag_written) {
+            text.push_str(&v);
+        }
+        for field in self
+            .fields
+            .iter()
+            .filter(|v| !v.keyword.starts_with("msgstr"))
+        {
+            field.raw.iter().for_each(|v| text.push_str(v));
+        }
+        let plural = self.msgid_plural().is_some();
+        for (i, value) in translation.iter().enumerate() {
+            let keyword = match plural {
+                true => format!("msgstr[{}]", i),
+                false => "msgstr".to_string(),
+            };
+            text.push_str(&format_field(&keyword, value));
+        }
+        text
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Block {
+    Raw(String),
+    Entry(PoEntry),
+}
+
+/// A parsed PO or POT file. Entries that are not translated are written back unchanged.
+#[derive(Clone, Debug)]
+pub struct PoCatalog {
+    blocks: Vec<Block>,
+}
+
+impl PoCatalog {
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut blocks = vec![];
+        let mut comments: Vec<String> = vec![];
+        let mut fields: Vec<Field> = vec![];
+        for (number, line) in text.split_inclusive('\n').enumerate() {
+            let trimmed = line.trim();
+            let in_msgstr = fields
+                .last()
+                .map(|v| v.keyword.starts_with("msgstr"))
+                .unwrap_or(false);
+            if trimmed.is_empty() {
+                flush_entry(&mut blocks, &mut comments, &mut fields);
+                blocks.push(Block::Raw(line.to_string()));
+                continue;
+            }
+            if trimmed.starts_with('#') {
+                if in_msgstr {
+                    flush_entry(&mut blocks, &mut comments, &mut fields);
+                }
+                comments.push(line.to_string());
+                continue;
+            }
+            if trimmed.starts_with('"') {
+                let field = fields.last_mut().ok_or_else(|| {
+                    Error::new_option(format!("Line {}: string without keyword", number + 1))
+                })?;
+                field.value.push_str(&parse_string(trimmed, number)?);
+                field.raw.push(line.to_string());
+                continue;
+            }
+            let (keyword, value) = trimmed
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| Error::new_option(format!("Line {}: missing string", number + 1)))?;
+            if !keyword.starts_with("msg") {
+                return Err(Error::new_option(format!(
+                    "Line {}: unknown keyword {}",
+                    number + 1,
+                    keyword
+                )));
+            }
+            if in_msgstr && !keyword.starts_with("msgstr") {
+                flush_entry(&mut blocks, &mut comments, &mut fields);
+            }
+            fields.push(Field {
+                keyword: keyword.to_string(),
+                raw: vec![line.to_string()],
+                value: parse_string(value.trim(), number)?,
+            });
+        }
+        flush_entry(&mut blocks, &mut comments, &mut fields);
+        Ok(Self { blocks })
+    }
+
+    pub fn entries(&self) -> Vec<&PoEntry> {
+        self.blocks
+            .iter()
+            .filter_map(|v| match v {
+                Block::Entry(v) => Some(v),
+                Block::Raw(_) => None,
+            })
+            .collect()
+    }
+
+    fn entries_mut(&mut self) -> Vec<&mut PoEntry> {
+        self.blocks
+            .iter_mut()
+            .filter_map(|v| match v {
+                Block::Entry(v) => Some(v),
+                Block::Raw(_) => None,
+            })
+            .collect()
+    }
+
+    /// Number of plural forms from the Plural-Forms header
+    pub fn nplurals(&self) -> Option<usize> {
+        let header = self.entries().into_iter().find(|v| v.is_header())?;
+        let msgstr = header.msgstr().concat();
+        let forms = msgstr
+            .lines()
+            .find_map(|v| v.strip_prefix("Plural-Forms:"))?;
+        let value = forms
+            .split(';')
+            .find_map(|v| v.trim().strip_prefix("nplurals="))?;
+        value.trim().parse().ok()
+    }
+
+    pub fn to_text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(|v| match v {
+                Block::Raw(v) => v.to_string(),
+                Block::Entry(v) => v.to_text(),
+            })
+            .collect()
+    }
+}
+
+impl Translators {
+    /// Fills the empty msgstr entries of a PO or POT file and marks them as fuzzy.
+    /// The plural form msgstr[0] is translated from msgid, the other forms from msgid_plural.
+    /// With a single plural form msgid_plural isn't translated.
+    /// msgctxt and comments are passed as `Context::Notes`.
+    pub fn translate_po(
+        &self,
+        text: &str,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<String, Error> {
+        let mut catalog = PoCatalog::parse(text)?;
+        let nplurals = catalog.nplurals();
+        let plural_count = |entry: &PoEntry| nplurals.unwrap_or(entry.msgstr().len()).max(1);
+        let mut queries = vec![];
+        let mut notes = vec![];
+        let mut protected = vec![];
+        let mut pending = vec![];
+        for (i, entry) in catalog.entries().iter().enumerate() {
+            if !entry.is_untranslated() {
+                continue;
+            }
+            let mut sources = vec![entry.msgid()];
+            // languages with a single plural form only use the msgid translation
+            if plural_count(entry) > 1 {
+                sources.extend(entry.msgid_plural());
+            }
+            for source in sources {
+                let trimmed = source.trim();
+                let start = source.len() - source.trim_start().len();
+                let text = protect_format(trimmed, entry.is_format());
+                queries.push(text.text.to_string());
+                notes.push(entry.note());
+                protected.push((
+                    text,
+                    source[..start].to_string(),
+                    source[start + trimmed.len()..].to_string(),
+                ));
+            }
+            pending.push(i);
+        }
+        let mut context = context_data.to_vec();
+        if notes.iter().any(|v| v.is_some()) {
+            context.push(Context::Notes(notes));
+        }
+        let translations = self.translate_segments(queries, from, &context, options)?;
+        let mut translations =
+            protected
+                .iter()
+                .zip(translations)
+                .map(|((text, prefix, suffix), translation)| {
+                    format!(
+                        "{}{}{}",
+                        prefix,
+                        restore_values(text, translation.trim(), |v| v.to_string()),
+                        suffix
+                    )
+                });
+        let mut entries = catalog.entries_mut();
+        for i in pending {
+            let entry = &mut entries[i];
+            let singular = translations.next().unwrap_or_default();
+            let count = plural_count(entry);
+            let translation = match entry.msgid_plural() {
+                Some(_) if count > 1 => {
+                    let plural = translations.next().unwrap_or_default();
+                    let mut values = vec![singular];
+                    values.resize(count, plural);
+                    values
+                }
+                _ => vec![singular],
+            };
+            entry.translation = Some(translation);
+            entry.fuzzy = true;
+        }
+        Ok(catalog.to_text())
+    }
+}
+
+fn flush_entry(blocks: &mut Vec<Block>, comments: &mut Vec<String>, fields: &mut Vec<Field>) {
+    match fields.is_empty() {
+        true if !comments.is_empty() => blocks.push(Block::Raw(comments.concat())),
+        true => {}
+        false => blocks.push(Block::Entry(PoEntry {
+            comments: std::mem::take(comments),
+            fields: std::mem::take(fields),
+            translation: None,
+            fuzzy: false,
+        })),
+    }
+    comments.clear();
+}
+
+/// Parses a quoted po string and unescapes it
+fn parse_string(text: &str, number: usize) -> Result<String, Error> {
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or_else(|| Error::new_option(format!("Line {}: invalid string", number + 1)))?;
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some(v) => value.push(v),
+            None => value.push('\\'),
+        }
+    }
+    Ok(value)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+}
+
+/// Formats a field, values with line breaks are split into multiple lines like msgmerge does
+fn format_field(keyword: &str, value: &str) -> String {
+    let lines = value.split_inclusive('\n').collect::<Vec<_>>();
+    if lines.len() < 2 {
+        return format!("{} \"{}\"\n", keyword, escape(value));
+    }
+    let mut text = format!("{} \"\"\n", keyword);
+    for line in lines {
+        text.push_str(&format!("\"{}\"\n", escape(line)));
+    }
+    text
+}
+
+/// Replaces format specifiers like %s, %1$d, %(name)s and {name} with placeholders.
+/// Only existing placeholders are protected, when the string has no format flag.
+fn protect_format(text: &str, format: bool) -> Protector<String> {
+    let mut protector = Protector::new();
+    if !format {
+        protector.push_text(text, |v| v);
+        return protector;
+    }
+    let mut rest = text;
+    while let Some(start) = rest.find(['%', '{']) {
+        let end = match rest[start..].starts_with('%') {
+            true => printf_end(&rest[start..]),
+            false => rest[start..]
+                .find('}')
+                .filter(|v| {
+                    rest[start + 1..start + v]
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | ':'))
+                })
+                .map(|v| v + 1),
+        };
+        match end {
+            Some(end) => {
+                protector.push_text(&rest[..start], |v| v);
+                protector.push_value(rest[start..start + end].to_string());
+                rest = &rest[start + end..];
+            }
+            None => {
+                protector.push_text(&rest[..start + 1], |v| v);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    protector.push_text(rest, |v| v);
+    protector
+}
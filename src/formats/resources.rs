@@ -0,0 +1,1229 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::error::Error;
+use crate::formats::markup::{
+    decode_entity, entity_at, escape_text, tokenize, MarkupOptions, Node, TagKind,
+};
+use crate::formats::placeholders::{push_interpolated, restore_values, Protector};
+use crate::formats::xliff::parse_language;
+use crate::languages::Language;
+use crate::translators::context::Context;
+use crate::translators::options::TranslationOptions;
+use crate::translators::Translators;
+
+/// Keys of a stringsdict that contain text
+const STRINGSDICT_KEYS: [&str; 7] = [
+    "NSStringLocalizedFormatKey",
+    "zero",
+    "one",
+    "two",
+    "few",
+    "many",
+    "other",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceFormat {
+    /// Nested json like i18next or vue-i18n
+    Json,
+    /// Yaml like rails
+    Yaml,
+    /// Fluent .ftl
+    Fluent,
+    /// Android strings.xml
+    AndroidXml,
+    /// Apple .strings
+    AppleStrings,
+    /// Apple .stringsdict
+    AppleStringsDict,
+}
+
+impl ResourceFormat {
+    /// Returns the format for a file extension like json
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.trim_start_matches('.').to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yml" | "yaml" => Some(Self::Yaml),
+            "ftl" => Some(Self::Fluent),
+            "xml" => Some(Self::AndroidXml),
+            "strings" => Some(Self::AppleStrings),
+            "stringsdict" => Some(Self::AppleStringsDict),
+            _ => None,
+        }
+    }
+}
+
+/// Settings for the resource translation
+#[derive(Clone, Debug)]
+pub struct ResourceOptions {
+    /// Values that are translated in one request
+    pub batch_size: usize,
+}
+
+impl Default for ResourceOptions {
+    fn default() -> Self {
+        Self { batch_size: 100 }
+    }
+}
+
+/// How a value is written in the file
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Style {
+    Json,
+    YamlPlain,
+    YamlDouble,
+    YamlSingle,
+    /// block scalar with the indentation of the lines
+    YamlBlock(String),
+    /// pattern with the indentation of the continuation lines
+    Fluent(String),
+    Android,
+    Apple,
+    Xml,
+}
+
+impl Style {
+    /// Encodes text for the file
+    fn encode(&self, text: &str) -> String {
+        match self {
+            Style::Json | Style::YamlDouble => escape_json(text),
+            Style::YamlSingle => text.replace('\'', "''").replace('\n', " "),
+            Style::YamlPlain => text.to_string(),
+            Style::YamlBlock(indent) => text.replace('\n', &format!("\n{}", indent)),
+            Style::Fluent(indent) => text
+                .replace('{', "{\"{\"}")
+                .replace('}', "{\"}\"}")
+                .replace('\n', &format!("\n{}", indent)),
+            Style::Android => escape_text(text)
+                .replace('\\', "\\\\")
+                .replace('\'', "\\'")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n"),
+            Style::Apple => text
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n"),
+            Style::Xml => escape_text(text),
+        }
+    }
+
+    /// Encodes a protected interpolation, fluent placeables are already written in the syntax of the file
+    fn encode_value(&self, value: &str) -> String {
+        match self {
+            Style::Fluent(_) => value.to_string(),
+            _ => self.encode(value),
+        }
+    }
+
+    /// Adds what is needed around the encoded value
+    fn finalize(&self, text: String) -> String {
+        match self {
+            Style::YamlPlain if needs_quotes(&text) => format!("\"{}\"", escape_json(&text)),
+            Style::YamlBlock(indent) => format!("{}{}", indent, text),
+            _ => text,
+        }
+    }
+}
+
+/// A translatable value of a resource file
+#[derive(Clone, Debug)]
+pub(crate) struct ResourceEntry {
+    pub(crate) key: String,
+    /// position of the encoded value in the file
+    pub(crate) range: Range<usize>,
+    pub(crate) text: Protector<String>,
+    style: Style,
+}
+
+impl ResourceEntry {
+    fn new(key: String, range: Range<usize>, value: &str, style: Style) -> Self {
+        let mut text = Protector::new();
+        push_interpolated(&mut text, value, |v| style.encode_value(v));
+        Self {
+            key,
+            range,
+            text,
+            style,
+        }
+    }
+
+    /// Values without letters or digits are kept
+    pub(crate) fn is_translatable(&self) -> bool {
+        self.text.text.chars().any(|c| c.is_alphanumeric())
+    }
+
+    pub(crate) fn render(&self, translation: &str) -> String {
+        let value = restore_values(&self.text, translation.trim(), |v| self.style.encode(v));
+        self.style.finalize(value)
+    }
+
+    /// Writes the value of an entry with another style in the style of this entry.
+    /// The interpolations of the other entry are protected again, so they keep their place.
+    fn render_from(&self, other: &ResourceEntry) -> String {
+        let value = restore_values(&other.text, &other.text.text, |v| v.to_string());
+        let mut text = Protector::new();
+        push_interpolated(&mut text, &value, |v| self.style.encode_value(v));
+        let value = restore_values(&text, &text.text, |v| self.style.encode(v));
+        self.style.finalize(value)
+    }
+}
+
+/// A parsed resource file. Only the values are changed, keys, comments and the order are kept.
+#[derive(Clone, Debug)]
+pub struct Resource {
+    pub format: ResourceFormat,
+    text: String,
+    pub(crate) entries: Vec<ResourceEntry>,
+}
+
+impl Resource {
+    pub fn parse(text: &str, format: ResourceFormat) -> Result<Self, Error> {
+        let mut entries = match format {
+            ResourceFormat::Json => parse_json(text)?,
+            ResourceFormat::Yaml => parse_yaml(text),
+            ResourceFormat::Fluent => parse_fluent(text),
+            ResourceFormat::AndroidXml => parse_android(text),
+            ResourceFormat::AppleStrings => parse_apple_strings(text)?,
+            ResourceFormat::AppleStringsDict => parse_stringsdict(text),
+        };
+        entries.sort_by_key(|v| v.range.start);
+        Ok(Self {
+            format,
+            text: text.to_string(),
+            entries,
+        })
+    }
+
+    /// Returns the key and the text of all values, interpolations are replaced by placeholders
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        self.entries
+            .iter()
+            .map(|v| (v.key.as_str(), v.text.text.as_str()))
+            .collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        self.text.to_string()
+    }
+
+    /// Replaces the values, translations are in the order of the entries
+    fn apply(&mut self, translations: Vec<Option<String>>) {
+        let values = self
+            .entries
+            .iter()
+            .zip(translations)
+            .map(|(entry, translation)| translation.map(|v| entry.render(&v)))
+            .collect();
+        self.replace(values);
+    }
+
+    /// Replaces the values with already encoded text
+    fn replace(&mut self, values: Vec<Option<String>>) {
+        let mut text = String::with_capacity(self.text.len());
+        let mut last = 0;
+        for (entry, value) in self.entries.iter().zip(values) {
+            if let Some(value) = value {
+                text.push_str(&self.text[last..entry.range.start]);
+                text.push_str(&value);
+                last = entry.range.end;
+            }
+        }
+        text.push_str(&self.text[last..]);
+        self.text = text;
+    }
+
+    /// Returns the value like it is written in the file
+    fn raw(&self, index: usize) -> &str {
+        &self.text[self.entries[index].range.clone()]
+    }
+
+    /// Returns the index of the first entry for every key
+    /// Indices of the keys without the locale root, so the keys of different locales match
+    fn key_indices(&self) -> HashMap<&str, usize> {
+        let root = self.locale_root();
+        let mut indices = HashMap::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            indices.entry(strip_root(&entry.key, root)).or_insert(i);
+        }
+        indices
+    }
+
+    /// Locale at the root of a rails yaml file, like en in en.greeting
+    fn locale_root(&self) -> Option<&str> {
+        if self.format != ResourceFormat::Yaml {
+            return None;
+        }
+        let root = self.entries.first()?.key.split('.').next()?;
+        let shared = self
+            .entries
+            .iter()
+            .all(|v| v.key.strip_prefix(root).is_some_and(|v| v.starts_with('.')));
+        match shared && parse_language(root).is_some() {
+            true => Some(root),
+            false => None,
+        }
+    }
+
+    /// Renames the locale root of a rails yaml file, like en: to de:
+    fn rename_root(&mut self, root: &str) {
+        let old = match self.locale_root() {
+            Some(v) if v != root => v.to_string(),
+            _ => return,
+        };
+        let line = lines_with_offsets(&self.text)
+            .into_iter()
+            .find(|(_, line)| {
+                indentation(line) == 0 && split_yaml_key(line).is_some_and(|v| v.0 == old)
+            });
+        if let Some((offset, line)) = line {
+            if let Some(start) = line.find(old.as_str()) {
+                let start = offset + start;
+                self.text.replace_range(start..start + old.len(), root);
+            }
+        }
+    }
+}
+
+/// Removes the locale root from the key
+fn strip_root<'a>(key: &'a str, root: Option<&str>) -> &'a str {
+    root.and_then(|root| key.strip_prefix(root)?.strip_prefix('.'))
+        .unwrap_or(key)
+}
+
+/// Result of an incremental resource translation
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalOutput {
+    /// The translated file
+    pub text: String,
+    /// Keys that were missing in the target
+    pub added: Vec<String>,
+    /// Keys whose source changed since the snapshot
+    pub updated: Vec<String>,
+    /// Keys of the target that are no longer in the source
+    pub removed: Vec<String>,
+}
+
+impl Translators {
+    /// Translates the values of a json, yaml, fluent, android or apple resource file.
+    /// Interpolations like {{name}} or %1$s are protected, the keys are passed as `Context::Notes`.
+    pub fn translate_resource(
+        &self,
+        text: &str,
+        format: ResourceFormat,
+        resource_options: &ResourceOptions,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<String, Error> {
+        let mut resource = Resource::parse(text, format)?;
+        let pending = resource
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|v| v.1.is_translatable())
+            .map(|v| v.0)
+            .collect::<Vec<_>>();
+        let translations = self.translate_entries(
+            &resource,
+            &pending,
+            resource_options,
+            from,
+            context_data,
+            options,
+        )?;
+        resource.apply(translations);
+        Ok(resource.to_text())
+    }
+
+    /// Translates only the values that are new or changed.
+    /// `snapshot` is the source file of the last translation and `target` the existing translated file.
+    /// Without a snapshot every key of the target is kept, keys that are not in the source anymore are removed.
+    /// The locale root of a rails yaml file is renamed to the root of the target.
+    #[allow(clippy::too_many_arguments)]
+    pub fn translate_resource_incremental(
+        &self,
+        text: &str,
+        snapshot: Option<&str>,
+        target: Option<&str>,
+        format: ResourceFormat,
+        resource_options: &ResourceOptions,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<IncrementalOutput, Error> {
+        let mut resource = Resource::parse(text, format)?;
+        let snapshot = snapshot.map(|v| Resource::parse(v, format)).transpose()?;
+        let target = target.map(|v| Resource::parse(v, format)).transpose()?;
+        let snapshot_keys = snapshot
+            .as_ref()
+            .map(|v| v.key_indices())
+            .unwrap_or_default();
+        let target_keys = target.as_ref().map(|v| v.key_indices()).unwrap_or_default();
+        let mut output = IncrementalOutput::default();
+        let mut pending = Vec::new();
+        let mut values = vec![None; resource.entries.len()];
+        let root = resource.locale_root();
+        for (i, entry) in resource.entries.iter().enumerate() {
+            if !entry.is_translatable() {
+                continue;
+            }
+            let key = strip_root(&entry.key, root);
+            let existing = target_keys.get(key).filter(|v| {
+                target
+                    .as_ref()
+                    .is_some_and(|t| t.entries[**v].is_translatable())
+            });
+            let changed = snapshot_keys.get(key).is_some_and(|v| {
+                snapshot
+                    .as_ref()
+                    .is_some_and(|s| s.raw(*v) != resource.raw(i))
+            });
+            match (existing, target.as_ref()) {
+                (Some(index), Some(target)) if !changed => {
+                    let old = &target.entries[*index];
+                    values[i] = Some(match old.style == entry.style {
+                        true => target.raw(*index).to_string(),
+                        false => entry.render_from(old),
+                    });
+                }
+                (Some(_), _) => {
+                    output.updated.push(entry.key.to_string());
+                    pending.push(i);
+                }
+                _ => {
+                    output.added.push(entry.key.to_string());
+                    pending.push(i);
+                }
+            }
+        }
+        if let Some(target) = target.as_ref() {
+            let keys = resource.key_indices();
+            let target_root = target.locale_root();
+            output.removed = target
+                .entries
+                .iter()
+                .filter(|v| !keys.contains_key(strip_root(&v.key, target_root)))
+                .map(|v| v.key.to_string())
+                .collect();
+        }
+        let translations = self.translate_entries(
+            &resource,
+            &pending,
+            resource_options,
+            from,
+            context_data,
+            options,
+        )?;
+        for (i, translation) in translations.into_iter().enumerate() {
+            if let Some(translation) = translation {
+                values[i] = Some(resource.entries[i].render(&translation));
+            }
+        }
+        resource.replace(values);
+        if let Some(root) = target.as_ref().and_then(|v| v.locale_root()) {
+            resource.rename_root(root);
+        }
+        output.text = resource.to_text();
+        Ok(output)
+    }
+
+    /// Translates the entries at the indices in batches
+    fn translate_entries(
+        &self,
+        resource: &Resource,
+        pending: &[usize],
+        resource_options: &ResourceOptions,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<Vec<Option<String>>, Error> {
+        let mut translations = vec![None; resource.entries.len()];
+        for batch in pending.chunks(resource_options.batch_size.max(1)) {
+            let mut context = context_data.to_vec();
+            context.push(Context::Notes(
+                batch
+                    .iter()
+                    .map(|v| Some(format!("key: {}", resource.entries[*v].key)))
+                    .collect(),
+            ));
+            let result = self.translate_segments(
+                batch
+                    .iter()
+                    .map(|v| resource.entries[*v].text.text.to_string())
+                    .collect(),
+                from,
+                &context,
+                options,
+            )?;
+            for (i, translation) in batch.iter().zip(result) {
+                translations[*i] = Some(translation);
+            }
+        }
+        Ok(translations)
+    }
+}
+
+fn join_key(parent: &str, key: &str) -> String {
+    match parent.is_empty() {
+        true => key.to_string(),
+        false => format!("{}.{}", parent, key),
+    }
+}
+
+fn escape_json(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Reads a string with backslash escapes until the closing quote.
+/// Returns the decoded string and the position of the closing quote.
+fn read_escaped(text: &str, start: usize, quote: char) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = text[start..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c == quote => return Some((value, start + i)),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                'b' => value.push('\u{8}'),
+                'f' => value.push('\u{c}'),
+                'u' | 'U' => {
+                    let code = chars.by_ref().take(4).map(|v| v.1).collect::<String>();
+                    let mut code = u32::from_str_radix(&code, 16).ok()?;
+                    // surrogate pair in json
+                    if (0xD800..0xDC00).contains(&code) {
+                        let low = chars.by_ref().take(6).map(|v| v.1).collect::<String>();
+                        let low = u32::from_str_radix(low.trim_start_matches("\\u"), 16).ok()?;
+                        code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                    }
+                    value.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                }
+                v => value.push(v),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Parses json and returns all string values with their path
+fn parse_json(text: &str) -> Result<Vec<ResourceEntry>, Error> {
+    let mut entries = vec![];
+    let mut pos = 0;
+    parse_json_value(text, &mut pos, "", &mut entries)?;
+    Ok(entries)
+}
+
+fn skip_whitespace(text: &str, pos: &mut usize) {
+    while text[*pos..].starts_with(char::is_whitespace) {
+        *pos += text[*pos..]
+            .chars()
+            .next()
+            .map(|v| v.len_utf8())
+            .unwrap_or(1);
+    }
+}
+
+fn parse_json_value(
+    text: &str,
+    pos: &mut usize,
+    path: &str,
+    entries: &mut Vec<ResourceEntry>,
+) -> Result<(), Error> {
+    let error = |pos: usize| Error::new_option(format!("Invalid json at position {}", pos));
+    skip_whitespace(text, pos);
+    match text[*pos..].chars().next() {
+        Some('{') => {
+            *pos += 1;
+            loop {
+                skip_whitespace(text, pos);
+                match text[*pos..].chars().next() {
+                    Some('}') => {
+                        *pos += 1;
+                        return Ok(());
+                    }
+                    Some(',') => *pos += 1,
+                    Some('"') => {
+                        let (key, end) =
+                            read_escaped(text, *pos + 1, '"').ok_or_else(|| error(*pos))?;
+                        *pos = end + 1;
+                        skip_whitespace(text, pos);
+                        if !text[*pos..].starts_with(':') {
+                            return Err(error(*pos));
+                        }
+                        *pos += 1;
+                        parse_json_value(text, pos, &join_key(path, &key), entries)?;
+                    }
+                    _ => return Err(error(*pos)),
+                }
+            }
+        }
+        Some('[') => {
+            *pos += 1;
+            let mut index = 0;
+            loop {
+                skip_whitespace(text, pos);
+                match text[*pos..].chars().next() {
+                    Some(']') => {
+                        *pos += 1;
+                        return Ok(());
+                    }
+                    Some(',') => *pos += 1,
+                    Some(_) => {
+                        parse_json_value(text, pos, &join_key(path, &index.to_string()), entries)?;
+                        index += 1;
+                    }
+                    None => return Err(error(*pos)),
+                }
+            }
+        }
+        Some('"') => {
+            let (value, end) = read_escaped(text, *pos + 1, '"').ok_or_else(|| error(*pos))?;
+            entries.push(ResourceEntry::new(
+                path.to_string(),
+                *pos + 1..end,
+                &value,
+                Style::Json,
+            ));
+            *pos = end + 1;
+            Ok(())
+        }
+        Some(_) => {
+            let end = text[*pos..]
+                .find(|c: char| c == ',' || c == '}' || c == ']' || c.is_whitespace())
+                .map(|v| *pos + v)
+                .unwrap_or(text.len());
+            if end == *pos {
+                return Err(error(*pos));
+            }
+            *pos = end;
+            Ok(())
+        }
+        None => Err(error(*pos)),
+    }
+}
+
+/// Returns the lines with their start position
+pub(crate) fn lines_with_offsets(text: &str) -> Vec<(usize, &str)> {
+    let mut offset = 0;
+    text.split_inclusive('\n')
+        .map(|v| {
+            let line = (offset, v.trim_end_matches(['\r', '\n']));
+            offset += v.len();
+            line
+        })
+        .collect()
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Checks if a plain yaml scalar has to be quoted
+fn needs_quotes(text: &str) -> bool {
+    text.is_empty()
+        || text.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c) || c.is_whitespace())
+        || text.ends_with(char::is_whitespace)
+        || text.contains(": ")
+        || text.contains(" #")
+        || text.contains('\n')
+        || is_yaml_literal(text)
+}
+
+/// Checks if the plain scalar is a bool, null or number
+fn is_yaml_literal(text: &str) -> bool {
+    matches!(
+        text.to_lowercase().as_str(),
+        "~" | "null" | "true" | "false" | "yes" | "no" | "on" | "off"
+    ) || text.parse::<f64>().is_ok()
+}
+
+/// Parses the block style yaml of rails locale files.
+/// Flow collections, anchors and multi line plain scalars are skipped.
+fn parse_yaml(text: &str) -> Vec<ResourceEntry> {
+    let lines = lines_with_offsets(text);
+    let mut entries = vec![];
+    let mut stack: Vec<(usize, String)> = vec![];
+    let mut list_index: Vec<(usize, usize)> = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let (offset, line) = lines[i];
+        i += 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+            continue;
+        }
+        let indent = indentation(line);
+        let item = line[indent..].strip_prefix("- ");
+        while stack
+            .last()
+            .map(|v| v.0 > indent || (v.0 == indent && item.is_none()))
+            .unwrap_or(false)
+        {
+            stack.pop();
+        }
+        list_index.retain(|v| v.0 <= indent);
+        let parent = stack
+            .iter()
+            .map(|v| v.1.as_str())
+            .collect::<Vec<_>>()
+            .join(".");
+        let (key, value_start) = match item {
+            // mappings in lists are not supported
+            Some(rest) if split_yaml_key(rest).is_some() => continue,
+            Some(rest) => {
+                let index = match list_index.iter_mut().find(|v| v.0 == indent) {
+                    Some(v) => {
+                        v.1 += 1;
+                        v.1
+                    }
+                    None => {
+                        list_index.push((indent, 0));
+                        0
+                    }
+                };
+                (index.to_string(), line.len() - rest.len())
+            }
+            None => match split_yaml_key(&line[indent..]) {
+                Some((key, rest)) => (key, line.len() - rest.len()),
+                None => continue,
+            },
+        };
+        let rest = &line[value_start..];
+        let value = rest.trim_start();
+        let value_start = offset + value_start + rest.len() - value.len();
+        let path = join_key(&parent, &key);
+        match value.chars().next() {
+            None | Some('#') => stack.push((indent, key)),
+            Some('"') => {
+                if let Some((decoded, end)) = read_escaped(value, 1, '"') {
+                    entries.push(ResourceEntry::new(
+                        path,
+                        value_start + 1..value_start + end,
+                        &decoded,
+                        Style::YamlDouble,
+                    ));
+                }
+            }
+            Some('\'') => {
+                let inner = &value[1..];
+                let mut end = None;
+                let mut chars = inner.char_indices().peekable();
+                while let Some((j, c)) = chars.next() {
+                    if c == '\'' {
+                        if chars.peek().map(|v| v.1) == Some('\'') {
+                            chars.next();
+                            continue;
+                        }
+                        end = Some(j);
+                        break;
+                    }
+                }
+                if let Some(end) = end {
+                    entries.push(ResourceEntry::new(
+                        path,
+                        value_start + 1..value_start + 1 + end,
+                        &inner[..end].replace("''", "'"),
+                        Style::YamlSingle,
+                    ));
+                }
+            }
+            Some('|' | '>') => {
+                let start = i;
+                while i < lines.len()
+                    && (lines[i].1.trim().is_empty() || indentation(lines[i].1) > indent)
+                {
+                    i += 1;
+                }
+                let mut end = i;
+                while end > start && lines[end - 1].1.trim().is_empty() {
+                    end -= 1;
+                }
+                if start == end {
+                    continue;
+                }
+                let block_indent = indentation(lines[start].1);
+                let decoded = lines[start..end]
+                    .iter()
+                    .map(|v| v.1.get(block_indent..).unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                entries.push(ResourceEntry::new(
+                    path,
+                    lines[start].0..lines[end - 1].0 + lines[end - 1].1.len(),
+                    &decoded,
+                    Style::YamlBlock(" ".repeat(block_indent)),
+                ));
+            }
+            Some('[' | '{' | '&' | '*' | '!') => {}
+            Some(_) => {
+                let plain = match value.find(" #") {
+                    Some(v) => &value[..v],
+                    None => value,
+                }
+                .trim_end();
+                let next_is_continuation = lines
+                    .get(i)
+                    .map(|v| {
+                        !v.1.trim().is_empty()
+                            && indentation(v.1) > indent
+                            && !v.1.trim_start().starts_with('#')
+                    })
+                    .unwrap_or(false);
+                if !is_yaml_literal(plain) && !next_is_continuation {
+                    entries.push(ResourceEntry::new(
+                        path,
+                        value_start..value_start + plain.len(),
+                        plain,
+                        Style::YamlPlain,
+                    ));
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// Splits a yaml line into key and the rest after the colon
+fn split_yaml_key(line: &str) -> Option<(String, &str)> {
+    if let Some(quote) = line.chars().next().filter(|v| *v == '"' || *v == '\'') {
+        let end = line[1..].find(quote)? + 1;
+        let rest = line[end + 1..].trim_start().strip_prefix(':')?;
+        return Some((line[1..end].to_string(), rest));
+    }
+    let end = line
+        .match_indices(':')
+        .map(|v| v.0)
+        .find(|v| line[v + 1..].is_empty() || line[v + 1..].starts_with(' '))?;
+    Some((line[..end].trim().to_string(), &line[end + 1..]))
+}
+
+/// Parses fluent messages, terms and attributes.
+/// Select expressions are split, so every variant is translated on its own.
+fn parse_fluent(text: &str) -> Vec<ResourceEntry> {
+    let lines = lines_with_offsets(text);
+    let mut entries = vec![];
+    let mut message = String::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let (offset, line) = lines[i];
+        i += 1;
+        let indented = line.starts_with([' ', '\t']);
+        let trimmed = line.trim_start();
+        let (key, start) = match trimmed.find('=') {
+            Some(v) if !indented && !trimmed.starts_with('#') && !line.is_empty() => {
+                message = trimmed[..v].trim().to_string();
+                (message.to_string(), offset + v + 1)
+            }
+            Some(v) if indented && trimmed.starts_with('.') && !message.is_empty() => (
+                format!("{}{}", message, trimmed[..v].trim()),
+                offset + line.len() - trimmed.len() + v + 1,
+            ),
+            _ => {
+                if !indented && !line.trim().is_empty() {
+                    message.clear();
+                }
+                continue;
+            }
+        };
+        let mut end = offset + line.len();
+        let mut indent = None;
+        while i < lines.len() {
+            let (next_offset, next) = lines[i];
+            let next_trimmed = next.trim_start();
+            if next_trimmed.is_empty() {
+                i += 1;
+                continue;
+            }
+            if !next.starts_with([' ', '\t']) || next_trimmed.starts_with('.') {
+                break;
+            }
+            indent.get_or_insert_with(|| next[..next.len() - next_trimmed.len()].to_string());
+            end = next_offset + next.len();
+            i += 1;
+        }
+        let pattern = &text[start..end];
+        let skipped = pattern.len() - pattern.trim_start().len();
+        let indent = indent.unwrap_or_else(|| "    ".to_string());
+        for range in fluent_runs(pattern.trim_start(), start + skipped) {
+            let raw = &text[range.clone()];
+            let value = raw.lines().map(|v| v.trim()).collect::<Vec<_>>().join("\n");
+            entries.push(ResourceEntry::new(
+                key.to_string(),
+                range,
+                &value,
+                Style::Fluent(indent.to_string()),
+            ));
+        }
+    }
+    entries
+}
+
+/// Returns the text runs of a fluent pattern. Runs are split at select expressions and the variants are returned as runs.
+fn fluent_runs(pattern: &str, offset: usize) -> Vec<Range<usize>> {
+    let mut runs = vec![];
+    let mut run_start = 0;
+    let mut i = 0;
+    let bytes = pattern.as_bytes();
+    while i < bytes.len() {
+        if bytes[i] != b'{' {
+            i += 1;
+            continue;
+        }
+        let end = match placeable_end(pattern, i) {
+            Some(v) => v,
+            None => break,
+        };
+        let inner = &pattern[i + 1..end];
+        if let Some(arrow) = top_level_arrow(inner) {
+            push_run(&mut runs, pattern, run_start..i, offset);
+            let variants = i + 1 + arrow + 2;
+            for range in fluent_variants(&pattern[variants..end]) {
+                let variant = &pattern[variants + range.start..variants + range.end];
+                runs.extend(fluent_runs(variant, offset + variants + range.start));
+            }
+            run_start = end + 1;
+        }
+        i = end + 1;
+    }
+    push_run(&mut runs, pattern, run_start..pattern.len(), offset);
+    runs
+}
+
+fn push_run(runs: &mut Vec<Range<usize>>, pattern: &str, range: Range<usize>, offset: usize) {
+    let text = &pattern[range.clone()];
+    let start = range.start + text.len() - text.trim_start().len();
+    let end = range.start + text.trim_end().len();
+    if start < end {
+        runs.push(offset + start..offset + end);
+    }
+}
+
+/// Returns the position of the closing brace, strings in placeables are skipped
+fn placeable_end(pattern: &str, start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in pattern[start..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the position of the -> of a select expression
+fn top_level_arrow(inner: &str) -> Option<usize> {
+    let first = inner.find(['{', '['])?;
+    inner[..first].find("->")
+}
+
+/// Returns the ranges of the variant values like "[one] one item"
+fn fluent_variants(variants: &str) -> Vec<Range<usize>> {
+    let mut result = vec![];
+    let mut current: Option<usize> = None;
+    let mut i = 0;
+    while i < variants.len() {
+        let rest = &variants[i..];
+        if rest.starts_with('{') {
+            i = placeable_end(variants, i)
+                .map(|v| v + 1)
+                .unwrap_or(variants.len());
+            continue;
+        }
+        let before = variants[..i].trim_end_matches([' ', '\t']);
+        let before = before
+            .strip_suffix('*')
+            .unwrap_or(before)
+            .trim_end_matches([' ', '\t']);
+        if rest.starts_with('[') && (before.is_empty() || before.ends_with('\n')) {
+            if let Some(close) = rest.find(']') {
+                if let Some(start) = current {
+                    result.push(start..before.trim_end().len().max(start));
+                }
+                i += close + 1;
+                current = Some(i);
+                continue;
+            }
+        }
+        i += rest.chars().next().map(|v| v.len_utf8()).unwrap_or(1);
+    }
+    if let Some(start) = current {
+        result.push(start..variants.trim_end().len().max(start));
+    }
+    result
+}
+
+/// Returns the position of every node in the text
+fn node_offsets(nodes: &[Node]) -> Vec<usize> {
+    let mut offset = 0;
+    nodes
+        .iter()
+        .map(|v| {
+            let start = offset;
+            offset += v.raw().len();
+            start
+        })
+        .collect()
+}
+
+fn find_close(nodes: &[Node], index: usize) -> usize {
+    let name = match &nodes[index] {
+        Node::Tag(v) if v.kind == TagKind::Open => &v.name,
+        _ => return index,
+    };
+    let mut depth = 0;
+    for (i, node) in nodes.iter().enumerate().skip(index + 1) {
+        if let Node::Tag(v) = node {
+            if &v.name != name {
+                continue;
+            }
+            match v.kind {
+                TagKind::Open => depth += 1,
+                TagKind::Close if depth == 0 => return i,
+                TagKind::Close => depth -= 1,
+                TagKind::SelfClosing => {}
+            }
+        }
+    }
+    index
+}
+
+/// Decodes xml entities
+fn decode_xml(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match entity_at(rest).and_then(|v| Some((v, decode_entity(&v[1..v.len() - 1])?))) {
+            Some((entity, c)) => {
+                result.push(c);
+                rest = &rest[entity.len()..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Removes the android escapes like \' and \n
+fn decode_android(text: &str) -> String {
+    let text = decode_xml(text);
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(v) => result.push(v),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Parses strings, string-arrays and plurals of an android strings.xml
+fn parse_android(text: &str) -> Vec<ResourceEntry> {
+    let nodes = tokenize(text, &MarkupOptions::xml());
+    let offsets = node_offsets(&nodes);
+    let mut entries = vec![];
+    let mut parent: Option<(String, usize, usize)> = None;
+    for (i, node) in nodes.iter().enumerate() {
+        let tag = match node {
+            Node::Tag(v) if v.kind == TagKind::Open => v,
+            _ => continue,
+        };
+        if parent.as_ref().map(|v| i > v.2).unwrap_or(false) {
+            parent = None;
+        }
+        let translatable = tag.attribute("translatable") != Some("false");
+        let key = match (tag.name.as_str(), &mut parent) {
+            ("string", _) => tag.attribute("name").unwrap_or_default().to_string(),
+            ("string-array" | "plurals", _) => {
+                let close = find_close(&nodes, i);
+                match translatable {
+                    true => {
+                        parent = Some((
+                            tag.attribute("name").unwrap_or_default().to_string(),
+                            0,
+                            close,
+                        ))
+                    }
+                    false => parent = Some((String::new(), 0, close)),
+                }
+                continue;
+            }
+            ("item", Some((name, index, _))) if !name.is_empty() => {
+                let key = match tag.attribute("quantity") {
+                    Some(v) => format!("{}.{}", name, v),
+                    None => format!("{}.{}", name, index),
+                };
+                *index += 1;
+                key
+            }
+            _ => continue,
+        };
+        if !translatable {
+            continue;
+        }
+        let close = find_close(&nodes, i);
+        if close == i {
+            continue;
+        }
+        // references like @string/name or ?attr/name are no text, an escaped \@ is
+        let raw = nodes[i + 1..close]
+            .iter()
+            .map(|v| v.raw())
+            .collect::<String>();
+        if raw.trim_start().starts_with(['@', '?']) {
+            continue;
+        }
+        let style = Style::Android;
+        let mut text = Protector::new();
+        let mut j = i + 1;
+        while j < close {
+            match &nodes[j] {
+                Node::Text(v) => {
+                    push_interpolated(&mut text, &decode_android(v), |v| style.encode(v))
+                }
+                Node::Tag(v) if v.kind == TagKind::Open && v.local_name() == "g" => {
+                    let end = find_close(&nodes, j);
+                    text.push_value(nodes[j..=end].iter().map(|v| v.raw()).collect());
+                    j = end;
+                }
+                v => text.push_value(v.raw().to_string()),
+            }
+            j += 1;
+        }
+        entries.push(ResourceEntry {
+            key,
+            range: offsets[i] + node.raw().len()..offsets[close],
+            text,
+            style,
+        });
+    }
+    entries
+}
+
+/// Parses "key" = "value"; pairs of an apple .strings file
+fn parse_apple_strings(text: &str) -> Result<Vec<ResourceEntry>, Error> {
+    let error = |pos: usize| Error::new_option(format!("Invalid strings file at position {}", pos));
+    let mut entries = vec![];
+    let mut pos = 0;
+    let mut key: Option<String> = None;
+    loop {
+        skip_whitespace(text, &mut pos);
+        let rest = &text[pos..];
+        if rest.is_empty() {
+            break;
+        }
+        if rest.starts_with("/*") {
+            pos += rest.find("*/").map(|v| v + 2).ok_or_else(|| error(pos))?;
+            continue;
+        }
+        if rest.starts_with("//") {
+            pos += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if rest.starts_with('=') || rest.starts_with(';') {
+            pos += 1;
+            continue;
+        }
+        let (value, range) = match rest.starts_with('"') {
+            true => {
+                let (value, end) = read_escaped(text, pos + 1, '"').ok_or_else(|| error(pos))?;
+                (value, pos + 1..end)
+            }
+            false => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '=' || c == ';')
+                    .unwrap_or(rest.len());
+                (rest[..end].to_string(), pos..pos + end)
+            }
+        };
+        pos = range.end + usize::from(rest.starts_with('"'));
+        match key.take() {
+            None => key = Some(value),
+            Some(key) => entries.push(ResourceEntry::new(key, range, &value, Style::Apple)),
+        }
+    }
+    Ok(entries)
+}
+
+/// Parses the format and plural strings of an apple .stringsdict
+fn parse_stringsdict(text: &str) -> Vec<ResourceEntry> {
+    let nodes = tokenize(text, &MarkupOptions::xml());
+    let offsets = node_offsets(&nodes);
+    let mut entries = vec![];
+    let mut path: Vec<String> = vec![];
+    let mut last_key = String::new();
+    for (i, node) in nodes.iter().enumerate() {
+        let tag = match node {
+            Node::Tag(v) => v,
+            _ => continue,
+        };
+        match (tag.name.as_str(), tag.kind) {
+            ("dict", TagKind::Open) => path.push(std::mem::take(&mut last_key)),
+            ("dict", TagKind::Close) => {
+                path.pop();
+            }
+            ("key", TagKind::Open) => {
+                let close = find_close(&nodes, i);
+                last_key = decode_xml(
+                    &nodes[i + 1..close.max(i + 1)]
+                        .iter()
+                        .map(|v| v.raw())
+                        .collect::<String>(),
+                );
+            }
+            ("string", TagKind::Open) if STRINGSDICT_KEYS.contains(&last_key.as_str()) => {
+                let close = find_close(&nodes, i);
+                let range = offsets[i] + node.raw().len()..offsets[close];
+                let key = path
+                    .iter()
+                    .filter(|v| !v.is_empty())
+                    .chain([&last_key])
+                    .map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join(".");
+                let value = decode_xml(&text[range.clone()]);
+                entries.push(ResourceEntry::new(key, range, &value, Style::Xml));
+            }
+            _ => {}
+        }
+    }
+    entries
+}
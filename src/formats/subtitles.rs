@@ -0,0 +1,696 @@
+use log::warn;
+
+use crate::error::Error;
+use crate::formats::placeholders::{split, Piece, Protector};
+use crate::languages::Language;
+use crate::translators::context::{Context, HistoryEntry};
+use crate::translators::options::TranslationOptions;
+use crate::translators::Translators;
+
+/// Fields of a dialogue line when the ass file has no format line
+const DEFAULT_ASS_FORMAT: [&str; 10] = [
+    "Layer", "Start", "End", "Style", "Name", "MarginL", "MarginR", "MarginV", "Effect", "Text",
+];
+
+/// First private use character, used to give placeholders a width of 0 while wrapping
+const PLACEHOLDER_CHAR: u32 = 0xE000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    WebVtt,
+    /// Advanced SubStation Alpha and SubStation Alpha
+    Ass,
+}
+
+impl SubtitleFormat {
+    /// Detects the format from the content of the file
+    pub fn detect(text: &str) -> Option<Self> {
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+        if text.starts_with("WEBVTT") {
+            return Some(Self::WebVtt);
+        }
+        if text
+            .lines()
+            .any(|v| matches!(v.trim(), "[Script Info]" | "[Events]"))
+        {
+            return Some(Self::Ass);
+        }
+        if text.contains("-->") {
+            return Some(Self::Srt);
+        }
+        None
+    }
+
+    /// Returns the format for a file extension like srt
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.trim_start_matches('.').to_lowercase().as_str() {
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::WebVtt),
+            "ass" | "ssa" => Some(Self::Ass),
+            _ => None,
+        }
+    }
+
+    fn line_break(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Ass => "\\N",
+            _ => "\n",
+        }
+    }
+}
+
+/// Settings for the subtitle translation
+#[derive(Clone, Debug)]
+pub struct SubtitleOptions {
+    /// Maximum characters per line, 0 keeps every cue on a single line
+    pub max_line_width: usize,
+    /// Cues that are translated in one request
+    pub batch_size: usize,
+    /// Number of previously translated cues that are passed as history
+    pub context_cues: usize,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_line_width: 42,
+            batch_size: 50,
+            context_cues: 5,
+        }
+    }
+}
+
+/// A single subtitle
+#[derive(Clone, Debug)]
+pub struct Cue {
+    /// Everything before the text, like the index and the timing line or the dialogue fields
+    head: String,
+    /// The text as written in the file
+    pub text: String,
+    /// Line ending after the text
+    tail: String,
+    /// Name field in ass, voice tag in webvtt
+    pub speaker: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+enum Block {
+    Raw(String),
+    Cue(Cue),
+}
+
+/// A parsed subtitle file. Everything except the cue text is written back unchanged.
+#[derive(Clone, Debug)]
+pub struct Subtitles {
+    pub format: SubtitleFormat,
+    blocks: Vec<Block>,
+    crlf: bool,
+    bom: bool,
+}
+
+impl Subtitles {
+    pub fn parse(text: &str, format: SubtitleFormat) -> Result<Self, Error> {
+        let bom = text.starts_with('\u{feff}');
+        let text = text.trim_start_matches('\u{feff}');
+        let crlf = text.contains("\r\n");
+        let text = text.replace("\r\n", "\n");
+        let blocks = match format {
+            SubtitleFormat::Srt | SubtitleFormat::WebVtt => parse_blocks(&text, format),
+            SubtitleFormat::Ass => parse_ass(&text)?,
+        };
+        Ok(Self {
+            format,
+            blocks,
+            crlf,
+            bom,
+        })
+    }
+
+    /// Parses the file and detects the format
+    pub fn parse_detect(text: &str) -> Result<Self, Error> {
+        let format = SubtitleFormat::detect(text)
+            .ok_or_else(|| Error::new_option("Unknown subtitle format"))?;
+        Self::parse(text, format)
+    }
+
+    pub fn cues(&self) -> Vec<&Cue> {
+        self.blocks
+            .iter()
+            .filter_map(|v| match v {
+                Block::Cue(v) => Some(v),
+                Block::Raw(_) => None,
+            })
+            .collect()
+    }
+
+    fn cues_mut(&mut self) -> Vec<&mut Cue> {
+        self.blocks
+            .iter_mut()
+            .filter_map(|v| match v {
+                Block::Cue(v) => Some(v),
+                Block::Raw(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the file with the original line endings
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if self.bom {
+            text.push('\u{feff}');
+        }
+        for block in &self.blocks {
+            match block {
+                Block::Raw(v) => text.push_str(v),
+                Block::Cue(v) => {
+                    text.push_str(&v.head);
+                    text.push_str(&v.text);
+                    text.push_str(&v.tail);
+                }
+            }
+        }
+        match self.crlf {
+            true => text.replace('\n', "\r\n"),
+            false => text,
+        }
+    }
+}
+
+/// Part of the cue text that is sent to the translator
+struct CueQuery {
+    cue: usize,
+    /// dash of a dialogue line, like "- "
+    dash: String,
+    text: Protector<String>,
+}
+
+/// Cue text split into translated lines and tags that stay at the start and end
+struct CueText {
+    prefix: String,
+    suffix: String,
+    /// line break of the cue, ass soft line breaks are kept
+    line_break: &'static str,
+    queries: Vec<CueQuery>,
+}
+
+#[derive(Clone, Debug)]
+enum Token {
+    Text(String),
+    Tag(String),
+}
+
+impl Translators {
+    /// Translates the text of every cue in a srt, webvtt or ass file.
+    /// Timings, styles and positions are kept, tags at the start and end of a cue stay in place
+    /// and tags inside the text are passed as placeholders. The translated text is wrapped to `max_line_width`.
+    pub fn translate_subtitles(
+        &self,
+        text: &str,
+        format: SubtitleFormat,
+        subtitle_options: &SubtitleOptions,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<String, Error> {
+        let mut subtitles = Subtitles::parse(text, format)?;
+        let cues = subtitles.cues();
+        let cue_texts = cues
+            .iter()
+            .enumerate()
+            .map(|(i, v)| split_cue(i, v, format))
+            .collect::<Vec<_>>();
+        let queries = cue_texts
+            .iter()
+            .flatten()
+            .flat_map(|v| v.queries.iter())
+            .collect::<Vec<_>>();
+        let mut translations: Vec<String> = Vec::with_capacity(queries.len());
+        for batch in queries.chunks(subtitle_options.batch_size.max(1)) {
+            let mut context = context_data.to_vec();
+            let start = translations
+                .len()
+                .saturating_sub(subtitle_options.context_cues);
+            let history = queries[start..translations.len()]
+                .iter()
+                .zip(&translations[start..])
+                .map(|(query, translation)| HistoryEntry {
+                    source: query.text.text.to_string(),
+                    translation: translation.to_string(),
+                })
+                .collect::<Vec<_>>();
+            if !history.is_empty() {
+                context.push(Context::History(history));
+            }
+            let speakers = batch
+                .iter()
+                .map(|v| cues[v.cue].speaker.clone())
+                .collect::<Vec<_>>();
+            if speakers.iter().any(|v| v.is_some()) {
+                context.push(Context::Speakers(speakers));
+            }
+            translations.extend(self.translate_segments(
+                batch.iter().map(|v| v.text.text.to_string()).collect(),
+                from,
+                &context,
+                options,
+            )?);
+        }
+        let mut translations = translations.into_iter();
+        let mut results = vec![];
+        for cue_text in cue_texts.iter().flatten() {
+            let lines = cue_text
+                .queries
+                .iter()
+                .zip(translations.by_ref())
+                .map(|(query, translation)| {
+                    let lines = restore_lines(
+                        &query.text,
+                        &translation,
+                        subtitle_options.max_line_width,
+                        format,
+                    );
+                    format!("{}{}", query.dash, lines.join(cue_text.line_break))
+                })
+                .collect::<Vec<_>>();
+            let cue = cue_text.queries[0].cue;
+            let text = format!(
+                "{}{}{}",
+                cue_text.prefix,
+                lines.join(cue_text.line_break),
+                cue_text.suffix
+            );
+            results.push((cue, text));
+        }
+        let mut cues = subtitles.cues_mut();
+        for (cue, text) in results {
+            cues[cue].text = text;
+        }
+        Ok(subtitles.to_text())
+    }
+}
+
+/// Parses srt and webvtt, blocks with a timing line are cues
+fn parse_blocks(text: &str, format: SubtitleFormat) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut current: Vec<&str> = vec![];
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            flush_block(&mut blocks, &mut current, format);
+            blocks.push(Block::Raw(line.to_string()));
+        } else {
+            current.push(line);
+        }
+    }
+    flush_block(&mut blocks, &mut current, format);
+    blocks
+}
+
+fn flush_block(blocks: &mut Vec<Block>, current: &mut Vec<&str>, format: SubtitleFormat) {
+    let lines = std::mem::take(current);
+    if lines.is_empty() {
+        return;
+    }
+    let timing = match lines.iter().position(|v| v.contains("-->")) {
+        Some(v) if v + 1 < lines.len() => v,
+        _ => {
+            blocks.push(Block::Raw(lines.concat()));
+            return;
+        }
+    };
+    let body = lines[timing + 1..].concat();
+    let text = body.trim_end_matches('\n').to_string();
+    let speaker = match format {
+        SubtitleFormat::WebVtt => voice_tag(&text),
+        _ => None,
+    };
+    blocks.push(Block::Cue(Cue {
+        head: lines[..=timing].concat(),
+        tail: body[text.len()..].to_string(),
+        text,
+        speaker,
+    }));
+}
+
+/// Returns the speaker of a webvtt voice tag like <v Roger>
+fn voice_tag(text: &str) -> Option<String> {
+    let start = text.find("<v")?;
+    let rest = &text[start + 2..];
+    let end = rest.find('>')?;
+    let tag = &rest[..end];
+    let name = tag
+        .trim_start_matches(|c: char| c != ' ' && c != '\t')
+        .trim();
+    match name.is_empty() {
+        true => None,
+        false => Some(name.to_string()),
+    }
+}
+
+fn parse_ass(text: &str) -> Result<Vec<Block>, Error> {
+    let mut blocks = vec![];
+    let mut in_events = false;
+    let mut has_events = false;
+    let mut fields = DEFAULT_ASS_FORMAT
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>();
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_events = trimmed.eq_ignore_ascii_case("[Events]");
+            has_events |= in_events;
+        }
+        if in_events {
+            if let Some(v) = trimmed.strip_prefix("Format:") {
+                fields = v.split(',').map(|v| v.trim().to_string()).collect();
+            }
+        }
+        let dialogue = match in_events {
+            true => line.strip_prefix("Dialogue:"),
+            false => None,
+        };
+        let dialogue = match dialogue {
+            Some(v) => v,
+            None => {
+                blocks.push(Block::Raw(line.to_string()));
+                continue;
+            }
+        };
+        let text_index = fields
+            .iter()
+            .position(|v| v.eq_ignore_ascii_case("Text"))
+            .ok_or_else(|| Error::new_option("Format line of the ass file has no Text field"))?;
+        let values = dialogue.splitn(text_index + 1, ',').collect::<Vec<_>>();
+        if values.len() != text_index + 1 {
+            blocks.push(Block::Raw(line.to_string()));
+            continue;
+        }
+        let text = values[text_index].trim_end_matches(['\r', '\n']);
+        let head_len = line.len() - values[text_index].len();
+        let speaker = fields
+            .iter()
+            .position(|v| v.eq_ignore_ascii_case("Name") || v.eq_ignore_ascii_case("Actor"))
+            .and_then(|v| values.get(v))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string());
+        blocks.push(Block::Cue(Cue {
+            head: line[..head_len].to_string(),
+            text: text.to_string(),
+            tail: line[head_len + text.len()..].to_string(),
+            speaker,
+        }));
+    }
+    if !has_events {
+        return Err(Error::new_option("Ass file has no [Events] section"));
+    }
+    Ok(blocks)
+}
+
+/// Splits the text of a cue into queries. Lines are joined unless every line is a dialogue line starting with a dash.
+/// Returns None when the cue has no text to translate.
+fn split_cue(index: usize, cue: &Cue, format: SubtitleFormat) -> Option<CueText> {
+    if format == SubtitleFormat::Ass && is_drawing(&cue.text) {
+        return None;
+    }
+    // ass soft line breaks are only used when the cue has no hard line break
+    let line_break = match format {
+        SubtitleFormat::Ass if !cue.text.contains("\\N") && cue.text.contains("\\n") => "\\n",
+        _ => format.line_break(),
+    };
+    let mut lines = cue
+        .text
+        .split(line_break)
+        .map(|v| tokenize(v, format))
+        .collect::<Vec<_>>();
+    let mut prefix = String::new();
+    while let Some(Token::Tag(v)) = lines.first().and_then(|v| v.first()) {
+        prefix.push_str(v);
+        lines[0].remove(0);
+    }
+    let mut suffix = vec![];
+    while let Some(Token::Tag(v)) = lines.last().and_then(|v| v.last()) {
+        suffix.insert(0, v.to_string());
+        if let Some(line) = lines.last_mut() {
+            line.pop();
+        }
+    }
+    let is_dialogue = lines.len() > 1
+        && lines
+            .iter()
+            .all(|v| matches!(v.first(), Some(Token::Text(v)) if v.starts_with('-')));
+    let groups = match is_dialogue {
+        true => lines,
+        false => vec![join_lines(lines)],
+    };
+    let mut queries = vec![];
+    for mut tokens in groups {
+        let mut dash = String::new();
+        if is_dialogue {
+            if let Some(Token::Text(v)) = tokens.first_mut() {
+                let text = v.trim_start_matches(['-', ' ']).to_string();
+                dash = v[..v.len() - text.len()].to_string();
+                *v = text;
+            }
+        }
+        let mut text = Protector::new();
+        for token in tokens {
+            match token {
+                Token::Text(v) => text.push_text(&collapse_whitespace(&decode(&v, format)), |v| v),
+                Token::Tag(v) => text.push_value(v),
+            }
+        }
+        text.text = text.text.trim().to_string();
+        queries.push(CueQuery {
+            cue: index,
+            dash,
+            text,
+        });
+    }
+    let has_text = queries
+        .iter()
+        .any(|v| v.text.text.chars().any(|c| c.is_alphanumeric()));
+    match has_text {
+        true => Some(CueText {
+            prefix,
+            suffix: suffix.concat(),
+            line_break,
+            queries,
+        }),
+        false => None,
+    }
+}
+
+/// Joins the lines of a cue with a space, except between cjk characters
+fn join_lines(lines: Vec<Vec<Token>>) -> Vec<Token> {
+    let mut tokens: Vec<Token> = vec![];
+    for line in lines {
+        let last = tokens.iter().rev().find_map(|v| match v {
+            Token::Text(v) => v.trim_end().chars().last(),
+            Token::Tag(_) => None,
+        });
+        let first = line.iter().find_map(|v| match v {
+            Token::Text(v) => v.trim_start().chars().next(),
+            Token::Tag(_) => None,
+        });
+        match (last, first) {
+            (Some(a), Some(b)) if is_cjk(a) || is_cjk(b) => {}
+            (Some(_), _) => tokens.push(Token::Text(" ".to_string())),
+            _ => {}
+        }
+        tokens.extend(line);
+    }
+    tokens
+}
+
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x3000..=0x9FFF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF)
+}
+
+/// Checks if the ass text is a vector drawing like {\p1}m 0 0 l 100 0
+fn is_drawing(text: &str) -> bool {
+    let mut rest = text;
+    while let Some(start) = rest.find("\\p") {
+        rest = &rest[start + 2..];
+        if rest.starts_with(|c: char| ('1'..='9').contains(&c)) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Splits a line into text and tags. Tags are html like tags in srt and webvtt and override blocks in ass.
+fn tokenize(line: &str, format: SubtitleFormat) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = line;
+    loop {
+        let start = rest
+            .char_indices()
+            .find(|(i, c)| match (c, format) {
+                ('{', SubtitleFormat::Ass) => true,
+                ('{', _) => rest[i + 1..].starts_with('\\'),
+                ('<', SubtitleFormat::Ass) => false,
+                ('<', _) => true,
+                _ => false,
+            })
+            .map(|v| v.0);
+        let start = match start {
+            Some(v) => v,
+            None => break,
+        };
+        let close = if rest[start..].starts_with('{') {
+            '}'
+        } else {
+            '>'
+        };
+        let end = match rest[start..].find(close) {
+            Some(v) => start + v + 1,
+            None => break,
+        };
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+        tokens.push(Token::Tag(rest[start..end].to_string()));
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    tokens
+}
+
+fn decode(text: &str, format: SubtitleFormat) -> String {
+    match format {
+        SubtitleFormat::WebVtt => text
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&nbsp;", "\u{a0}")
+            .replace("&amp;", "&"),
+        SubtitleFormat::Ass => text.replace("\\h", "\u{a0}").replace("\\n", " "),
+        SubtitleFormat::Srt => text.to_string(),
+    }
+}
+
+fn encode(text: &str, format: SubtitleFormat) -> String {
+    match format {
+        SubtitleFormat::WebVtt => text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+        SubtitleFormat::Ass => text.replace('\u{a0}', "\\h"),
+        SubtitleFormat::Srt => text.to_string(),
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for (i, word) in text.split_whitespace().enumerate() {
+        if i > 0 {
+            result.push(' ');
+        }
+        result.push_str(word);
+    }
+    if text.starts_with(char::is_whitespace) {
+        result.insert(0, ' ');
+    }
+    if text.ends_with(char::is_whitespace) && !result.trim().is_empty() {
+        result.push(' ');
+    }
+    result
+}
+
+/// Wraps the translation and replaces the placeholders with the tags.
+/// When placeholders are missing the tags are removed.
+fn restore_lines(
+    text: &Protector<String>,
+    translation: &str,
+    width: usize,
+    format: SubtitleFormat,
+) -> Vec<String> {
+    let mut pieces = split(translation);
+    if !text.is_complete(&pieces) {
+        warn!(
+            "Placeholders in subtitle translation \"{}\" dont match",
+            translation
+        );
+        pieces.retain(|v| matches!(v, Piece::Text(_)));
+    }
+    let marked = pieces
+        .iter()
+        .map(|v| match v {
+            Piece::Text(v) => {
+                collapse_whitespace(&v.replace(|c: char| placeholder_index(c).is_some(), ""))
+            }
+            Piece::Placeholder(v) => char::from_u32(PLACEHOLDER_CHAR + *v as u32)
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+        })
+        .collect::<String>();
+    wrap(marked.trim(), width)
+        .into_iter()
+        .map(|line| {
+            line.chars()
+                .map(|c| match placeholder_index(c) {
+                    Some(v) => text.values[v].to_string(),
+                    None => encode(&c.to_string(), format),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn placeholder_index(c: char) -> Option<usize> {
+    match c as u32 {
+        v if (PLACEHOLDER_CHAR..=0xF8FF).contains(&v) => Some((v - PLACEHOLDER_CHAR) as usize),
+        _ => None,
+    }
+}
+
+fn visible_width(text: &str) -> usize {
+    text.chars()
+        .filter(|c| placeholder_index(*c).is_none())
+        .count()
+}
+
+/// Greedy line wrapping. Words longer than the width, like japanese text without spaces, are split anywhere.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    if width == 0 || visible_width(text) <= width {
+        return vec![text.to_string()];
+    }
+    let mut lines: Vec<String> = vec![];
+    let mut current = String::new();
+    for word in text.split(' ').filter(|v| !v.is_empty()) {
+        let mut word = word.to_string();
+        while visible_width(&word) > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let mut count = 0;
+            let end = word
+                .char_indices()
+                .find(|(_, c)| {
+                    if placeholder_index(*c).is_none() {
+                        count += 1;
+                    }
+                    count > width
+                })
+                .map(|v| v.0)
+                .unwrap_or(word.len());
+            lines.push(word[..end].to_string());
+            word = word[end..].to_string();
+        }
+        if current.is_empty() {
+            current = word;
+        } else if visible_width(&current) + 1 + visible_width(&word) <= width {
+            current.push(' ');
+            current.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut current, word));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
@@ -0,0 +1,528 @@
+use std::str::FromStr;
+
+use log::warn;
+
+use crate::error::Error;
+use crate::formats::markup::{
+    decode_entity, entity_at, escape_attribute, escape_text, tokenize, MarkupOptions, Node, Tag,
+    TagKind,
+};
+use crate::formats::placeholders::{split, Piece, Protector};
+use crate::languages::Language;
+use crate::translators::context::Context;
+use crate::translators::options::TranslationOptions;
+use crate::translators::Translators;
+
+/// Inline elements where the content is native code and not text
+const CODE_ELEMENTS: [&str; 4] = ["ph", "bpt", "ept", "it"];
+
+/// States of xliff 1.2 targets that still need a translation
+const UNTRANSLATED_STATES: [&str; 2] = ["new", "needs-translation"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XliffVersion {
+    V1_2,
+    V2_0,
+}
+
+/// Settings for the xliff translation
+#[derive(Clone, Debug)]
+pub struct XliffOptions {
+    /// Xliff 1.2: Sets the state to needs-review-translation instead of translated
+    pub needs_review: bool,
+}
+
+impl Default for XliffOptions {
+    fn default() -> Self {
+        Self { needs_review: true }
+    }
+}
+
+/// Inline element in the source, replaced by a placeholder
+#[derive(Clone, Debug)]
+enum Inline {
+    Open(String, String),
+    Close(String, String),
+    Standalone(String),
+}
+
+impl Inline {
+    fn raw(&self) -> &str {
+        match self {
+            Inline::Open(_, v) | Inline::Close(_, v) | Inline::Standalone(v) => v,
+        }
+    }
+}
+
+/// A segment that needs a translation
+struct XliffSegment {
+    from: Option<Language>,
+    to: Language,
+    /// node of the trans-unit or segment
+    unit: usize,
+    /// node of the closing source tag
+    source_end: usize,
+    /// nodes of the target element
+    target: Option<(usize, usize)>,
+    text: Protector<Inline>,
+}
+
+/// A parsed xliff file, the text is only changed where targets are inserted
+pub struct XliffDocument {
+    pub version: XliffVersion,
+    nodes: Vec<Node>,
+}
+
+impl XliffDocument {
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let nodes = tokenize(text, &MarkupOptions::xml());
+        let root = nodes
+            .iter()
+            .find_map(|v| match v {
+                Node::Tag(v) if v.local_name() == "xliff" && v.kind != TagKind::Close => Some(v),
+                _ => None,
+            })
+            .ok_or_else(|| Error::new_option("No xliff element found"))?;
+        let version = match root.attribute("version") {
+            Some(v) if v.starts_with('1') => XliffVersion::V1_2,
+            Some(v) if v.starts_with('2') => XliffVersion::V2_0,
+            v => {
+                return Err(Error::new_option(format!(
+                    "Unsupported xliff version {:?}",
+                    v
+                )))
+            }
+        };
+        Ok(Self { version, nodes })
+    }
+
+    pub fn to_text(&self) -> String {
+        self.nodes.iter().map(|v| v.raw()).collect()
+    }
+
+    /// Returns the target language of the file, the first file element is used for xliff 1.2
+    pub fn target_language(&self) -> Option<Language> {
+        let attribute = match self.version {
+            XliffVersion::V1_2 => "target-language",
+            XliffVersion::V2_0 => "trgLang",
+        };
+        self.tags()
+            .find_map(|v| v.1.attribute(attribute))
+            .and_then(parse_language)
+    }
+
+    fn tags(&self) -> impl Iterator<Item = (usize, &Tag)> {
+        self.nodes.iter().enumerate().filter_map(|(i, v)| match v {
+            Node::Tag(v) => Some((i, v)),
+            _ => None,
+        })
+    }
+
+    fn tag(&self, index: usize) -> Option<&Tag> {
+        match &self.nodes[index] {
+            Node::Tag(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the closing tag
+    fn find_close(&self, index: usize) -> usize {
+        let tag = match self.tag(index) {
+            Some(v) if v.kind == TagKind::Open => v,
+            _ => return index,
+        };
+        let mut depth = 0;
+        for (i, node) in self.nodes.iter().enumerate().skip(index + 1) {
+            if let Node::Tag(v) = node {
+                if v.name != tag.name {
+                    continue;
+                }
+                match v.kind {
+                    TagKind::Open => depth += 1,
+                    TagKind::Close if depth == 0 => return i,
+                    TagKind::Close => depth -= 1,
+                    TagKind::SelfClosing => {}
+                }
+            }
+        }
+        index
+    }
+
+    /// Returns the direct children of an element with the name
+    fn find_child(&self, start: usize, end: usize, name: &str) -> Option<(usize, usize)> {
+        let mut i = start + 1;
+        while i < end {
+            if let Some(tag) = self.tag(i) {
+                if tag.kind == TagKind::Close {
+                    i += 1;
+                    continue;
+                }
+                let close = self.find_close(i);
+                if tag.local_name() == name {
+                    return Some((i, close));
+                }
+                i = close + 1;
+                continue;
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Collects all segments that are not locked or translated yet
+    fn segments(&self, from: Option<Language>) -> Result<Vec<XliffSegment>, Error> {
+        let (unit_name, source_attribute, target_attribute) = match self.version {
+            XliffVersion::V1_2 => ("trans-unit", "source-language", "target-language"),
+            XliffVersion::V2_0 => ("segment", "srcLang", "trgLang"),
+        };
+        let mut segments = vec![];
+        let mut source_language = None;
+        let mut target_language = None;
+        let mut locked_until = None;
+        for (i, tag) in self.tags() {
+            if tag.kind == TagKind::Close {
+                continue;
+            }
+            if let Some(v) = tag.attribute(source_attribute) {
+                source_language = parse_language(v);
+            }
+            if let Some(v) = tag.attribute(target_attribute) {
+                target_language =
+                    Some(parse_language(v).ok_or_else(|| {
+                        Error::new_option(format!("Unknown target language {}", v))
+                    })?);
+            }
+            if locked_until.map(|v| i <= v).unwrap_or(false) {
+                continue;
+            }
+            if is_locked(tag) {
+                locked_until = Some(self.find_close(i));
+                continue;
+            }
+            if tag.local_name() != unit_name || tag.kind != TagKind::Open {
+                continue;
+            }
+            let end = self.find_close(i);
+            let source = match self.find_child(i, end, "source") {
+                Some(v) => v,
+                None => continue,
+            };
+            let target = self.find_child(i, end, "target");
+            if self.is_translated(tag, target) {
+                continue;
+            }
+            let text = self.source_text(source.0, source.1);
+            if !text.text.chars().any(|c| c.is_alphanumeric()) {
+                continue;
+            }
+            segments.push(XliffSegment {
+                from: from.or(source_language),
+                to: target_language
+                    .ok_or_else(|| Error::new_option("Xliff file has no target language"))?,
+                unit: i,
+                source_end: source.1,
+                target,
+                text,
+            });
+        }
+        Ok(segments)
+    }
+
+    fn is_translated(&self, unit: &Tag, target: Option<(usize, usize)>) -> bool {
+        let has_text = target
+            .map(|(start, end)| {
+                self.nodes[start + 1..end.max(start + 1)]
+                    .iter()
+                    .any(|v| !matches!(v, Node::Text(v) if v.trim().is_empty()))
+            })
+            .unwrap_or(false);
+        match self.version {
+            XliffVersion::V1_2 => {
+                let state = target
+                    .and_then(|v| self.tag(v.0))
+                    .and_then(|v| v.attribute("state"));
+                has_text
+                    && !state
+                        .map(|v| UNTRANSLATED_STATES.contains(&v))
+                        .unwrap_or(false)
+            }
+            XliffVersion::V2_0 => {
+                let state = unit.attribute("state");
+                has_text || matches!(state, Some("translated" | "reviewed" | "final"))
+            }
+        }
+    }
+
+    /// Converts the source into text, inline elements are replaced by placeholders
+    fn source_text(&self, start: usize, end: usize) -> Protector<Inline> {
+        let mut text = Protector::new();
+        let mut i = start + 1;
+        while i < end {
+            match &self.nodes[i] {
+                Node::Text(v) => push_decoded(&mut text, v),
+                Node::Raw(v) => match v
+                    .strip_prefix("<![CDATA[")
+                    .and_then(|v| v.strip_suffix("]]>"))
+                {
+                    Some(v) => text.push_text(v, |v| Inline::Standalone(escape_text(&v))),
+                    None => text.push_value(Inline::Standalone(v.to_string())),
+                },
+                Node::Tag(tag) => {
+                    let close = self.find_close(i);
+                    match tag.kind {
+                        TagKind::Open if CODE_ELEMENTS.contains(&tag.local_name()) => {
+                            let raw = self.nodes[i..=close].iter().map(|v| v.raw()).collect();
+                            text.push_value(Inline::Standalone(raw));
+                            i = close;
+                        }
+                        TagKind::Open if close != i => {
+                            text.push_value(Inline::Open(tag.name.to_string(), tag.raw.to_string()))
+                        }
+                        TagKind::Close => text
+                            .push_value(Inline::Close(tag.name.to_string(), tag.raw.to_string())),
+                        _ => text.push_value(Inline::Standalone(tag.raw.to_string())),
+                    }
+                }
+            }
+            i += 1;
+        }
+        text.text = text.text.trim().to_string();
+        text
+    }
+
+    /// Writes the translation into the target and updates the state
+    fn apply(&mut self, segment: &XliffSegment, translation: &str, xliff_options: &XliffOptions) {
+        let content = restore(&segment.text, translation);
+        let state = match (self.version, xliff_options.needs_review) {
+            (XliffVersion::V1_2, true) => Some("needs-review-translation"),
+            (XliffVersion::V1_2, false) => Some("translated"),
+            (XliffVersion::V2_0, _) => None,
+        };
+        match segment.target {
+            Some((start, end)) => {
+                let tag = match &self.nodes[start] {
+                    Node::Tag(v) => v.clone(),
+                    _ => return,
+                };
+                let name = tag.name.to_string();
+                let mut open = match state {
+                    Some(v) => set_attribute(&tag, "state", v),
+                    None => tag.raw.to_string(),
+                };
+                if tag.kind == TagKind::SelfClosing {
+                    open = format!(
+                        "{}>",
+                        open.trim_end_matches('>').trim_end_matches('/').trim_end()
+                    );
+                }
+                let element = format!("{}{}</{}>", open, content, name);
+                self.nodes.splice(start..=end, [Node::Raw(element)]);
+            }
+            None => {
+                let indent = self.indent(segment.source_end);
+                let name = self
+                    .tag(segment.source_end)
+                    .map(|v| v.name.replace("source", "target"))
+                    .unwrap_or_else(|| "target".to_string());
+                let state = state
+                    .map(|v| format!(" state=\"{}\"", v))
+                    .unwrap_or_default();
+                let element = format!("{}<{}{}>{}</{}>", indent, name, state, content, name);
+                self.nodes
+                    .insert(segment.source_end + 1, Node::Raw(element));
+            }
+        }
+        if self.version == XliffVersion::V2_0 {
+            if let Some(tag) = self.tag(segment.unit).cloned() {
+                self.nodes[segment.unit] = Node::Raw(set_attribute(&tag, "state", "translated"));
+            }
+        }
+    }
+
+    /// Returns a line break and the indentation of the source element
+    fn indent(&self, source_end: usize) -> String {
+        let source_start = (0..source_end)
+            .rev()
+            .find(|v| matches!(self.tag(*v), Some(tag) if tag.local_name() == "source" && tag.kind == TagKind::Open))
+            .unwrap_or(source_end);
+        match source_start.checked_sub(1).map(|v| &self.nodes[v]) {
+            Some(Node::Text(v)) if v.trim().is_empty() && v.contains('\n') => {
+                v[v.rfind('\n').unwrap_or(0)..].to_string()
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+impl Translators {
+    /// Translates the sources of a xliff 1.2 or 2.0 file into targets.
+    /// Locked and already translated segments are skipped, the target language is read from the file.
+    pub fn translate_xliff(
+        &self,
+        text: &str,
+        xliff_options: &XliffOptions,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<String, Error> {
+        let mut document = XliffDocument::parse(text)?;
+        let segments = document.segments(from)?;
+        let mut translations = vec![String::new(); segments.len()];
+        let mut groups: Vec<(Option<Language>, Language)> = vec![];
+        for segment in &segments {
+            if !groups.contains(&(segment.from, segment.to)) {
+                groups.push((segment.from, segment.to));
+            }
+        }
+        for (from, to) in groups {
+            let indices = segments
+                .iter()
+                .enumerate()
+                .filter(|v| v.1.from == from && v.1.to == to)
+                .map(|v| v.0)
+                .collect::<Vec<_>>();
+            let result = self.translate_segments_to(
+                indices
+                    .iter()
+                    .map(|v| segments[*v].text.text.to_string())
+                    .collect(),
+                from,
+                to,
+                context_data,
+                options,
+            )?;
+            for (i, translation) in indices.into_iter().zip(result) {
+                translations[i] = translation;
+            }
+        }
+        // back to front, so the node indices stay valid
+        for (segment, translation) in segments.iter().zip(translations).rev() {
+            document.apply(segment, &translation, xliff_options);
+        }
+        Ok(document.to_text())
+    }
+}
+
+/// Parses a language code like de or de-DE
+pub(crate) fn parse_language(code: &str) -> Option<Language> {
+    Language::from_str(code)
+        .ok()
+        .or_else(|| Language::from_str(code.split(['-', '_']).next()?).ok())
+        .filter(|v| *v != Language::Unknown)
+}
+
+/// Checks for translate="no" and locked attributes
+fn is_locked(tag: &Tag) -> bool {
+    tag.attribute("translate") == Some("no")
+        || tag
+            .attributes
+            .iter()
+            .any(|v| v.name.ends_with("locked") && v.value == "true")
+}
+
+/// Adds the text with decoded entities, unknown entities are protected
+fn push_decoded(protector: &mut Protector<Inline>, text: &str) {
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        protector.push_text(&rest[..start], |v| Inline::Standalone(escape_text(&v)));
+        rest = &rest[start..];
+        match entity_at(rest) {
+            Some(entity) => {
+                match decode_entity(&entity[1..entity.len() - 1]) {
+                    Some(c) => protector.text.push(c),
+                    None => protector.push_value(Inline::Standalone(entity.to_string())),
+                }
+                rest = &rest[entity.len()..];
+            }
+            None => {
+                protector.text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    protector.push_text(rest, |v| Inline::Standalone(escape_text(&v)));
+}
+
+/// Sets or adds an attribute of a tag
+fn set_attribute(tag: &Tag, name: &str, value: &str) -> String {
+    let mut tag = tag.clone();
+    if let Some(attribute) = tag.attributes.iter_mut().find(|v| v.name == name) {
+        attribute.translation = Some(escape_attribute(value));
+        return tag.to_markup();
+    }
+    let raw = tag.to_markup();
+    let end = match tag.kind {
+        TagKind::SelfClosing => raw
+            .trim_end_matches('>')
+            .trim_end_matches('/')
+            .trim_end()
+            .len(),
+        _ => raw.len() - 1,
+    };
+    format!(
+        "{} {}=\"{}\"{}",
+        &raw[..end],
+        name,
+        escape_attribute(value),
+        &raw[end..]
+    )
+}
+
+/// Checks if opening and closing inline elements are nested correctly
+fn is_balanced(values: &[Inline], order: impl Iterator<Item = usize>) -> bool {
+    let mut stack = vec![];
+    for i in order {
+        match &values[i] {
+            Inline::Open(name, _) => stack.push(name),
+            Inline::Close(name, _) => {
+                if stack.pop() != Some(name) {
+                    return false;
+                }
+            }
+            Inline::Standalone(_) => {}
+        }
+    }
+    stack.is_empty()
+}
+
+/// Replaces the placeholders with the inline elements.
+/// When placeholders are missing or in the wrong order, the inline elements keep their original order.
+fn restore(text: &Protector<Inline>, translation: &str) -> String {
+    let pieces = split(translation);
+    let order = pieces.iter().filter_map(|v| match v {
+        Piece::Placeholder(v) => Some(*v),
+        Piece::Text(_) => None,
+    });
+    if text.is_complete(&pieces) && is_balanced(&text.values, order) {
+        return pieces
+            .iter()
+            .map(|v| match v {
+                Piece::Text(v) => escape_text(v),
+                Piece::Placeholder(v) => text.values[*v].raw().to_string(),
+            })
+            .collect();
+    }
+    warn!("Placeholders in translation \"{}\" dont match", translation);
+    let translated = pieces
+        .iter()
+        .filter_map(|v| match v {
+            Piece::Text(v) => Some(escape_text(v)),
+            Piece::Placeholder(_) => None,
+        })
+        .collect::<String>();
+    let first_text = split(&text.text)
+        .iter()
+        .position(|v| matches!(v, Piece::Text(_)))
+        .unwrap_or(0);
+    let mut result = String::new();
+    for (i, value) in text.values.iter().enumerate() {
+        if i == first_text {
+            result.push_str(&translated);
+        }
+        result.push_str(value.raw());
+    }
+    if first_text >= text.values.len() {
+        result.push_str(&translated);
+    }
+    result
+}
@@ -110,7 +110,9 @@ impl Records {
                 enum_name
             ));
         }
-        v.push_variant(Variant::new("Unknown"));
+        let mut unknown = Variant::new("Unknown");
+        unknown.annotation("#[default]");
+        v.push_variant(unknown);
 
         let im_from = scope.new_impl("FromStr for Language");
 
@@ -134,7 +136,7 @@ impl Records {
         file.write_all(b"///This file is auto generated.\n\n")?;
         file.write_all(b"use crate::error::Error;\n")?;
         file.write_all(b"use std::str::FromStr;\n")?;
-        file.write_all(b"#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]\n")?;
+        file.write_all(b"#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default, serde::Serialize, serde::Deserialize)]\n")?;
         file.write_all(b"pub ")?;
         file.write_all(scope.to_string().as_bytes())?;
         Ok(())
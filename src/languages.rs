@@ -3,7 +3,9 @@ use std::str::FromStr;
 ///This file is auto generated.
 use crate::error::Error;
 
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+#[derive(
+    PartialEq, Eq, Hash, Copy, Clone, Debug, Default, serde::Serialize, serde::Deserialize,
+)]
 pub enum Language {
     /// Code: ach
     Acoli,
@@ -621,6 +623,7 @@ pub enum Language {
     Waray,
     /// Code: zsm
     MalaysianMalay,
+    #[default]
     Unknown,
 }
 
@@ -630,46 +633,64 @@ impl FromStr for Language {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "Acoli" | "ach" => Ok(Language::Acoli),
-            "Afrikaans" | "afr" | "af" | "af-ZA" | "afr_Latn" => Ok(Language::Afrikaans),
-            "Akan" | "aka" | "ak" | "aka_Latn" => Ok(Language::Akan),
-            "Albanian" | "sqi" | "sq" | "sq-AL" | "alb" => Ok(Language::Albanian),
+            "Afrikaans" | "afr" | "af" | "af-ZA" | "afr_Latn" | "af_ZA" | "<2af>" => {
+                Ok(Language::Afrikaans)
+            }
+            "Akan" | "aka" | "ak" | "aka_Latn" | "<2ak>" => Ok(Language::Akan),
+            "Albanian" | "sqi" | "sq" | "sq-AL" | "alb" | "<2sq>" => Ok(Language::Albanian),
             "Algerian" | "arq" => Ok(Language::Algerian),
-            "Amharic" | "amh" | "am" | "am-ET" | "amh_Ethi" => Ok(Language::Amharic),
+            "Amharic" | "amh" | "am" | "am-ET" | "amh_Ethi" | "<2am>" => Ok(Language::Amharic),
             "Ancient Greek" | "grc" | "grc-GR" => Ok(Language::AncientGreek),
             "Antiguan" | "aig" | "aig-AG" => Ok(Language::Antiguan),
-            "Arabic" | "ara" | "ar" | "ar-SA" => Ok(Language::Arabic),
+            "Arabic" | "ara" | "ar" | "ar-SA" | "ar_AR" | "<2ar>" => Ok(Language::Arabic),
             "Aragonese" | "arg" | "an" => Ok(Language::Aragonese),
-            "Armenian" | "hye" | "hy" | "hy-AM" | "arm" | "hye_Armn" => Ok(Language::Armenian),
-            "Assamese" | "asm" | "as" | "asm_Beng" => Ok(Language::Assamese),
-            "Asturian" | "ast" | "ast_Latn" => Ok(Language::Asturian),
-            "Aymara" | "aym" | "ay" | "ayr_Latn" => Ok(Language::Aymara),
-            "Azerbaijani" | "aze" | "az" | "az-AZ" | "azb_Arab" => Ok(Language::Azerbaijani),
+            "Armenian" | "hye" | "hy" | "hy-AM" | "arm" | "hye_Armn" | "<2hy>" => {
+                Ok(Language::Armenian)
+            }
+            "Assamese" | "asm" | "as" | "asm_Beng" | "<2as>" => Ok(Language::Assamese),
+            "Asturian" | "ast" | "ast_Latn" | "<2ast>" => Ok(Language::Asturian),
+            "Aymara" | "aym" | "ay" | "ayr_Latn" | "<2ay>" => Ok(Language::Aymara),
+            "Azerbaijani" | "aze" | "az" | "az-AZ" | "azb_Arab" | "az_AZ" | "<2az>" => {
+                Ok(Language::Azerbaijani)
+            }
             "Bahamian" | "bah" | "bah-BS" => Ok(Language::Bahamian),
             "Bajan Creole" | "bjs" | "bjs-BB" => Ok(Language::BajanCreole),
             "Balochi" | "bal" => Ok(Language::Balochi),
-            "Bambara" | "bam" | "bm" | "bam_Latn" => Ok(Language::Bambara),
+            "Bambara" | "bam" | "bm" | "bam_Latn" | "<2bm>" => Ok(Language::Bambara),
             "Barí" | "mot" => Ok(Language::Barí),
-            "Bashkir" | "bak" | "ba" | "bak_Cyrl" => Ok(Language::Bashkir),
-            "Basque" | "eus" | "eu" | "eu-ES" | "baq" | "eus_Latn" => Ok(Language::Basque),
-            "Belarusian" | "bel" | "be" | "be-BY" | "bel_Cyrl" => Ok(Language::Belarusian),
+            "Bashkir" | "bak" | "ba" | "bak_Cyrl" | "<2ba>" => Ok(Language::Bashkir),
+            "Basque" | "eus" | "eu" | "eu-ES" | "baq" | "eus_Latn" | "<2eu>" => {
+                Ok(Language::Basque)
+            }
+            "Belarusian" | "bel" | "be" | "be-BY" | "bel_Cyrl" | "<2be>" => {
+                Ok(Language::Belarusian)
+            }
             "Bemba" | "bem" | "bem-ZM" | "bem_Latn" => Ok(Language::Bemba),
-            "Bengali" | "ben" | "bn" | "bn-IN" | "ben_Beng" => Ok(Language::Bengali),
+            "Bengali" | "ben" | "bn" | "bn-IN" | "ben_Beng" | "bn_IN" | "<2bn>" => {
+                Ok(Language::Bengali)
+            }
             "Berber" | "ber" => Ok(Language::Berber),
             "Bhili" | "gra" => Ok(Language::Bhili),
             "Bhojpuri" | "bho" | "bho_Deva" => Ok(Language::Bhojpuri),
             "Bislama" | "bis" | "bi" | "bi-VU" => Ok(Language::Bislama),
             "Bokmål" | "nob" | "nb" | "NB" | "nob_Latn" => Ok(Language::Bokmål),
-            "Bosnian" | "bos" | "bs" | "bs-BA" | "bos_Latn" => Ok(Language::Bosnian),
-            "Breton" | "bre" | "br" | "br-FR" => Ok(Language::Breton),
-            "Bulgarian" | "bul" | "bg" | "BG" | "bg-BG" | "bul_Cyrl" => Ok(Language::Bulgarian),
-            "Burmese" | "mya" | "my" | "my-MM" | "bur" | "mya_Mymr" => Ok(Language::Burmese),
+            "Bosnian" | "bos" | "bs" | "bs-BA" | "bos_Latn" | "<2bs>" => Ok(Language::Bosnian),
+            "Breton" | "bre" | "br" | "br-FR" | "<2br>" => Ok(Language::Breton),
+            "Bulgarian" | "bul" | "bg" | "BG" | "bg-BG" | "bul_Cyrl" | "<2bg>" => {
+                Ok(Language::Bulgarian)
+            }
+            "Burmese" | "mya" | "my" | "my-MM" | "bur" | "mya_Mymr" | "my_MM" | "<2my>" => {
+                Ok(Language::Burmese)
+            }
             "Cape Verdean Creole" | "kea" | "kea-CV" | "kea_Latn" => {
                 Ok(Language::CapeVerdeanCreole)
             }
-            "Catalan" | "cat" | "ca" | "ca-ES" | "cat_Latn" => Ok(Language::Catalan),
-            "Cebuano" | "ceb" | "ceb_Latn" => Ok(Language::Cebuano),
-            "Central Khmer" | "khm" | "km" | "km-KM" | "khm_Khmr" => Ok(Language::CentralKhmer),
-            "Chamorro" | "cha" | "ch" | "ch-GU" => Ok(Language::Chamorro),
+            "Catalan" | "cat" | "ca" | "ca-ES" | "cat_Latn" | "<2ca>" => Ok(Language::Catalan),
+            "Cebuano" | "ceb" | "ceb_Latn" | "<2ceb>" => Ok(Language::Cebuano),
+            "Central Khmer" | "khm" | "km" | "km-KM" | "khm_Khmr" | "km_KH" | "<2km>" => {
+                Ok(Language::CentralKhmer)
+            }
+            "Chamorro" | "cha" | "ch" | "ch-GU" | "<2ch>" => Ok(Language::Chamorro),
             "Chinese" | "zho" | "zh" | "zh-CN" | "zh-Hans" | "ZH" | "zh-cn" | "zh-CHS"
             | "zho_Hant" => Ok(Language::Chinese),
             "Chinese Classical" | "lzh" => Ok(Language::ChineseClassical),
@@ -677,222 +698,302 @@ impl FromStr for Language {
             "Chinese Taiwanese" | "goyu" | "zh-TW" | "zh-Hant" | "zh-tw" => {
                 Ok(Language::ChineseTaiwanese)
             }
-            "Chinese Yue" | "yue" | "yue_Hant" => Ok(Language::ChineseYue),
+            "Chinese Yue" | "yue" | "yue_Hant" | "<2yue>" => Ok(Language::ChineseYue),
             "Cholón" | "cht" => Ok(Language::Cholón),
-            "Chuvash" | "chv" | "cv" => Ok(Language::Chuvash),
+            "Chuvash" | "chv" | "cv" | "<2cv>" => Ok(Language::Chuvash),
             "Comorian" | "zdj" | "zdj-KM" => Ok(Language::Comorian),
             "Coptic" | "cop" | "cop-EG" => Ok(Language::Coptic),
-            "Cornish" | "cor" | "kw" => Ok(Language::Cornish),
-            "Corsican" | "cos" | "co" => Ok(Language::Corsican),
+            "Cornish" | "cor" | "kw" | "<2kw>" => Ok(Language::Cornish),
+            "Corsican" | "cos" | "co" | "<2co>" => Ok(Language::Corsican),
             "Cree" | "cre" | "cr" => Ok(Language::Cree),
-            "Croatian" | "hrv" | "hr" | "hr-HR" | "hrv_Latn" => Ok(Language::Croatian),
-            "Czech" | "ces" | "cs" | "CS" | "cs-CZ" | "ces_Latn" => Ok(Language::Czech),
-            "Danish" | "dan" | "da" | "DA" | "da-DK" | "dan_Latn" => Ok(Language::Danish),
+            "Croatian" | "hrv" | "hr" | "hr-HR" | "hrv_Latn" | "hr_HR" | "<2hr>" => {
+                Ok(Language::Croatian)
+            }
+            "Czech" | "ces" | "cs" | "CS" | "cs-CZ" | "ces_Latn" | "cs_CZ" | "<2cs>" => {
+                Ok(Language::Czech)
+            }
+            "Danish" | "dan" | "da" | "DA" | "da-DK" | "dan_Latn" | "<2da>" => Ok(Language::Danish),
             "Dari" | "prs" | "prs_Arab" => Ok(Language::Dari),
             "Deg Xinag" | "ing" => Ok(Language::DegXinag),
-            "Divehi" | "div" | "dv" | "dv-MV" => Ok(Language::Divehi),
+            "Divehi" | "div" | "dv" | "dv-MV" | "<2dv>" => Ok(Language::Divehi),
             "Dogri" | "doi" => Ok(Language::Dogri),
-            "Dutch" | "nld" | "nl" | "NL" | "nl-NL" | "nld_Latn" => Ok(Language::Dutch),
-            "Dzongkha" | "dzo" | "dz" | "dz-BT" | "dzo_Tibt" => Ok(Language::Dzongkha),
+            "Dutch" | "nld" | "nl" | "NL" | "nl-NL" | "nld_Latn" | "nl_XX" | "<2nl>" => {
+                Ok(Language::Dutch)
+            }
+            "Dzongkha" | "dzo" | "dz" | "dz-BT" | "dzo_Tibt" | "<2dz>" => Ok(Language::Dzongkha),
             "Enggano" | "eno" => Ok(Language::Enggano),
             "English" | "eng" | "en" | "en-US" | "EN-US" | "en-GB" | "eng_Latn" => {
                 Ok(Language::English)
             }
-            "Esperanto" | "epo" | "eo" | "eo-EU" | "epo_Latn" => Ok(Language::Esperanto),
-            "Estonian" | "est" | "et" | "ET" | "et-EE" | "est_Latn" => Ok(Language::Estonian),
-            "Ewe" | "ewe" | "ee" | "ewe_Latn" => Ok(Language::Ewe),
-            "Faroese" | "fao" | "fo" | "fo-FO" | "fao_Latn" => Ok(Language::Faroese),
+            "Esperanto" | "epo" | "eo" | "eo-EU" | "epo_Latn" | "<2eo>" => Ok(Language::Esperanto),
+            "Estonian" | "est" | "et" | "ET" | "et-EE" | "est_Latn" | "et_EE" | "<2et>" => {
+                Ok(Language::Estonian)
+            }
+            "Ewe" | "ewe" | "ee" | "ewe_Latn" | "<2ee>" => Ok(Language::Ewe),
+            "Faroese" | "fao" | "fo" | "fo-FO" | "fao_Latn" | "<2fo>" => Ok(Language::Faroese),
             "Fer" | "kah" => Ok(Language::Fer),
-            "Fijian" | "fij" | "fj" | "fij_Latn" => Ok(Language::Fijian),
+            "Fijian" | "fij" | "fj" | "fij_Latn" | "<2fj>" => Ok(Language::Fijian),
             "Filipino" | "fil" => Ok(Language::Filipino),
-            "Finnish" | "fin" | "fi" | "FI" | "fi-FI" | "fin_Latn" => Ok(Language::Finnish),
+            "Finnish" | "fin" | "fi" | "FI" | "fi-FI" | "fin_Latn" | "fi_FI" | "<2fi>" => {
+                Ok(Language::Finnish)
+            }
             "Forro Creole" | "cri" => Ok(Language::ForroCreole),
-            "French" | "fra" | "fr" | "FR" | "fr-FR" | "fra_Latn" => Ok(Language::French),
-            "Fulah" | "ful" | "ff" | "fuv_Latn" => Ok(Language::Fulah),
-            "Gaelic" | "gla" | "gd" | "gd-GB" | "gla_Latn" => Ok(Language::Gaelic),
-            "Galician" | "glg" | "gl" | "gl-ES" | "glg_Latn" => Ok(Language::Galician),
-            "Ganda" | "lug" | "lg" | "lug_Latn" => Ok(Language::Ganda),
-            "Georgian" | "kat" | "ka" | "ka-GE" | "geo" | "kat_Geor" => Ok(Language::Georgian),
-            "German" | "deu" | "de" | "DE" | "de-DE" | "deu_Latn" => Ok(Language::German),
-            "Greek" | "ell" | "el" | "EL" | "el-GR" | "ell_Grek" => Ok(Language::Greek),
+            "French" | "fra" | "fr" | "FR" | "fr-FR" | "fra_Latn" | "fr_XX" | "<2fr>" => {
+                Ok(Language::French)
+            }
+            "Fulah" | "ful" | "ff" | "fuv_Latn" | "<2ff>" => Ok(Language::Fulah),
+            "Gaelic" | "gla" | "gd" | "gd-GB" | "gla_Latn" | "<2gd>" => Ok(Language::Gaelic),
+            "Galician" | "glg" | "gl" | "gl-ES" | "glg_Latn" | "gl_ES" | "<2gl>" => {
+                Ok(Language::Galician)
+            }
+            "Ganda" | "lug" | "lg" | "lug_Latn" | "<2lg>" => Ok(Language::Ganda),
+            "Georgian" | "kat" | "ka" | "ka-GE" | "geo" | "kat_Geor" | "ka_GE" | "<2ka>" => {
+                Ok(Language::Georgian)
+            }
+            "German" | "deu" | "de" | "DE" | "de-DE" | "deu_Latn" | "de_DE" | "<2de>" => {
+                Ok(Language::German)
+            }
+            "Greek" | "ell" | "el" | "EL" | "el-GR" | "ell_Grek" | "<2el>" => Ok(Language::Greek),
             "Grenadian Creole English" | "gcl" | "gcl-GD" => Ok(Language::GrenadianCreoleEnglish),
-            "Guarani" | "grn" | "gn" | "grn_Latn" => Ok(Language::Guarani),
+            "Guarani" | "grn" | "gn" | "grn_Latn" | "<2gn>" => Ok(Language::Guarani),
             "Guinea Bissau Creole" | "pov" | "pov-GW" => Ok(Language::GuineaBissauCreole),
-            "Gujarati" | "guj" | "gu" | "gu-IN" | "guj_Gujr" => Ok(Language::Gujarati),
+            "Gujarati" | "guj" | "gu" | "gu-IN" | "guj_Gujr" | "gu_IN" | "<2gu>" => {
+                Ok(Language::Gujarati)
+            }
             "Guyanese Creole" | "gyn" | "gyn-GY" => Ok(Language::GuyaneseCreole),
-            "Haitian" | "hat" | "ht" | "ht-HT" | "hat_Latn" => Ok(Language::Haitian),
-            "Hausa" | "hau" | "ha" | "ha-NE" | "hau_Latn" => Ok(Language::Hausa),
-            "Hawaiian" | "haw" | "haw-US" => Ok(Language::Hawaiian),
-            "Hebrew" | "heb" | "he" | "iw" | "he-IL" | "heb_Hebr" => Ok(Language::Hebrew),
-            "Hiligaynon" | "hil" => Ok(Language::Hiligaynon),
-            "Hindi" | "hin" | "hi" | "hi-IN" | "hin_Deva" => Ok(Language::Hindi),
-            "Hmong" | "hmn" | "mww" => Ok(Language::Hmong),
-            "Hungarian" | "hun" | "hu" | "HU" | "hu-HU" | "hun_Latn" => Ok(Language::Hungarian),
+            "Haitian" | "hat" | "ht" | "ht-HT" | "hat_Latn" | "<2ht>" => Ok(Language::Haitian),
+            "Hausa" | "hau" | "ha" | "ha-NE" | "hau_Latn" | "<2ha>" => Ok(Language::Hausa),
+            "Hawaiian" | "haw" | "haw-US" | "<2haw>" => Ok(Language::Hawaiian),
+            "Hebrew" | "heb" | "he" | "iw" | "he-IL" | "heb_Hebr" | "he_IL" | "<2he>" => {
+                Ok(Language::Hebrew)
+            }
+            "Hiligaynon" | "hil" | "<2hil>" => Ok(Language::Hiligaynon),
+            "Hindi" | "hin" | "hi" | "hi-IN" | "hin_Deva" | "hi_IN" | "<2hi>" => {
+                Ok(Language::Hindi)
+            }
+            "Hmong" | "hmn" | "mww" | "<2hmn>" => Ok(Language::Hmong),
+            "Hungarian" | "hun" | "hu" | "HU" | "hu-HU" | "hun_Latn" | "<2hu>" => {
+                Ok(Language::Hungarian)
+            }
             "Hupa" | "hup" => Ok(Language::Hupa),
-            "Icelandic" | "isl" | "is" | "is-IS" | "ice" | "isl_Latn" => Ok(Language::Icelandic),
-            "Ido" | "ido" | "io" => Ok(Language::Ido),
-            "Igbo" | "ibo" | "ig" | "ibo_Latn" => Ok(Language::Igbo),
-            "Ilocano" | "ilo" | "ilo_Latn" => Ok(Language::Ilocano),
-            "Indonesian" | "ind" | "id" | "ID" | "id-ID" | "ind_Latn" => Ok(Language::Indonesian),
-            "Interlingua" | "ina" | "ia" => Ok(Language::Interlingua),
-            "Inuktitut" | "iku" | "iu" => Ok(Language::Inuktitut),
+            "Icelandic" | "isl" | "is" | "is-IS" | "ice" | "isl_Latn" | "<2is>" => {
+                Ok(Language::Icelandic)
+            }
+            "Ido" | "ido" | "io" | "<2io>" => Ok(Language::Ido),
+            "Igbo" | "ibo" | "ig" | "ibo_Latn" | "<2ig>" => Ok(Language::Igbo),
+            "Ilocano" | "ilo" | "ilo_Latn" | "<2ilo>" => Ok(Language::Ilocano),
+            "Indonesian" | "ind" | "id" | "ID" | "id-ID" | "ind_Latn" | "id_ID" | "<2id>" => {
+                Ok(Language::Indonesian)
+            }
+            "Interlingua" | "ina" | "ia" | "<2ia>" => Ok(Language::Interlingua),
+            "Inuktitut" | "iku" | "iu" | "<2iu>" => Ok(Language::Inuktitut),
             "Inuvialuktun" | "ikt" => Ok(Language::Inuvialuktun),
-            "Irish" | "gle" | "ga" | "ga-IE" | "gle_Latn" => Ok(Language::Irish),
-            "Italian" | "ita" | "it" | "IT" | "it-IT" | "ita_Latn" => Ok(Language::Italian),
+            "Irish" | "gle" | "ga" | "ga-IE" | "gle_Latn" | "<2ga>" => Ok(Language::Irish),
+            "Italian" | "ita" | "it" | "IT" | "it-IT" | "ita_Latn" | "it_IT" | "<2it>" => {
+                Ok(Language::Italian)
+            }
             "Jamaican Patois" | "jam" | "jam-JM" => Ok(Language::JamaicanPatois),
-            "Japanese" | "jpn" | "ja" | "JA" | "ja-JP" | "jpn_Jpan" => Ok(Language::Japanese),
-            "Javanese" | "jav" | "jv" | "jw" | "jv-ID" | "jav_Latn" => Ok(Language::Javanese),
+            "Japanese" | "jpn" | "ja" | "JA" | "ja-JP" | "jpn_Jpan" | "ja_XX" | "<2ja>" => {
+                Ok(Language::Japanese)
+            }
+            "Javanese" | "jav" | "jv" | "jw" | "jv-ID" | "jav_Latn" | "<2jv>" => {
+                Ok(Language::Javanese)
+            }
             "Kabyle" | "kab" | "kab-DZ" | "kab_Latn" => Ok(Language::Kabyle),
-            "Kalaallisut" | "kal" | "kl" | "kl-GL" => Ok(Language::Kalaallisut),
+            "Kalaallisut" | "kal" | "kl" | "kl-GL" | "<2kl>" => Ok(Language::Kalaallisut),
             "Kalumpang" | "kli" => Ok(Language::Kalumpang),
-            "Kannada" | "kan" | "kn" | "kn-IN" | "kan_Knda" => Ok(Language::Kannada),
+            "Kannada" | "kan" | "kn" | "kn-IN" | "kan_Knda" | "<2kn>" => Ok(Language::Kannada),
             "Kanuri" | "kau" | "kr" => Ok(Language::Kanuri),
             "Kapampangan" | "pam" => Ok(Language::Kapampangan),
-            "Kashmiri" | "kas" | "ks" | "kas_Arab" => Ok(Language::Kashmiri),
-            "Kazakh" | "kaz" | "kk" | "kk-KZ" | "kaz_Cyrl" => Ok(Language::Kazakh),
-            "Kinyarwanda" | "kin" | "rw" | "rw-RW" | "kin_Latn" => Ok(Language::Kinyarwanda),
-            "Kirghiz" | "kir" | "ky" | "ky-KG" | "kir_Cyrl" => Ok(Language::Kirghiz),
-            "Kongo" | "kon" | "kg" | "kon_Latn" => Ok(Language::Kongo),
+            "Kashmiri" | "kas" | "ks" | "kas_Arab" | "<2ks>" => Ok(Language::Kashmiri),
+            "Kazakh" | "kaz" | "kk" | "kk-KZ" | "kaz_Cyrl" | "kk_KZ" | "<2kk>" => {
+                Ok(Language::Kazakh)
+            }
+            "Kinyarwanda" | "kin" | "rw" | "rw-RW" | "kin_Latn" | "<2rw>" => {
+                Ok(Language::Kinyarwanda)
+            }
+            "Kirghiz" | "kir" | "ky" | "ky-KG" | "kir_Cyrl" | "<2ky>" => Ok(Language::Kirghiz),
+            "Kongo" | "kon" | "kg" | "kon_Latn" | "<2kg>" => Ok(Language::Kongo),
             "Konkani" | "kok" | "gom" => Ok(Language::Konkani),
-            "Korean" | "kor" | "ko" | "KO" | "ko-KR" | "kor_Hang" => Ok(Language::Korean),
+            "Korean" | "kor" | "ko" | "KO" | "ko-KR" | "kor_Hang" | "ko_KR" | "<2ko>" => {
+                Ok(Language::Korean)
+            }
             "Krio" | "kri" => Ok(Language::Krio),
-            "Kurdish" | "kur" | "ku" | "ku-TR" => Ok(Language::Kurdish),
+            "Kurdish" | "kur" | "ku" | "ku-TR" | "<2ku>" => Ok(Language::Kurdish),
             "Kurmanji" | "kmr" | "kmr_Latn" => Ok(Language::Kurmanji),
-            "Lao" | "lao" | "lo" | "lo-LA" | "lao_Laoo" => Ok(Language::Lao),
-            "Latin" | "lat" | "la" | "la-VA" => Ok(Language::Latin),
-            "Latvian" | "lav" | "lv" | "LV" | "lv-LV" | "lvs_Latn" => Ok(Language::Latvian),
+            "Lao" | "lao" | "lo" | "lo-LA" | "lao_Laoo" | "<2lo>" => Ok(Language::Lao),
+            "Latin" | "lat" | "la" | "la-VA" | "<2la>" => Ok(Language::Latin),
+            "Latvian" | "lav" | "lv" | "LV" | "lv-LV" | "lvs_Latn" | "lv_LV" | "<2lv>" => {
+                Ok(Language::Latvian)
+            }
             "Lia Ntomba" | "bli" => Ok(Language::LiaNtomba),
-            "Limburgan" | "lim" | "li" | "lim_Latn" => Ok(Language::Limburgan),
-            "Lingala" | "lin" | "ln" | "lin_Latn" => Ok(Language::Lingala),
-            "Lithuanian" | "lit" | "lt" | "LT" | "lt-LT" | "lit_Latn" => Ok(Language::Lithuanian),
+            "Limburgan" | "lim" | "li" | "lim_Latn" | "<2li>" => Ok(Language::Limburgan),
+            "Lingala" | "lin" | "ln" | "lin_Latn" | "<2ln>" => Ok(Language::Lingala),
+            "Lithuanian" | "lit" | "lt" | "LT" | "lt-LT" | "lit_Latn" | "lt_LT" | "<2lt>" => {
+                Ok(Language::Lithuanian)
+            }
             "Logo" | "log" => Ok(Language::Logo),
             "Logudorese" | "src" => Ok(Language::Logudorese),
             "Loniu" | "los" => Ok(Language::Loniu),
             "Lou" | "loj" => Ok(Language::Lou),
-            "Luxembourgish" | "ltz" | "lb" | "lb-LU" | "ltz_Latn" => Ok(Language::Luxembourgish),
-            "Macedonian" | "mkd" | "mk" | "mk-MK" | "mac" | "mkd_Cyrl" => Ok(Language::Macedonian),
+            "Luxembourgish" | "ltz" | "lb" | "lb-LU" | "ltz_Latn" | "<2lb>" => {
+                Ok(Language::Luxembourgish)
+            }
+            "Macedonian" | "mkd" | "mk" | "mk-MK" | "mac" | "mkd_Cyrl" | "mk_MK" | "<2mk>" => {
+                Ok(Language::Macedonian)
+            }
             "Maithili" | "mai" | "mai_Deva" => Ok(Language::Maithili),
             "Mala" | "ped" => Ok(Language::Mala),
             "Mala Nigeria" | "ruy" => Ok(Language::MalaNigeria),
-            "Malagasy" | "mlg" | "mg" | "mg-MG" | "plt_Latn" => Ok(Language::Malagasy),
-            "Malay" | "msa" | "ms" | "ms-MY" | "may" => Ok(Language::Malay),
-            "Malayalam" | "mal" | "ml" | "mal_Mlym" => Ok(Language::Malayalam),
-            "Maltese" | "mlt" | "mt" | "mt-MT" | "mlt_Latn" => Ok(Language::Maltese),
-            "Manx" | "glv" | "gv" | "gv-IM" => Ok(Language::Manx),
-            "Maori" | "mri" | "mi" | "mi-NZ" | "mri_Latn" => Ok(Language::Maori),
-            "Marathi" | "mar" | "mr" | "mar_Deva" => Ok(Language::Marathi),
+            "Malagasy" | "mlg" | "mg" | "mg-MG" | "plt_Latn" | "<2mg>" => Ok(Language::Malagasy),
+            "Malay" | "msa" | "ms" | "ms-MY" | "may" | "<2ms>" => Ok(Language::Malay),
+            "Malayalam" | "mal" | "ml" | "mal_Mlym" | "ml_IN" | "<2ml>" => Ok(Language::Malayalam),
+            "Maltese" | "mlt" | "mt" | "mt-MT" | "mlt_Latn" | "<2mt>" => Ok(Language::Maltese),
+            "Manx" | "glv" | "gv" | "gv-IM" | "<2gv>" => Ok(Language::Manx),
+            "Maori" | "mri" | "mi" | "mi-NZ" | "mri_Latn" | "<2mi>" => Ok(Language::Maori),
+            "Marathi" | "mar" | "mr" | "mar_Deva" | "mr_IN" | "<2mr>" => Ok(Language::Marathi),
             "Marshallese" | "mah" | "mh" | "mh-MH" => Ok(Language::Marshallese),
             "Mauritian Creole" | "mfe" | "mfe-MU" => Ok(Language::MauritianCreole),
             "Mazatecan" | "mau" => Ok(Language::Mazatecan),
             "Mende" | "men" | "men-SL" => Ok(Language::Mende),
             "Middle French" | "frm" => Ok(Language::MiddleFrench),
             "Mizo" | "lus" | "lus_Latn" => Ok(Language::Mizo),
-            "Mongolian" | "mon" | "mn" | "mn-MN" | "khk" => Ok(Language::Mongolian),
+            "Mongolian" | "mon" | "mn" | "mn-MN" | "khk" | "mn_MN" | "<2mn>" => {
+                Ok(Language::Mongolian)
+            }
             "Māori" | "mao" => Ok(Language::Māori),
             "NKo" | "nqo" => Ok(Language::NKo),
-            "Nepali" | "nep" | "ne" | "ne-NP" => Ok(Language::Nepali),
+            "Nepali" | "nep" | "ne" | "ne-NP" | "ne_NP" | "<2ne>" => Ok(Language::Nepali),
             "Ngadha" | "nea" => Ok(Language::Ngadha),
             "Niuean" | "niu" | "niu-NU" => Ok(Language::Niuean),
-            "Northern Sami" | "sme" | "se" => Ok(Language::NorthernSami),
-            "Norwegian" | "nor" | "no" | "no-NO" => Ok(Language::Norwegian),
-            "Nyanja" | "nya" | "ny" | "ny-MW" | "nya_Latn" => Ok(Language::Nyanja),
+            "Northern Sami" | "sme" | "se" | "<2se>" => Ok(Language::NorthernSami),
+            "Norwegian" | "nor" | "no" | "no-NO" | "<2no>" => Ok(Language::Norwegian),
+            "Nyanja" | "nya" | "ny" | "ny-MW" | "nya_Latn" | "<2ny>" => Ok(Language::Nyanja),
             "Nynorsk" | "nno" | "nn" | "nno_Latn" => Ok(Language::Nynorsk),
-            "Occitan" | "oci" | "oc" | "oci_Latn" => Ok(Language::Occitan),
+            "Occitan" | "oci" | "oc" | "oci_Latn" | "<2oc>" => Ok(Language::Occitan),
             "Ojibwa" | "oji" | "oj" => Ok(Language::Ojibwa),
-            "Oriya" | "ori" | "or" => Ok(Language::Oriya),
-            "Oromo" | "orm" | "om" | "gaz_Latn" => Ok(Language::Oromo),
-            "Ossetian" | "oss" | "os" => Ok(Language::Ossetian),
+            "Oriya" | "ori" | "or" | "<2or>" => Ok(Language::Oriya),
+            "Oromo" | "orm" | "om" | "gaz_Latn" | "<2om>" => Ok(Language::Oromo),
+            "Ossetian" | "oss" | "os" | "<2os>" => Ok(Language::Ossetian),
             "Otomi Northwestern" | "otq" => Ok(Language::OtomiNorthwestern),
             "Palauan" | "pau" | "pau-PW" => Ok(Language::Palauan),
-            "Panjabi" | "pan" | "pa" | "pa-IN" | "pan_Guru" => Ok(Language::Panjabi),
+            "Panjabi" | "pan" | "pa" | "pa-IN" | "pan_Guru" | "<2pa>" => Ok(Language::Panjabi),
             "Papiamento" | "pap" | "pap-CW" | "pap_Latn" => Ok(Language::Papiamento),
-            "Persian" | "per" | "fa" | "fa-IR" => Ok(Language::Persian),
+            "Persian" | "per" | "fa" | "fa-IR" | "fa_IR" | "<2fa>" => Ok(Language::Persian),
             "Pijin" | "pis" | "pis-SB" => Ok(Language::Pijin),
-            "Polish" | "pol" | "pl" | "PL" | "pl-PL" | "pol_Latn" => Ok(Language::Polish),
+            "Polish" | "pol" | "pl" | "PL" | "pl-PL" | "pol_Latn" | "pl_PL" | "<2pl>" => {
+                Ok(Language::Polish)
+            }
             "Portuguese" | "por" | "pt" | "PT-BR" | "pt-PT" | "por_Latn" => {
                 Ok(Language::Portuguese)
             }
             "Potawatomi" | "pot" | "pot-US" => Ok(Language::Potawatomi),
-            "Pushto" | "pus" | "ps" | "ps-PK" => Ok(Language::Pushto),
-            "Quechua" | "que" | "qu" | "qu-PE" => Ok(Language::Quechua),
+            "Pushto" | "pus" | "ps" | "ps-PK" | "ps_AF" | "<2ps>" => Ok(Language::Pushto),
+            "Quechua" | "que" | "qu" | "qu-PE" | "<2qu>" => Ok(Language::Quechua),
             "Rangi" | "lag" => Ok(Language::Rangi),
             "Romani" | "rom" => Ok(Language::Romani),
-            "Romanian" | "ron" | "ro" | "RO" | "ro-RO" | "ron_Latn" => Ok(Language::Romanian),
-            "Romansh" | "roh" | "rm" | "rm-RO" => Ok(Language::Romansh),
-            "Rundi" | "run" | "rn" | "rn-BI" | "run_Latn" => Ok(Language::Rundi),
-            "Russian" | "rus" | "ru" | "RU" | "ru-RU" | "rus_Cyrl" => Ok(Language::Russian),
+            "Romanian" | "ron" | "ro" | "RO" | "ro-RO" | "ron_Latn" | "ro_RO" | "<2ro>" => {
+                Ok(Language::Romanian)
+            }
+            "Romansh" | "roh" | "rm" | "rm-RO" | "<2rm>" => Ok(Language::Romansh),
+            "Rundi" | "run" | "rn" | "rn-BI" | "run_Latn" | "<2rn>" => Ok(Language::Rundi),
+            "Russian" | "rus" | "ru" | "RU" | "ru-RU" | "rus_Cyrl" | "ru_RU" | "<2ru>" => {
+                Ok(Language::Russian)
+            }
             "Saint Lucian Creole" | "acf" | "acf-LC" => Ok(Language::SaintLucianCreole),
-            "Samoan" | "smo" | "sm" | "sm-WS" | "smo_Latn" => Ok(Language::Samoan),
-            "Sango" | "sag" | "sg" | "sg-CF" | "sag_Latn" => Ok(Language::Sango),
-            "Sanskrit" | "san" | "sa" | "san_Deva" => Ok(Language::Sanskrit),
-            "Sardinian" | "srd" | "sc" | "srd_Latn" => Ok(Language::Sardinian),
+            "Samoan" | "smo" | "sm" | "sm-WS" | "smo_Latn" | "<2sm>" => Ok(Language::Samoan),
+            "Sango" | "sag" | "sg" | "sg-CF" | "sag_Latn" | "<2sg>" => Ok(Language::Sango),
+            "Sanskrit" | "san" | "sa" | "san_Deva" | "<2sa>" => Ok(Language::Sanskrit),
+            "Sardinian" | "srd" | "sc" | "srd_Latn" | "<2sc>" => Ok(Language::Sardinian),
             "Scots" | "sco" => Ok(Language::Scots),
             "Sechelt" | "sec" => Ok(Language::Sechelt),
-            "Serbian" | "srp" | "sr" | "sr-RS" | "srp_Cyrl" => Ok(Language::Serbian),
+            "Serbian" | "srp" | "sr" | "sr-RS" | "srp_Cyrl" | "<2sr>" => Ok(Language::Serbian),
             "Seychellois Creole" | "crs" | "crs-SC" => Ok(Language::SeychelloisCreole),
             "Shall Zwall" | "sha" => Ok(Language::ShallZwall),
-            "Shona" | "sna" | "sn" | "sn-ZW" | "sna_Latn" => Ok(Language::Shona),
-            "Sindhi" | "snd" | "sd" | "snd_Arab" => Ok(Language::Sindhi),
-            "Sinhala" | "sin" | "si" | "si-LK" | "sin_Sinh" => Ok(Language::Sinhala),
+            "Shona" | "sna" | "sn" | "sn-ZW" | "sna_Latn" | "<2sn>" => Ok(Language::Shona),
+            "Sindhi" | "snd" | "sd" | "snd_Arab" | "<2sd>" => Ok(Language::Sindhi),
+            "Sinhala" | "sin" | "si" | "si-LK" | "sin_Sinh" | "si_LK" | "<2si>" => {
+                Ok(Language::Sinhala)
+            }
             "Sisaala" | "sil" => Ok(Language::Sisaala),
-            "Slovak" | "slk" | "sk" | "SK" | "sk-SK" | "slk_Latn" => Ok(Language::Slovak),
-            "Slovenian" | "slv" | "sl" | "SL" | "sl-SI" | "slv_Latn" => Ok(Language::Slovenian),
+            "Slovak" | "slk" | "sk" | "SK" | "sk-SK" | "slk_Latn" | "<2sk>" => Ok(Language::Slovak),
+            "Slovenian" | "slv" | "sl" | "SL" | "sl-SI" | "slv_Latn" | "sl_SI" | "<2sl>" => {
+                Ok(Language::Slovenian)
+            }
             "Solos" | "sol" => Ok(Language::Solos),
-            "Somali" | "som" | "so" | "so-SO" | "som_Latn" => Ok(Language::Somali),
-            "Sorani" | "ckb" | "ckb-IQ" | "ckb_Arab" => Ok(Language::Sorani),
+            "Somali" | "som" | "so" | "so-SO" | "som_Latn" | "<2so>" => Ok(Language::Somali),
+            "Sorani" | "ckb" | "ckb-IQ" | "ckb_Arab" | "<2ckb>" => Ok(Language::Sorani),
             "Sorbian Lower" | "dsb" => Ok(Language::SorbianLower),
             "Sorbian Upper" | "hsb" => Ok(Language::SorbianUpper),
-            "Sotho" | "sot" | "st" | "st-ST" | "sot_Latn" => Ok(Language::Sotho),
+            "Sotho" | "sot" | "st" | "st-ST" | "sot_Latn" | "<2st>" => Ok(Language::Sotho),
             "Sotho Northern" | "nso" | "ns" | "nso_Latn" => Ok(Language::SothoNorthern),
             "Southern Ndebele" | "nbl" | "nr" => Ok(Language::SouthernNdebele),
-            "Spanish" | "spa" | "es" | "ES" | "es-ES" | "spa_Latn" => Ok(Language::Spanish),
+            "Spanish" | "spa" | "es" | "ES" | "es-ES" | "spa_Latn" | "es_XX" | "<2es>" => {
+                Ok(Language::Spanish)
+            }
             "Sranan Tongo" | "srn" | "srn-SR" => Ok(Language::SrananTongo),
-            "Sundanese" | "sun" | "su" | "sun_Latn" => Ok(Language::Sundanese),
-            "Swahili" | "swa" | "sw" | "sw-SZ" => Ok(Language::Swahili),
-            "Swati" | "ssw" | "ss" | "ssw_Latn" => Ok(Language::Swati),
-            "Swedish" | "swe" | "sv" | "SV" | "sv-SE" | "swe_Latn" => Ok(Language::Swedish),
+            "Sundanese" | "sun" | "su" | "sun_Latn" | "<2su>" => Ok(Language::Sundanese),
+            "Swahili" | "swa" | "sw" | "sw-SZ" | "sw_KE" | "<2sw>" => Ok(Language::Swahili),
+            "Swati" | "ssw" | "ss" | "ssw_Latn" | "<2ss>" => Ok(Language::Swati),
+            "Swedish" | "swe" | "sv" | "SV" | "sv-SE" | "swe_Latn" | "sv_SE" | "<2sv>" => {
+                Ok(Language::Swedish)
+            }
             "Syriac" | "syc" | "syc-TR" => Ok(Language::Syriac),
-            "Tagalog" | "tgl" | "tl" | "tl-PH" | "tgl_Latn" => Ok(Language::Tagalog),
-            "Tahitian" | "tah" | "ty" => Ok(Language::Tahitian),
-            "Tajik" | "tgk" | "tg" | "tg-TJ" | "tgk_Cyrl" => Ok(Language::Tajik),
-            "Tamil" | "tam" | "ta" | "ta-LK" | "tam_Taml" => Ok(Language::Tamil),
-            "Tatar" | "tat" | "tt" | "tat_Cyrl" => Ok(Language::Tatar),
-            "Telugu" | "tel" | "te" | "te-IN" | "tel_Telu" => Ok(Language::Telugu),
+            "Tagalog" | "tgl" | "tl" | "tl-PH" | "tgl_Latn" | "tl_XX" | "<2fil>" => {
+                Ok(Language::Tagalog)
+            }
+            "Tahitian" | "tah" | "ty" | "<2ty>" => Ok(Language::Tahitian),
+            "Tajik" | "tgk" | "tg" | "tg-TJ" | "tgk_Cyrl" | "<2tg>" => Ok(Language::Tajik),
+            "Tamil" | "tam" | "ta" | "ta-LK" | "tam_Taml" | "ta_IN" | "<2ta>" => {
+                Ok(Language::Tamil)
+            }
+            "Tatar" | "tat" | "tt" | "tat_Cyrl" | "<2tt>" => Ok(Language::Tatar),
+            "Telugu" | "tel" | "te" | "te-IN" | "tel_Telu" | "te_IN" | "<2te>" => {
+                Ok(Language::Telugu)
+            }
             "Tetum" | "tet" | "tet-TL" => Ok(Language::Tetum),
-            "Thai" | "tha" | "th" | "th-TH" | "tha_Thai" => Ok(Language::Thai),
-            "Tibetan" | "bod" | "bo" | "bo-CN" | "bod_Tibt" => Ok(Language::Tibetan),
-            "Tigrinya" | "tir" | "ti" | "ti-TI" | "tir_Ethi" => Ok(Language::Tigrinya),
+            "Thai" | "tha" | "th" | "th-TH" | "tha_Thai" | "th_TH" | "<2th>" => Ok(Language::Thai),
+            "Tibetan" | "bod" | "bo" | "bo-CN" | "bod_Tibt" | "<2bo>" => Ok(Language::Tibetan),
+            "Tigrinya" | "tir" | "ti" | "ti-TI" | "tir_Ethi" | "<2ti>" => Ok(Language::Tigrinya),
             "Tok Pisin" | "tpi" | "tpi-PG" | "tpi_Latn" => Ok(Language::TokPisin),
             "Tokelauan" | "tkl" | "tkl-TK" => Ok(Language::Tokelauan),
-            "Tonga" | "ton" | "to" | "to-TO" => Ok(Language::Tonga),
-            "Tsonga" | "tso" | "ts" | "tso_Latn" => Ok(Language::Tsonga),
-            "Tswana" | "tsn" | "tn" | "tn-BW" | "tsn_Latn" => Ok(Language::Tswana),
+            "Tonga" | "ton" | "to" | "to-TO" | "<2to>" => Ok(Language::Tonga),
+            "Tsonga" | "tso" | "ts" | "tso_Latn" | "<2ts>" => Ok(Language::Tsonga),
+            "Tswana" | "tsn" | "tn" | "tn-BW" | "tsn_Latn" | "<2tn>" => Ok(Language::Tswana),
             "Tuareg" | "tmh" | "tmh-DZ" => Ok(Language::Tuareg),
-            "Turkish" | "tur" | "tr" | "TR" | "tr-TR" | "tur_Latn" => Ok(Language::Turkish),
-            "Turkmen" | "tuk" | "tk" | "tk-TM" | "tuk_Latn" => Ok(Language::Turkmen),
+            "Turkish" | "tur" | "tr" | "TR" | "tr-TR" | "tur_Latn" | "tr_TR" | "<2tr>" => {
+                Ok(Language::Turkish)
+            }
+            "Turkmen" | "tuk" | "tk" | "tk-TM" | "tuk_Latn" | "<2tk>" => Ok(Language::Turkmen),
             "Tuvaluan" | "tvl" | "tvl-TV" => Ok(Language::Tuvaluan),
-            "Twi" | "twi" | "tw" | "twi_Latn" => Ok(Language::Twi),
-            "Uighur" | "uig" | "ug" | "uig_Arab" => Ok(Language::Uighur),
-            "Ukrainian" | "ukr" | "uk" | "UK" | "uk-UA" | "ukr_Cyrl" => Ok(Language::Ukrainian),
+            "Twi" | "twi" | "tw" | "twi_Latn" | "<2tw>" => Ok(Language::Twi),
+            "Uighur" | "uig" | "ug" | "uig_Arab" | "<2ug>" => Ok(Language::Uighur),
+            "Ukrainian" | "ukr" | "uk" | "UK" | "uk-UA" | "ukr_Cyrl" | "uk_UA" | "<2uk>" => {
+                Ok(Language::Ukrainian)
+            }
             "Uma" | "ppk" | "ppk-ID" => Ok(Language::Uma),
-            "Urdu" | "urd" | "ur" | "ur-PK" | "urd_Arab" => Ok(Language::Urdu),
-            "Uzbek" | "uzb" | "uz" | "uz-UZ" => Ok(Language::Uzbek),
-            "Venda" | "ven" | "ve" => Ok(Language::Venda),
-            "Vietnamese" | "vie" | "vi" | "vi-VN" | "vie_Latn" => Ok(Language::Vietnamese),
+            "Urdu" | "urd" | "ur" | "ur-PK" | "urd_Arab" | "ur_PK" | "<2ur>" => Ok(Language::Urdu),
+            "Uzbek" | "uzb" | "uz" | "uz-UZ" | "<2uz>" => Ok(Language::Uzbek),
+            "Venda" | "ven" | "ve" | "<2ve>" => Ok(Language::Venda),
+            "Vietnamese" | "vie" | "vi" | "vi-VN" | "vie_Latn" | "vi_VN" | "<2vi>" => {
+                Ok(Language::Vietnamese)
+            }
             "Vincentian Creole" | "svc" | "svc-VC" => Ok(Language::VincentianCreole),
             "Virgin Islands Creole" | "vic" | "vic-US" => Ok(Language::VirginIslandsCreole),
             "Wallisian" | "wls" | "wls-WF" => Ok(Language::Wallisian),
-            "Walloon" | "wln" | "wa" => Ok(Language::Walloon),
-            "Welsh" | "cym" | "cy" | "cy-GB" | "wel" | "cym_Latn" => Ok(Language::Welsh),
-            "Western Frisian" | "fry" | "fy" => Ok(Language::WesternFrisian),
+            "Walloon" | "wln" | "wa" | "<2wa>" => Ok(Language::Walloon),
+            "Welsh" | "cym" | "cy" | "cy-GB" | "wel" | "cym_Latn" | "<2cy>" => Ok(Language::Welsh),
+            "Western Frisian" | "fry" | "fy" | "<2fy>" => Ok(Language::WesternFrisian),
             "Wiarumus" | "tua" => Ok(Language::Wiarumus),
-            "Wolof" | "wol" | "wo" | "wo-SN" | "wol_Latn" => Ok(Language::Wolof),
-            "Xhosa" | "xho" | "xh" | "xh-ZA" | "xho_Latn" => Ok(Language::Xhosa),
-            "Yiddish" | "yid" | "yi" | "yi-YD" | "ydd_Hebr" => Ok(Language::Yiddish),
-            "Yoruba" | "yor" | "yo" | "yor_Latn" => Ok(Language::Yoruba),
+            "Wolof" | "wol" | "wo" | "wo-SN" | "wol_Latn" | "<2wo>" => Ok(Language::Wolof),
+            "Xhosa" | "xho" | "xh" | "xh-ZA" | "xho_Latn" | "xh_ZA" | "<2xh>" => {
+                Ok(Language::Xhosa)
+            }
+            "Yiddish" | "yid" | "yi" | "yi-YD" | "ydd_Hebr" | "<2yi>" => Ok(Language::Yiddish),
+            "Yoruba" | "yor" | "yo" | "yor_Latn" | "<2yo>" => Ok(Language::Yoruba),
             "Yucatec Maya" | "yua" => Ok(Language::YucatecMaya),
             "Zari" | "zaz" => Ok(Language::Zari),
-            "Zulu" | "zul" | "zu" | "zu-ZA" | "zul_Latn" => Ok(Language::Zulu),
+            "Zulu" | "zul" | "zu" | "zu-ZA" | "zul_Latn" | "<2zu>" => Ok(Language::Zulu),
             "Acehnese" | "ace" | "ace_Arab" => Ok(Language::Acehnese),
             "Arabic Mesopotamian" | "acm" | "acm_Arab" => Ok(Language::ArabicMesopotamian),
             "Arabic Ta izzi Adeni" | "acq" | "acq_Arab" => Ok(Language::ArabicTaIzziAdeni),
@@ -918,7 +1019,7 @@ impl FromStr for Language {
             "Jingpho" | "kac" | "kac_Latn" => Ok(Language::Jingpho),
             "Kamba" | "kam" | "kam_Latn" => Ok(Language::Kamba),
             "Kabiye" | "kbp" | "kbp_Latn" => Ok(Language::Kabiye),
-            "Kikuyu" | "kik" | "ki" | "kik_Latn" => Ok(Language::Kikuyu),
+            "Kikuyu" | "kik" | "ki" | "kik_Latn" | "<2ki>" => Ok(Language::Kikuyu),
             "Kimbundu" | "kmb" | "kmb_Latn" => Ok(Language::Kimbundu),
             "Ligurian" | "lij" | "lij_Latn" => Ok(Language::Ligurian),
             "Lombard" | "lmo" | "lmo_Latn" => Ok(Language::Lombard),
@@ -944,7 +1045,7 @@ impl FromStr for Language {
             "Central Atlas Tamazight" | "tzm" | "tzm_Tfng" => Ok(Language::CentralAtlasTamazight),
             "Umbundu" | "umb" | "umb_Latn" => Ok(Language::Umbundu),
             "Venetian" | "vec" | "vec_Latn" => Ok(Language::Venetian),
-            "Waray" | "war" | "war_Latn" => Ok(Language::Waray),
+            "Waray" | "war" | "war_Latn" | "<2war>" => Ok(Language::Waray),
             "Malaysian Malay" | "zsm" | "zsm_Latn" => Ok(Language::MalaysianMalay),
             _ => Err(Error::new_option("No result found")),
         }
@@ -3827,4 +3928,454 @@ impl Language {
             Self::Zulu,
         ]
     }
+
+    pub fn to_mbart50_str(self) -> Result<String, Error> {
+        match self {
+            Self::Afrikaans => Ok("af_ZA".to_string()),
+            Self::Arabic => Ok("ar_AR".to_string()),
+            Self::Azerbaijani => Ok("az_AZ".to_string()),
+            Self::Bengali => Ok("bn_IN".to_string()),
+            Self::Burmese => Ok("my_MM".to_string()),
+            Self::CentralKhmer => Ok("km_KH".to_string()),
+            Self::Chinese => Ok("zh_CN".to_string()),
+            Self::Croatian => Ok("hr_HR".to_string()),
+            Self::Czech => Ok("cs_CZ".to_string()),
+            Self::Dutch => Ok("nl_XX".to_string()),
+            Self::English => Ok("en_XX".to_string()),
+            Self::Estonian => Ok("et_EE".to_string()),
+            Self::Finnish => Ok("fi_FI".to_string()),
+            Self::French => Ok("fr_XX".to_string()),
+            Self::Galician => Ok("gl_ES".to_string()),
+            Self::Georgian => Ok("ka_GE".to_string()),
+            Self::German => Ok("de_DE".to_string()),
+            Self::Gujarati => Ok("gu_IN".to_string()),
+            Self::Hebrew => Ok("he_IL".to_string()),
+            Self::Hindi => Ok("hi_IN".to_string()),
+            Self::Indonesian => Ok("id_ID".to_string()),
+            Self::Italian => Ok("it_IT".to_string()),
+            Self::Japanese => Ok("ja_XX".to_string()),
+            Self::Kazakh => Ok("kk_KZ".to_string()),
+            Self::Korean => Ok("ko_KR".to_string()),
+            Self::Latvian => Ok("lv_LV".to_string()),
+            Self::Lithuanian => Ok("lt_LT".to_string()),
+            Self::Macedonian => Ok("mk_MK".to_string()),
+            Self::Malayalam => Ok("ml_IN".to_string()),
+            Self::Marathi => Ok("mr_IN".to_string()),
+            Self::Mongolian => Ok("mn_MN".to_string()),
+            Self::Nepali => Ok("ne_NP".to_string()),
+            Self::Persian => Ok("fa_IR".to_string()),
+            Self::Polish => Ok("pl_PL".to_string()),
+            Self::Portuguese => Ok("pt_XX".to_string()),
+            Self::Pushto => Ok("ps_AF".to_string()),
+            Self::Romanian => Ok("ro_RO".to_string()),
+            Self::Russian => Ok("ru_RU".to_string()),
+            Self::Sinhala => Ok("si_LK".to_string()),
+            Self::Slovenian => Ok("sl_SI".to_string()),
+            Self::Spanish => Ok("es_XX".to_string()),
+            Self::Swahili => Ok("sw_KE".to_string()),
+            Self::Swedish => Ok("sv_SE".to_string()),
+            Self::Tagalog => Ok("tl_XX".to_string()),
+            Self::Tamil => Ok("ta_IN".to_string()),
+            Self::Telugu => Ok("te_IN".to_string()),
+            Self::Thai => Ok("th_TH".to_string()),
+            Self::Turkish => Ok("tr_TR".to_string()),
+            Self::Ukrainian => Ok("uk_UA".to_string()),
+            Self::Urdu => Ok("ur_PK".to_string()),
+            Self::Vietnamese => Ok("vi_VN".to_string()),
+            Self::Xhosa => Ok("xh_ZA".to_string()),
+            _ => Err(Error::new_option("Translator doenst support this language")),
+        }
+    }
+
+    pub fn get_supported_mbart50() -> Vec<Self> {
+        vec![
+            Self::Afrikaans,
+            Self::Arabic,
+            Self::Azerbaijani,
+            Self::Bengali,
+            Self::Burmese,
+            Self::CentralKhmer,
+            Self::Chinese,
+            Self::Croatian,
+            Self::Czech,
+            Self::Dutch,
+            Self::English,
+            Self::Estonian,
+            Self::Finnish,
+            Self::French,
+            Self::Galician,
+            Self::Georgian,
+            Self::German,
+            Self::Gujarati,
+            Self::Hebrew,
+            Self::Hindi,
+            Self::Indonesian,
+            Self::Italian,
+            Self::Japanese,
+            Self::Kazakh,
+            Self::Korean,
+            Self::Latvian,
+            Self::Lithuanian,
+            Self::Macedonian,
+            Self::Malayalam,
+            Self::Marathi,
+            Self::Mongolian,
+            Self::Nepali,
+            Self::Persian,
+            Self::Polish,
+            Self::Portuguese,
+            Self::Pushto,
+            Self::Romanian,
+            Self::Russian,
+            Self::Sinhala,
+            Self::Slovenian,
+            Self::Spanish,
+            Self::Swahili,
+            Self::Swedish,
+            Self::Tagalog,
+            Self::Tamil,
+            Self::Telugu,
+            Self::Thai,
+            Self::Turkish,
+            Self::Ukrainian,
+            Self::Urdu,
+            Self::Vietnamese,
+            Self::Xhosa,
+        ]
+    }
+
+    pub fn to_madlad400_str(self) -> Result<String, Error> {
+        match self {
+            Self::Afrikaans => Ok("<2af>".to_string()),
+            Self::Akan => Ok("<2ak>".to_string()),
+            Self::Albanian => Ok("<2sq>".to_string()),
+            Self::Amharic => Ok("<2am>".to_string()),
+            Self::Arabic => Ok("<2ar>".to_string()),
+            Self::Armenian => Ok("<2hy>".to_string()),
+            Self::Assamese => Ok("<2as>".to_string()),
+            Self::Asturian => Ok("<2ast>".to_string()),
+            Self::Aymara => Ok("<2ay>".to_string()),
+            Self::Azerbaijani => Ok("<2az>".to_string()),
+            Self::Bambara => Ok("<2bm>".to_string()),
+            Self::Bashkir => Ok("<2ba>".to_string()),
+            Self::Basque => Ok("<2eu>".to_string()),
+            Self::Belarusian => Ok("<2be>".to_string()),
+            Self::Bengali => Ok("<2bn>".to_string()),
+            Self::Bosnian => Ok("<2bs>".to_string()),
+            Self::Breton => Ok("<2br>".to_string()),
+            Self::Bulgarian => Ok("<2bg>".to_string()),
+            Self::Burmese => Ok("<2my>".to_string()),
+            Self::Catalan => Ok("<2ca>".to_string()),
+            Self::Cebuano => Ok("<2ceb>".to_string()),
+            Self::CentralKhmer => Ok("<2km>".to_string()),
+            Self::Chamorro => Ok("<2ch>".to_string()),
+            Self::Chinese => Ok("<2zh>".to_string()),
+            Self::ChineseYue => Ok("<2yue>".to_string()),
+            Self::Chuvash => Ok("<2cv>".to_string()),
+            Self::Cornish => Ok("<2kw>".to_string()),
+            Self::Corsican => Ok("<2co>".to_string()),
+            Self::Croatian => Ok("<2hr>".to_string()),
+            Self::Czech => Ok("<2cs>".to_string()),
+            Self::Danish => Ok("<2da>".to_string()),
+            Self::Divehi => Ok("<2dv>".to_string()),
+            Self::Dutch => Ok("<2nl>".to_string()),
+            Self::Dzongkha => Ok("<2dz>".to_string()),
+            Self::English => Ok("<2en>".to_string()),
+            Self::Esperanto => Ok("<2eo>".to_string()),
+            Self::Estonian => Ok("<2et>".to_string()),
+            Self::Ewe => Ok("<2ee>".to_string()),
+            Self::Faroese => Ok("<2fo>".to_string()),
+            Self::Fijian => Ok("<2fj>".to_string()),
+            Self::Finnish => Ok("<2fi>".to_string()),
+            Self::French => Ok("<2fr>".to_string()),
+            Self::Fulah => Ok("<2ff>".to_string()),
+            Self::Gaelic => Ok("<2gd>".to_string()),
+            Self::Galician => Ok("<2gl>".to_string()),
+            Self::Ganda => Ok("<2lg>".to_string()),
+            Self::Georgian => Ok("<2ka>".to_string()),
+            Self::German => Ok("<2de>".to_string()),
+            Self::Greek => Ok("<2el>".to_string()),
+            Self::Guarani => Ok("<2gn>".to_string()),
+            Self::Gujarati => Ok("<2gu>".to_string()),
+            Self::Haitian => Ok("<2ht>".to_string()),
+            Self::Hausa => Ok("<2ha>".to_string()),
+            Self::Hawaiian => Ok("<2haw>".to_string()),
+            Self::Hebrew => Ok("<2he>".to_string()),
+            Self::Hiligaynon => Ok("<2hil>".to_string()),
+            Self::Hindi => Ok("<2hi>".to_string()),
+            Self::Hmong => Ok("<2hmn>".to_string()),
+            Self::Hungarian => Ok("<2hu>".to_string()),
+            Self::Icelandic => Ok("<2is>".to_string()),
+            Self::Ido => Ok("<2io>".to_string()),
+            Self::Igbo => Ok("<2ig>".to_string()),
+            Self::Ilocano => Ok("<2ilo>".to_string()),
+            Self::Indonesian => Ok("<2id>".to_string()),
+            Self::Interlingua => Ok("<2ia>".to_string()),
+            Self::Inuktitut => Ok("<2iu>".to_string()),
+            Self::Irish => Ok("<2ga>".to_string()),
+            Self::Italian => Ok("<2it>".to_string()),
+            Self::Japanese => Ok("<2ja>".to_string()),
+            Self::Javanese => Ok("<2jv>".to_string()),
+            Self::Kalaallisut => Ok("<2kl>".to_string()),
+            Self::Kannada => Ok("<2kn>".to_string()),
+            Self::Kashmiri => Ok("<2ks>".to_string()),
+            Self::Kazakh => Ok("<2kk>".to_string()),
+            Self::Kikuyu => Ok("<2ki>".to_string()),
+            Self::Kinyarwanda => Ok("<2rw>".to_string()),
+            Self::Kirghiz => Ok("<2ky>".to_string()),
+            Self::Kongo => Ok("<2kg>".to_string()),
+            Self::Korean => Ok("<2ko>".to_string()),
+            Self::Kurdish => Ok("<2ku>".to_string()),
+            Self::Lao => Ok("<2lo>".to_string()),
+            Self::Latin => Ok("<2la>".to_string()),
+            Self::Latvian => Ok("<2lv>".to_string()),
+            Self::Limburgan => Ok("<2li>".to_string()),
+            Self::Lingala => Ok("<2ln>".to_string()),
+            Self::Lithuanian => Ok("<2lt>".to_string()),
+            Self::Luxembourgish => Ok("<2lb>".to_string()),
+            Self::Macedonian => Ok("<2mk>".to_string()),
+            Self::Malagasy => Ok("<2mg>".to_string()),
+            Self::Malay => Ok("<2ms>".to_string()),
+            Self::Malayalam => Ok("<2ml>".to_string()),
+            Self::Maltese => Ok("<2mt>".to_string()),
+            Self::Manx => Ok("<2gv>".to_string()),
+            Self::Maori => Ok("<2mi>".to_string()),
+            Self::Marathi => Ok("<2mr>".to_string()),
+            Self::Mongolian => Ok("<2mn>".to_string()),
+            Self::Nepali => Ok("<2ne>".to_string()),
+            Self::NorthernSami => Ok("<2se>".to_string()),
+            Self::Norwegian => Ok("<2no>".to_string()),
+            Self::Nyanja => Ok("<2ny>".to_string()),
+            Self::Occitan => Ok("<2oc>".to_string()),
+            Self::Oriya => Ok("<2or>".to_string()),
+            Self::Oromo => Ok("<2om>".to_string()),
+            Self::Ossetian => Ok("<2os>".to_string()),
+            Self::Panjabi => Ok("<2pa>".to_string()),
+            Self::Persian => Ok("<2fa>".to_string()),
+            Self::Polish => Ok("<2pl>".to_string()),
+            Self::Portuguese => Ok("<2pt>".to_string()),
+            Self::Pushto => Ok("<2ps>".to_string()),
+            Self::Quechua => Ok("<2qu>".to_string()),
+            Self::Romanian => Ok("<2ro>".to_string()),
+            Self::Romansh => Ok("<2rm>".to_string()),
+            Self::Rundi => Ok("<2rn>".to_string()),
+            Self::Russian => Ok("<2ru>".to_string()),
+            Self::Samoan => Ok("<2sm>".to_string()),
+            Self::Sango => Ok("<2sg>".to_string()),
+            Self::Sanskrit => Ok("<2sa>".to_string()),
+            Self::Sardinian => Ok("<2sc>".to_string()),
+            Self::Serbian => Ok("<2sr>".to_string()),
+            Self::Shona => Ok("<2sn>".to_string()),
+            Self::Sindhi => Ok("<2sd>".to_string()),
+            Self::Sinhala => Ok("<2si>".to_string()),
+            Self::Slovak => Ok("<2sk>".to_string()),
+            Self::Slovenian => Ok("<2sl>".to_string()),
+            Self::Somali => Ok("<2so>".to_string()),
+            Self::Sorani => Ok("<2ckb>".to_string()),
+            Self::Sotho => Ok("<2st>".to_string()),
+            Self::Spanish => Ok("<2es>".to_string()),
+            Self::Sundanese => Ok("<2su>".to_string()),
+            Self::Swahili => Ok("<2sw>".to_string()),
+            Self::Swati => Ok("<2ss>".to_string()),
+            Self::Swedish => Ok("<2sv>".to_string()),
+            Self::Tagalog => Ok("<2fil>".to_string()),
+            Self::Tahitian => Ok("<2ty>".to_string()),
+            Self::Tajik => Ok("<2tg>".to_string()),
+            Self::Tamil => Ok("<2ta>".to_string()),
+            Self::Tatar => Ok("<2tt>".to_string()),
+            Self::Telugu => Ok("<2te>".to_string()),
+            Self::Thai => Ok("<2th>".to_string()),
+            Self::Tibetan => Ok("<2bo>".to_string()),
+            Self::Tigrinya => Ok("<2ti>".to_string()),
+            Self::Tonga => Ok("<2to>".to_string()),
+            Self::Tsonga => Ok("<2ts>".to_string()),
+            Self::Tswana => Ok("<2tn>".to_string()),
+            Self::Turkish => Ok("<2tr>".to_string()),
+            Self::Turkmen => Ok("<2tk>".to_string()),
+            Self::Twi => Ok("<2tw>".to_string()),
+            Self::Uighur => Ok("<2ug>".to_string()),
+            Self::Ukrainian => Ok("<2uk>".to_string()),
+            Self::Urdu => Ok("<2ur>".to_string()),
+            Self::Uzbek => Ok("<2uz>".to_string()),
+            Self::Venda => Ok("<2ve>".to_string()),
+            Self::Vietnamese => Ok("<2vi>".to_string()),
+            Self::Walloon => Ok("<2wa>".to_string()),
+            Self::Waray => Ok("<2war>".to_string()),
+            Self::Welsh => Ok("<2cy>".to_string()),
+            Self::WesternFrisian => Ok("<2fy>".to_string()),
+            Self::Wolof => Ok("<2wo>".to_string()),
+            Self::Xhosa => Ok("<2xh>".to_string()),
+            Self::Yiddish => Ok("<2yi>".to_string()),
+            Self::Yoruba => Ok("<2yo>".to_string()),
+            Self::Zulu => Ok("<2zu>".to_string()),
+            _ => Err(Error::new_option("Translator doenst support this language")),
+        }
+    }
+
+    pub fn get_supported_madlad400() -> Vec<Self> {
+        vec![
+            Self::Afrikaans,
+            Self::Akan,
+            Self::Albanian,
+            Self::Amharic,
+            Self::Arabic,
+            Self::Armenian,
+            Self::Assamese,
+            Self::Asturian,
+            Self::Aymara,
+            Self::Azerbaijani,
+            Self::Bambara,
+            Self::Bashkir,
+            Self::Basque,
+            Self::Belarusian,
+            Self::Bengali,
+            Self::Bosnian,
+            Self::Breton,
+            Self::Bulgarian,
+            Self::Burmese,
+            Self::Catalan,
+            Self::Cebuano,
+            Self::CentralKhmer,
+            Self::Chamorro,
+            Self::Chinese,
+            Self::ChineseYue,
+            Self::Chuvash,
+            Self::Cornish,
+            Self::Corsican,
+            Self::Croatian,
+            Self::Czech,
+            Self::Danish,
+            Self::Divehi,
+            Self::Dutch,
+            Self::Dzongkha,
+            Self::English,
+            Self::Esperanto,
+            Self::Estonian,
+            Self::Ewe,
+            Self::Faroese,
+            Self::Fijian,
+            Self::Finnish,
+            Self::French,
+            Self::Fulah,
+            Self::Gaelic,
+            Self::Galician,
+            Self::Ganda,
+            Self::Georgian,
+            Self::German,
+            Self::Greek,
+            Self::Guarani,
+            Self::Gujarati,
+            Self::Haitian,
+            Self::Hausa,
+            Self::Hawaiian,
+            Self::Hebrew,
+            Self::Hiligaynon,
+            Self::Hindi,
+            Self::Hmong,
+            Self::Hungarian,
+            Self::Icelandic,
+            Self::Ido,
+            Self::Igbo,
+            Self::Ilocano,
+            Self::Indonesian,
+            Self::Interlingua,
+            Self::Inuktitut,
+            Self::Irish,
+            Self::Italian,
+            Self::Japanese,
+            Self::Javanese,
+            Self::Kalaallisut,
+            Self::Kannada,
+            Self::Kashmiri,
+            Self::Kazakh,
+            Self::Kikuyu,
+            Self::Kinyarwanda,
+            Self::Kirghiz,
+            Self::Kongo,
+            Self::Korean,
+            Self::Kurdish,
+            Self::Lao,
+            Self::Latin,
+            Self::Latvian,
+            Self::Limburgan,
+            Self::Lingala,
+            Self::Lithuanian,
+            Self::Luxembourgish,
+            Self::Macedonian,
+            Self::Malagasy,
+            Self::Malay,
+            Self::Malayalam,
+            Self::Maltese,
+            Self::Manx,
+            Self::Maori,
+            Self::Marathi,
+            Self::Mongolian,
+            Self::Nepali,
+            Self::NorthernSami,
+            Self::Norwegian,
+            Self::Nyanja,
+            Self::Occitan,
+            Self::Oriya,
+            Self::Oromo,
+            Self::Ossetian,
+            Self::Panjabi,
+            Self::Persian,
+            Self::Polish,
+            Self::Portuguese,
+            Self::Pushto,
+            Self::Quechua,
+            Self::Romanian,
+            Self::Romansh,
+            Self::Rundi,
+            Self::Russian,
+            Self::Samoan,
+            Self::Sango,
+            Self::Sanskrit,
+            Self::Sardinian,
+            Self::Serbian,
+            Self::Shona,
+            Self::Sindhi,
+            Self::Sinhala,
+            Self::Slovak,
+            Self::Slovenian,
+            Self::Somali,
+            Self::Sorani,
+            Self::Sotho,
+            Self::Spanish,
+            Self::Sundanese,
+            Self::Swahili,
+            Self::Swati,
+            Self::Swedish,
+            Self::Tagalog,
+            Self::Tahitian,
+            Self::Tajik,
+            Self::Tamil,
+            Self::Tatar,
+            Self::Telugu,
+            Self::Thai,
+            Self::Tibetan,
+            Self::Tigrinya,
+            Self::Tonga,
+            Self::Tsonga,
+            Self::Tswana,
+            Self::Turkish,
+            Self::Turkmen,
+            Self::Twi,
+            Self::Uighur,
+            Self::Ukrainian,
+            Self::Urdu,
+            Self::Uzbek,
+            Self::Venda,
+            Self::Vietnamese,
+            Self::Walloon,
+            Self::Waray,
+            Self::Welsh,
+            Self::WesternFrisian,
+            Self::Wolof,
+            Self::Xhosa,
+            Self::Yiddish,
+            Self::Yoruba,
+            Self::Zulu,
+        ]
+    }
 }
@@ -1,5 +1,6 @@
 pub mod detector;
 pub mod error;
+pub mod formats;
 #[cfg(feature = "generate")]
 pub mod generator;
 pub mod languages;
@@ -14,15 +15,33 @@ mod tests {
 
     use dotenv::dotenv;
     #[cfg(feature = "ctranslate_req")]
-    use model_manager::model_manager::ModelManager;
+    use model_manager::model_manager::{ModelManager, ModelSource};
     use reqwest::blocking::Client;
+    #[cfg(feature = "ctranslate_req")]
+    use std::path::PathBuf;
 
     use crate::detector;
     use crate::detector::Detectors;
+    #[cfg(feature = "ctranslate_req")]
+    use crate::error::Error;
+    use crate::formats::manga::Page;
+    use crate::formats::markdown::{MarkdownDocument, MarkdownOptions};
+    use crate::formats::markup::{MarkupDocument, MarkupOptions};
+    use crate::formats::po::PoCatalog;
+    #[cfg(not(feature = "ctranslate_req"))]
+    use crate::formats::resources::ResourceOptions;
+    use crate::formats::resources::{Resource, ResourceFormat};
+    use crate::formats::subtitles::{SubtitleFormat, Subtitles};
+    #[cfg(not(feature = "ctranslate_req"))]
+    use crate::formats::xliff::XliffOptions;
+    use crate::formats::xliff::{XliffDocument, XliffVersion};
     use crate::generator::Records;
     use crate::languages::Language;
     #[cfg(feature = "ctranslate_req")]
-    use crate::model_register::register;
+    use crate::model_register::{
+        check_files, default_models, local_model, register, register_local_models, verify_file,
+        ModelManifest, MADLAD400_FILES,
+    };
     #[cfg(not(feature = "ctranslate_req"))]
     use crate::translators::chainer::TranslatorInfo;
     #[cfg(not(feature = "ctranslate_req"))]
@@ -30,21 +49,37 @@ mod tests {
     #[cfg(not(feature = "ctranslate_req"))]
     use crate::translators::context::Context;
     use crate::translators::dev::{get_csv_errors, get_languages};
+    use crate::translators::normalization::JapaneseNormalization;
+    #[cfg(feature = "argos")]
+    use crate::translators::offline::argos::ArgosPackage;
     #[cfg(feature = "ctranslate_req")]
-    use crate::translators::offline::ctranslate2::model_management::{
-        CTranslateModels, ModelLifetime, TokenizerModels,
-    };
+    use crate::translators::offline::ctranslate2::model_management::{ModelPool, PoolConfig};
+    #[cfg(feature = "ctranslate_req")]
+    use crate::translators::offline::ctranslate2::tokenizer::{SpecialTokens, Tokenizer};
     #[cfg(feature = "ctranslate_req")]
-    use crate::translators::offline::ctranslate2::Device;
+    use crate::translators::offline::ctranslate2::{
+        strip_prefix, ComputeType, Device, DeviceConfig,
+    };
     #[cfg(feature = "ctranslate_req")]
     use crate::translators::offline::m2m100::{M2M100ModelType, M2M100Translator};
+    #[cfg(feature = "madlad400")]
+    use crate::translators::offline::madlad400::Madlad400ModelType;
+    #[cfg(feature = "opus-mt")]
+    use crate::translators::offline::opus_mt::OpusMtModel;
     #[cfg(feature = "ctranslate_req")]
     use crate::translators::offline::ModelFormat;
+    #[cfg(not(feature = "ctranslate_req"))]
+    use crate::translators::options::TranslationOption;
+    use crate::translators::options::TranslationOptions;
+    use crate::translators::post_processing::{post_process, typography, PostProcessing};
     use crate::translators::scrape::papago::PapagoTranslator;
     use crate::translators::tokens::Tokens;
+    #[cfg(not(feature = "ctranslate_req"))]
+    use crate::translators::translator_structure::TranslationOutput;
     #[cfg(feature = "ctranslate_req")]
     use crate::translators::translator_structure::TranslatorCTranslate;
     use crate::translators::translator_structure::TranslatorLanguages;
+    use crate::translators::transliteration::transliterate;
     use crate::translators::Translator;
     #[cfg(not(feature = "ctranslate_req"))]
     use crate::translators::Translators;
@@ -56,23 +91,21 @@ mod tests {
         let mut mm = ModelManager::new().unwrap();
         register(&mut mm);
         let v = M2M100Translator::new(
-            &Device::CPU,
+            &Device::CPU.into(),
             &ModelFormat::Normal,
             &M2M100ModelType::Small418m,
             &mm,
         )
-        .await
         .unwrap();
-        let mut tk = TokenizerModels::new(ModelLifetime::KeepAlive);
-        let mut tt = CTranslateModels::new(ModelLifetime::KeepAlive);
+        let pool = ModelPool::default();
 
         let mess = v
             .translate_vec(
-                &mut tt,
-                &mut tk,
+                &pool,
                 &["こんにちは!".to_string()],
                 None,
                 &Language::English,
+                &TranslationOptions::default(),
             )
             .unwrap();
         println!("{:?}", mess);
@@ -89,6 +122,270 @@ mod tests {
         mm.clean_directory().unwrap();
     }
 
+    #[test]
+    #[cfg(feature = "ctranslate_req")]
+    fn model_manifest() {
+        let toml = r#"
+            [[models]]
+            id = "nllb-200-distilled-600M-ct2-int8"
+            directory = "translators/nllb-finetuned"
+            version = "1.0"
+            files = ["model.bin"]
+            source = { type = "huggingface", repo = "org/nllb-finetuned-ct2", commit = "3f2a9c1" }
+            sha256 = { "model.bin" = "ABC" }
+        "#;
+        let manifest = ModelManifest::from_toml(toml).unwrap();
+        let json = r#"{"models": [{"id": "nllb-200-distilled-600M-ct2-int8",
+            "directory": "translators/nllb-finetuned", "version": "1.0", "files": ["model.bin"],
+            "source": {"type": "huggingface", "repo": "org/nllb-finetuned-ct2", "commit": "3f2a9c1"},
+            "sha256": {"model.bin": "ABC"}}]}"#;
+        assert_eq!(ModelManifest::from_json(json).unwrap(), manifest);
+        assert_eq!(
+            manifest.checksums()["nllb-200-distilled-600M-ct2-int8"]["model.bin"],
+            "ABC"
+        );
+        assert!(ModelManifest::from_toml("[[models]]\nid = 1").is_err());
+
+        let defaults = default_models();
+        let models = manifest.merge(defaults.clone());
+        assert_eq!(models.len(), defaults.len());
+        let model = &models["nllb-200-distilled-600M-ct2-int8"];
+        assert_eq!(model.directory, PathBuf::from("translators/nllb-finetuned"));
+        match &model.source {
+            ModelSource::Huggingface(v) => {
+                assert_eq!(v.repo, "org/nllb-finetuned-ct2");
+                assert_eq!(v.commit.as_deref(), Some("3f2a9c1"));
+            }
+        }
+
+        let directory = std::env::temp_dir().join("translators-local-model");
+        std::fs::create_dir_all(&directory).unwrap();
+        let toml = format!(
+            "[[models]]\nid = \"sugoi-ja-en-ct2\"\nsource = {{ type = \"local\", path = {:?} }}",
+            directory
+        );
+        let manifest = ModelManifest::from_toml(&toml).unwrap();
+        assert_eq!(manifest.local_models()["sugoi-ja-en-ct2"], directory);
+        let models = manifest.clone().merge(defaults);
+        assert!(!models.contains_key("sugoi-ja-en-ct2"));
+        register_local_models(manifest.local_models()).unwrap();
+        assert_eq!(
+            local_model("sugoi-ja-en-ct2").unwrap(),
+            Some(directory.clone())
+        );
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let toml =
+            "[[models]]\nid = \"a\"\nsource = { type = \"url\", url = \"https://a.b/c.zip\" }";
+        match ModelManifest::from_toml(toml) {
+            Err(Error::Text(v)) => assert_eq!(v.message, "Unsupported source of model a"),
+            v => panic!("{:?}", v),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ctranslate_req")]
+    fn model_directory_files() {
+        let directory = std::env::temp_dir().join("translators-check-files");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("model.bin"), "").unwrap();
+        std::fs::write(directory.join("config.json"), "").unwrap();
+        match check_files(&directory, MADLAD400_FILES) {
+            Err(Error::Text(v)) => {
+                assert_eq!(
+                    v.error.as_deref(),
+                    Some("shared_vocabulary.txt, spiece.model")
+                )
+            }
+            v => panic!("{:?}", v),
+        }
+        assert!(check_files(&directory, &["model.bin", "config.json"]).is_ok());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "ctranslate_req")]
+    fn model_file_checksum() {
+        let path = std::env::temp_dir().join("translators-checksum.txt");
+        std::fs::write(&path, "hello").unwrap();
+        let sha256 = "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824";
+        assert!(verify_file("model", &path, sha256).is_ok());
+        match verify_file("model", &path, &"0".repeat(64)) {
+            Err(Error::Checksum(v)) => {
+                assert_eq!(v.model, "model");
+                assert_eq!(v.file, path);
+                assert_eq!(v.actual, sha256.to_lowercase());
+            }
+            v => panic!("{:?}", v),
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "opus-mt")]
+    fn opus_mt_models() {
+        let model = OpusMtModel::find(Language::English, Language::German).unwrap();
+        assert_eq!(model.name, "en-de");
+        assert!(!model.is_multi_target());
+        let model = OpusMtModel::find(Language::English, Language::Portuguese).unwrap();
+        assert_eq!(model.name, "en-ROMANCE");
+        assert!(model.is_multi_target());
+        assert!(OpusMtModel::find(Language::German, Language::Portuguese).is_err());
+        assert!(OpusMtModel::supported_pairs().contains(&(Language::Catalan, Language::English)));
+        let models = default_models();
+        for ident in [
+            "opus-mt-en-de-ct2",
+            "opus-mt-en-de-ct2-int8",
+            "opus-mt-ROMANCE-en-ct2-float16",
+        ] {
+            assert!(models.contains_key(ident), "{}", ident);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "mbart50", feature = "madlad400"))]
+    fn mbart50_madlad400_languages() {
+        assert_eq!(Language::English.to_mbart50_str().unwrap(), "en_XX");
+        assert_eq!(Language::Afrikaans.to_madlad400_str().unwrap(), "<2af>");
+        assert!(Language::get_supported_mbart50().contains(&Language::Dutch));
+        assert_eq!("Mt7B".parse(), Ok(Madlad400ModelType::Mt7B));
+        assert_eq!("".parse(), Ok(Madlad400ModelType::Mt3B));
+    }
+
+    #[test]
+    #[cfg(feature = "argos")]
+    fn argos_package() {
+        let directory = std::env::temp_dir().join("translators-argos-package");
+        std::fs::create_dir_all(directory.join("model")).unwrap();
+        for file in [
+            "sentencepiece.model",
+            "model/model.bin",
+            "model/config.json",
+        ] {
+            std::fs::write(directory.join(file), "").unwrap();
+        }
+        assert!(ArgosPackage::open(&directory).is_err());
+        std::fs::write(
+            directory.join("metadata.json"),
+            r#"{"package_version": "1.9", "from_code": "en", "to_code": "zt", "target_prefix": "__zt__"}"#,
+        )
+        .unwrap();
+        let package = ArgosPackage::open(&directory).unwrap();
+        assert_eq!(package.from, Language::English);
+        assert_eq!(package.to, Language::ChineseTaiwanese);
+        assert_eq!(package.metadata.target_prefix, "__zt__");
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "ctranslate_req")]
+    fn special_tokens() {
+        let directory = std::env::temp_dir().join("translators-special-tokens");
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("special_tokens_map.json");
+        std::fs::write(
+            &path,
+            r#"{"bos_token": "<s>", "eos_token": {"content": "</s>", "lstrip": false},
+            "unk_token": "<unk>", "additional_special_tokens": ["eng_Latn", {"content": "deu_Latn"}]}"#,
+        )
+        .unwrap();
+        let tokens = SpecialTokens::from_file(&path).unwrap();
+        assert_eq!(tokens.eos(), "</s>");
+        assert_eq!(tokens.additional, vec!["eng_Latn", "deu_Latn"]);
+        assert!(tokens.is_special("<s>") && tokens.is_special("deu_Latn"));
+        assert!(!tokens.is_special("<unk>"));
+        assert_eq!(SpecialTokens::default().eos(), "</s>");
+        std::fs::write(directory.join("tokenizer.json"), "{}").unwrap();
+        let expected = match cfg!(feature = "hf-tokenizer") {
+            true => "tokenizer.json",
+            false => "sentencepiece.bpe.model",
+        };
+        assert_eq!(
+            Tokenizer::find(&directory, "sentencepiece.bpe.model"),
+            directory.join(expected)
+        );
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "ctranslate_req")]
+    fn strip_target_prefix() {
+        let mut translations = vec![
+            vec!["deu_Latn".to_string(), "▁Hallo".to_string()],
+            vec!["▁Welt".to_string()],
+            vec![],
+        ];
+        strip_prefix(&mut translations, "deu_Latn");
+        assert_eq!(
+            translations,
+            vec![
+                vec!["▁Hallo".to_string()],
+                vec!["▁Welt".to_string()],
+                vec![]
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ctranslate_req")]
+    fn device_config() {
+        let config: DeviceConfig = "cuda".parse().unwrap();
+        assert_eq!(config.device, Some(Device::CUDA));
+        assert_eq!(config.compute_type, ComputeType::Auto);
+        assert_eq!(
+            "false".parse::<DeviceConfig>().unwrap().device,
+            Some(Device::CPU)
+        );
+        assert_eq!("auto".parse::<DeviceConfig>().unwrap().device, None);
+        assert!("tpu".parse::<DeviceConfig>().is_err());
+        assert_eq!("int8".parse(), Ok(ComputeType::Int8));
+        assert!("float16".parse::<ComputeType>().is_err());
+        let config = DeviceConfig::from(Device::CPU);
+        assert_eq!(
+            config
+                .clone()
+                .with_format(&ModelFormat::Compact)
+                .compute_type,
+            ComputeType::Int8
+        );
+        assert_eq!(
+            config
+                .clone()
+                .with_format(&ModelFormat::Normal)
+                .compute_type,
+            ComputeType::Default
+        );
+        let config = DeviceConfig {
+            compute_type: ComputeType::Default,
+            ..config
+        };
+        assert_eq!(
+            config
+                .clone()
+                .with_format(&ModelFormat::Compact)
+                .compute_type,
+            ComputeType::Default
+        );
+        assert_eq!(config.ident("m2m100"), "m2m100-cpu-Default");
+        let config = DeviceConfig {
+            translators: 2,
+            ..config
+        };
+        assert_eq!(config.ident("m2m100"), "m2m100-cpu-Default-x2");
+    }
+
+    #[test]
+    #[cfg(feature = "ctranslate_req")]
+    fn model_pool_failed_load() {
+        let pool = ModelPool::new(PoolConfig::single());
+        let path = std::env::temp_dir().join("translators-missing.model");
+        assert!(pool.get_tokenizer("missing", path.clone()).is_err());
+        // a failed load doesnt block the next one
+        assert!(pool.get_tokenizer("missing", path).is_err());
+        assert!(pool.loaded().unwrap().is_empty());
+        assert!(pool.unload_idle().is_ok());
+    }
+
     #[test]
     fn test_detector() {
         let text = "Hallo Welt";
@@ -117,6 +414,7 @@ mod tests {
             TranslatorInfo {
                 translator: Translator::Google,
                 to: Language::English,
+                post_processing: vec![],
             },
         );
         let v = Translators::new(
@@ -130,18 +428,377 @@ mod tests {
 
         let chatgpt_context = Context::ChatGPT("This is a text about ...".to_string());
         let translation = v
-                .translate("Dies ist ein kurzer test der dazu da ist um zu überprüfen ob der übersetzer funtioniert.".to_string(), None, &[chatgpt_context])
+                .translate("Dies ist ein kurzer test der dazu da ist um zu überprüfen ob der übersetzer funtioniert.".to_string(), None, &[chatgpt_context], &TranslationOptions::default())
                 .unwrap();
         let translations = v
             .translate_vec(
                 vec!["Hello world".to_string(), "This is a test".to_string()],
                 None,
                 &[],
+                &TranslationOptions::default(),
             )
             .unwrap();
         println!("{:?}, {:?}", translation, translations);
     }
 
+    #[test]
+    fn markup_roundtrip() {
+        let html = "<!DOCTYPE html><p class=a>Hello <b>world</b> &amp; <img alt='x'></p><pre>a &lt; b</pre><script>if (a < b) {}</script>";
+        let document = MarkupDocument::parse(html, &MarkupOptions::html());
+        assert_eq!(document.to_markup(), html);
+    }
+
+    #[test]
+    fn subtitles_roundtrip() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:02,000\r\n<i>Hello there,\r\nmy friend</i>\r\n\r\n";
+        let subtitles = Subtitles::parse(srt, SubtitleFormat::Srt).unwrap();
+        assert_eq!(subtitles.cues().len(), 1);
+        assert_eq!(subtitles.to_text(), srt);
+        let ass = "[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\nDialogue: 0,0:00:01.00,0:00:02.00,Default,Taro,0,0,0,,{\\an8}Hello,\\Nworld\n";
+        let subtitles = Subtitles::parse_detect(ass).unwrap();
+        assert_eq!(subtitles.cues()[0].speaker.as_deref(), Some("Taro"));
+        assert_eq!(subtitles.to_text(), ass);
+    }
+
+    #[test]
+    fn po_roundtrip() {
+        let po = "msgid \"\"\nmsgstr \"\"\n\"Plural-Forms: nplurals=2; plural=(n != 1);\\n\"\n\n#. Button label\n#, c-format\nmsgctxt \"menu\"\nmsgid \"Open %s\"\nmsgstr \"\"\n";
+        let catalog = PoCatalog::parse(po).unwrap();
+        assert_eq!(catalog.nplurals(), Some(2));
+        assert_eq!(catalog.entries()[1].msgctxt(), Some("menu"));
+        assert!(catalog.entries()[1].is_untranslated());
+        assert_eq!(catalog.to_text(), po);
+    }
+
+    #[test]
+    fn xliff_roundtrip() {
+        let xliff = "<xliff version=\"2.0\" srcLang=\"en\" trgLang=\"ja\"><file id=\"f1\"><unit id=\"1\"><segment><source>Click <pc id=\"1\">here</pc></source></segment></unit></file></xliff>";
+        let document = XliffDocument::parse(xliff).unwrap();
+        assert_eq!(document.version, XliffVersion::V2_0);
+        assert_eq!(document.target_language(), Some(Language::Japanese));
+        assert_eq!(document.to_text(), xliff);
+    }
+
+    #[test]
+    fn resource_entries() {
+        let json = "{\"app\": {\"title\": \"Hello {{name}}\", \"count\": 5}}";
+        let resource = Resource::parse(json, ResourceFormat::Json).unwrap();
+        assert_eq!(resource.entries(), vec![("app.title", "Hello {0}")]);
+        let yaml = "en:\n  # comment\n  greeting: Hello %{name}\n  flag: true\n";
+        let resource = Resource::parse(yaml, ResourceFormat::Yaml).unwrap();
+        assert_eq!(resource.entries(), vec![("en.greeting", "Hello {0}")]);
+        assert_eq!(resource.to_text(), yaml);
+        let xml = "<resources><string name=\"app\">@string/name</string><string name=\"hi\">Hi</string></resources>";
+        let resource = Resource::parse(xml, ResourceFormat::AndroidXml).unwrap();
+        assert_eq!(resource.entries(), vec![("hi", "Hi")]);
+    }
+
+    #[test]
+    fn markdown_segments() {
+        let markdown = "---\ntitle: Hello\nlayout: post\n---\n# Intro\n\nSee **this** [page](http://a.com).\n\n```\nlet x = 1;\n```\n\n| a | `b` |\n|---|---|\n";
+        let document = MarkdownDocument::parse(markdown, &MarkdownOptions::default());
+        assert_eq!(
+            document.segments(),
+            vec!["Hello", "Intro", "See {0}this{1} {2}page{3}.", "a"]
+        );
+        assert_eq!(document.to_text(), markdown);
+    }
+
+    #[test]
+    fn page_reading_order() {
+        let json = r#"{"regions": [
+            {"id": "a", "bbox": {"x": 10, "y": 10, "width": 50, "height": 100}, "source_text": "left"},
+            {"id": "b", "bbox": {"x": 200, "y": 20, "width": 50, "height": 100}, "source_text": "right"},
+            {"id": "c", "bbox": {"x": 100, "y": 300, "width": 50, "height": 100}, "vertical": true, "source_text": "bottom"}
+        ]}"#;
+        let page = Page::from_json(json).unwrap();
+        assert_eq!(page.reading_order(), vec![1, 0, 2]);
+        assert_eq!(Page::from_json(&page.to_json().unwrap()).unwrap(), page);
+    }
+
+    #[test]
+    fn japanese_normalization() {
+        let normalization = JapaneseNormalization::default();
+        assert_eq!(
+            normalization.normalize("「「漢字(かんじ)の\nテスト‥」」すごーーい！！"),
+            "「漢字のテスト…」すごーい！"
+        );
+        assert_eq!(normalization.normalize("Hello\nworld"), "Hello\nworld");
+        assert_eq!(normalization.normalize("えっ・・"), "えっ…");
+    }
+
+    #[test]
+    #[cfg(not(feature = "ctranslate_req"))]
+    fn normalize_only_japanese() {
+        let selector = TranslatorSelectorInfo::create_single(TranslatorInfo {
+            translator: Translator::Transliterate,
+            to: Language::English,
+            post_processing: vec![],
+        });
+        let mut v = Translators::new(
+            Some(Tokens::empty()),
+            selector,
+            None,
+            None,
+            Detectors::Whatlang,
+        )
+        .unwrap();
+        v.normalization = Some(JapaneseNormalization::default());
+        let options = TranslationOptions::default();
+        let translation = v
+            .translate("Wait!! What?!".to_string(), None, &[], &options)
+            .unwrap();
+        assert_eq!(translation[0].text, "Wait!! What?!");
+        let translation = v
+            .translate("ねこ！！".to_string(), None, &[], &options)
+            .unwrap();
+        assert!(!translation[0].text.contains("！！"));
+    }
+
+    #[test]
+    fn target_typography() {
+        assert_eq!(
+            typography("Il a dit \"oui\"... Vraiment ?", Language::French),
+            "Il a dit «\u{a0}oui\u{a0}»… Vraiment\u{202f}?"
+        );
+        assert_eq!(
+            typography("Hello。How are you？", Language::English),
+            "Hello. How are you?"
+        );
+        assert_eq!(
+            typography("本当に!(テスト)", Language::Japanese),
+            "本当に！（テスト）"
+        );
+        assert_eq!(typography("えっ・・", Language::Japanese), "えっ・・");
+        assert_eq!(typography("えっ・・・", Language::Japanese), "えっ…");
+        assert_eq!(
+            post_process(
+                &[PostProcessing::Typography],
+                "<b class=\"a\">\"oui\"</b> ?".to_string(),
+                Language::French,
+                true
+            ),
+            "<b class=\"a\">«\u{a0}oui\u{a0}»</b>\u{202f}?"
+        );
+    }
+
+    #[test]
+    fn transliteration() {
+        assert_eq!(
+            transliterate("コーヒーをいっぱい、ちょうだい。", Language::Japanese),
+            "kōhīoippai, chōdai."
+        );
+        assert_eq!(
+            transliterate("とうきょうのくうき", Language::Japanese),
+            "tōkyōnokūki"
+        );
+        assert_eq!(
+            transliterate("한국어 선생님", Language::Korean),
+            "hangugeo seonsaengnim"
+        );
+        assert_eq!(
+            transliterate("你好，世界", Language::Chinese),
+            "nǐ hǎo, shì jiè"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "ctranslate_req"))]
+    fn translation_metadata() {
+        let selector = TranslatorSelectorInfo::List(vec![TranslatorInfo {
+            translator: Translator::Transliterate,
+            to: Language::English,
+            post_processing: vec![],
+        }]);
+        let v = Translators::new(
+            Some(Tokens::empty()),
+            selector,
+            None,
+            None,
+            Detectors::Whatlang,
+        )
+        .unwrap();
+        let translations = v
+            .translate(
+                "こんにちは".to_string(),
+                Some(Language::Japanese),
+                &[],
+                &TranslationOptions::default(),
+            )
+            .unwrap();
+        let output = &translations[1];
+        assert_eq!(output.text, "konnichiha");
+        assert_eq!(output.lang, Language::English);
+        assert_eq!(output.metadata.translator.as_deref(), Some("Transliterate"));
+        assert_eq!(output.metadata.detected_lang, Some(Language::Japanese));
+        assert_eq!((output.metadata.attempts, output.metadata.chars), (1, 5));
+        let json = serde_json::to_string(output).unwrap();
+        let parsed: TranslationOutput = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.metadata, output.metadata);
+
+        // the confidence of the detector is kept for the translation
+        let selector = TranslatorSelectorInfo::Selective(
+            HashMap::new(),
+            TranslatorInfo {
+                translator: Translator::Transliterate,
+                to: Language::English,
+                post_processing: vec![],
+            },
+        );
+        let v = Translators::new(
+            Some(Tokens::empty()),
+            selector,
+            None,
+            None,
+            Detectors::Whatlang,
+        )
+        .unwrap();
+        let translations = v
+            .translate(
+                "こんにちは、お元気ですか".to_string(),
+                None,
+                &[],
+                &TranslationOptions::default(),
+            )
+            .unwrap();
+        assert!(translations[0].metadata.confidence.is_some());
+        assert_eq!(
+            translations[1].metadata.confidence,
+            translations[0].metadata.confidence
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "ctranslate_req"))]
+    fn ignored_options() {
+        let selector = TranslatorSelectorInfo::create_single(TranslatorInfo {
+            translator: Translator::Transliterate,
+            to: Language::English,
+            post_processing: vec![],
+        });
+        let v = Translators::new(
+            Some(Tokens::empty()),
+            selector,
+            None,
+            None,
+            Detectors::Whatlang,
+        )
+        .unwrap();
+        let options = TranslationOptions {
+            beam_size: Some(4),
+            max_decoding_length: Some(256),
+            ..Default::default()
+        };
+        assert_eq!(
+            v.get_ignored_options(&options),
+            vec![(
+                Translator::Transliterate,
+                vec![
+                    TranslationOption::BeamSize,
+                    TranslationOption::MaxDecodingLength
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "ctranslate_req"))]
+    fn translate_markup() {
+        let selector = TranslatorSelectorInfo::create_single(TranslatorInfo {
+            translator: Translator::Transliterate,
+            to: Language::English,
+            post_processing: vec![],
+        });
+        let v = Translators::new(
+            Some(Tokens::empty()),
+            selector,
+            None,
+            None,
+            Detectors::Whatlang,
+        )
+        .unwrap();
+        let html = "<p class=\"a\">ねこ <b>いぬ</b> <img alt='とり' src=\"a.png\"></p>";
+        let translation = v
+            .translate_markup(
+                html,
+                &MarkupOptions::html(),
+                Some(Language::Japanese),
+                &[],
+                &TranslationOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(
+            translation,
+            "<p class=\"a\">neko <b>inu</b> <img alt='tori' src=\"a.png\"></p>"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "ctranslate_req"))]
+    fn translate_xliff() {
+        // the translator targets german, the file asks for english
+        let selector = TranslatorSelectorInfo::create_single(TranslatorInfo {
+            translator: Translator::Transliterate,
+            to: Language::German,
+            post_processing: vec![],
+        });
+        let v = Translators::new(
+            Some(Tokens::empty()),
+            selector,
+            None,
+            None,
+            Detectors::Whatlang,
+        )
+        .unwrap();
+        let xliff = "<xliff version=\"2.0\" srcLang=\"ja\" trgLang=\"en\"><file id=\"f1\"><unit id=\"1\"><segment><source>ねこ</source></segment></unit></file></xliff>";
+        let translation = v
+            .translate_xliff(
+                xliff,
+                &XliffOptions::default(),
+                None,
+                &[],
+                &TranslationOptions::default(),
+            )
+            .unwrap();
+        assert!(translation.contains("<target>neko</target>"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "ctranslate_req"))]
+    fn translate_resource_incremental() {
+        let selector = TranslatorSelectorInfo::create_single(TranslatorInfo {
+            translator: Translator::Transliterate,
+            to: Language::English,
+            post_processing: vec![],
+        });
+        let v = Translators::new(
+            Some(Tokens::empty()),
+            selector,
+            None,
+            None,
+            Detectors::Whatlang,
+        )
+        .unwrap();
+        // the rails locale roots differ, the keys still match
+        let source = "en:\n  a: ねこ\n  b: いぬ\n";
+        let target = "de:\n  a: Katze\n  c: Alt\n";
+        let output = v
+            .translate_resource_incremental(
+                source,
+                None,
+                Some(target),
+                ResourceFormat::Yaml,
+                &ResourceOptions::default(),
+                None,
+                &[],
+                &TranslationOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(output.added, vec!["en.b"]);
+        assert_eq!(output.removed, vec!["de.c"]);
+        assert_eq!(output.text, "de:\n  a: Katze\n  b: inu\n");
+    }
+
     #[tokio::test]
     async fn generate_file() {
         let v = Records::new().unwrap();
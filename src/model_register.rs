@@ -1,61 +1,372 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
 
 use model_manager::model_manager::{HuggingfaceModel, Model, ModelManager, ModelSource};
+use serde::{Deserialize, Serialize};
 
-#[allow(dead_code)]
+use crate::error::{ChecksumError, Error};
+#[cfg(feature = "opus-mt")]
+use crate::translators::offline::opus_mt::OPUS_MT_MODELS;
+
+/// Registers the built-in models
 pub fn register(mm: &mut ModelManager) {
+    mm.register_models(default_models());
+}
+
+/// Registers the built-in models and the models of the manifest, manifest entries replace built-in models with the same id.
+/// The sha256 of the manifest entries are added to the checksum registry and local models to the local registry.
+pub fn register_manifest(mm: &mut ModelManager, path: &Path) -> Result<(), Error> {
+    let manifest = ModelManifest::from_file(path)?;
+    register_checksums(manifest.checksums())?;
+    register_local_models(manifest.local_models())?;
+    mm.register_models(manifest.merge(default_models()));
+    Ok(())
+}
+
+/// Expected sha256 of the model files by model id and file
+pub type Checksums = HashMap<String, HashMap<String, String>>;
+
+/// Directories of the local models by model id
+pub type LocalModels = HashMap<String, PathBuf>;
+
+#[derive(Default)]
+struct Registry {
+    checksums: Checksums,
+    /// Models whose files were verified since they were registered
+    verified: HashSet<String>,
+    /// Models that are loaded from a directory instead of the model manager
+    local: LocalModels,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+fn lock_registry() -> Result<std::sync::MutexGuard<'static, Registry>, Error> {
+    registry()
+        .lock()
+        .map_err(|_| Error::new_option("Model registry poisoned"))
+}
+
+/// Adds the sha256 of model files, the checksums of a model replace the ones registered before
+pub fn register_checksums(checksums: Checksums) -> Result<(), Error> {
+    let mut registry = lock_registry()?;
+    for (id, files) in checksums {
+        registry.verified.remove(&id);
+        registry.checksums.insert(id, files);
+    }
+    Ok(())
+}
+
+/// Checksums of all registered models
+pub fn registered_checksums() -> Result<Checksums, Error> {
+    Ok(lock_registry()?.checksums.clone())
+}
+
+/// Adds models that are used from a directory, they replace the models of the model manager with the same id
+pub fn register_local_models(models: LocalModels) -> Result<(), Error> {
+    let mut registry = lock_registry()?;
+    for (id, path) in models {
+        registry.verified.remove(&id);
+        registry.local.insert(id, path);
+    }
+    Ok(())
+}
+
+/// Directory of a registered local model
+pub fn local_model(id: &str) -> Result<Option<PathBuf>, Error> {
+    Ok(lock_registry()?.local.get(id).cloned())
+}
+
+/// Returns the directory of the model. Local models are used from their directory,
+/// otherwise the model manager downloads the model if it is missing.
+/// The files with a registered checksum are verified the first time the model is used.
+pub fn get_model(mm: &ModelManager, id: &str) -> Result<PathBuf, Error> {
+    let directory = match local_model(id)? {
+        Some(v) => v,
+        None => {
+            let (base, model) = mm
+                .get_model(id)
+                .map_err(|e| Error::new(format!("Couldnt get model {}", id), e))?;
+            base.join(&model.directory)
+        }
+    };
+    verify_model(id, &directory)?;
+    Ok(directory)
+}
+
+/// Downloads all registered models and verifies the files that have a checksum
+pub async fn download_models(mm: &ModelManager, threads: usize) -> Result<(), Error> {
+    mm.download_all(threads)
+        .await
+        .map_err(|e| Error::new("Couldnt download models", e))?;
+    verify_models(mm)
+}
+
+/// Verifies the files of the registered models, fails with Error::Checksum on the first corrupt file
+pub fn verify_models(mm: &ModelManager) -> Result<(), Error> {
+    for id in registered_checksums()?.keys() {
+        get_model(mm, id)?;
+    }
+    Ok(())
+}
+
+/// Verifies the registered files of the model in the directory
+fn verify_model(id: &str, directory: &Path) -> Result<(), Error> {
+    let files = {
+        let registry = lock_registry()?;
+        match registry.verified.contains(id) {
+            true => return Ok(()),
+            false => registry.checksums.get(id).cloned().unwrap_or_default(),
+        }
+    };
+    for (file, expected) in &files {
+        verify_file(id, &directory.join(file), expected)?;
+    }
+    lock_registry()?.verified.insert(id.to_string());
+    Ok(())
+}
+
+/// Compares the sha256 of the file, the case of the expected hash is ignored
+pub fn verify_file(model: &str, path: &Path, expected: &str) -> Result<(), Error> {
+    let actual = sha256::try_digest(path)
+        .map_err(|e| Error::new(format!("Couldnt read {}", path.display()), e))?;
+    match actual.eq_ignore_ascii_case(expected) {
+        true => Ok(()),
+        false => Err(Error::Checksum(ChecksumError {
+            model: model.to_string(),
+            file: path.to_path_buf(),
+            expected: expected.to_lowercase(),
+            actual,
+        })),
+    }
+}
+
+/// List of models, read from a toml or json file
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelManifest {
+    #[serde(default)]
+    pub models: Vec<ManifestModel>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ManifestModel {
+    /// Ident the translators use to get the model
+    pub id: String,
+    /// Directory relative to the model manager directory, local models use the path of the source
+    #[serde(default)]
+    pub directory: PathBuf,
+    #[serde(default)]
+    pub version: String,
+    pub source: ManifestSource,
+    /// Files of the model, relative to the directory
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Sha256 of the files, files without a checksum aren't verified
+    #[serde(default)]
+    pub sha256: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ManifestSource {
+    /// Huggingface repo, the commit pins the revision
+    Huggingface {
+        repo: String,
+        commit: Option<String>,
+    },
+    /// Archive or file url, the model manager can't download them yet, so the manifest is rejected
+    Url { url: String },
+    /// Directory with the model files, nothing is downloaded
+    Local { path: PathBuf },
+}
+
+impl ModelManifest {
+    /// Reads a json manifest if the file ends with .json, otherwise a toml manifest
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::new(format!("Couldnt read manifest {}", path.display()), e))?;
+        match path.extension().and_then(|v| v.to_str()) {
+            Some("json") => Self::from_json(&text),
+            _ => Self::from_toml(&text),
+        }
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, Error> {
+        let manifest: Self =
+            toml::from_str(text).map_err(|e| Error::new("Couldnt parse toml manifest", e))?;
+        manifest.check_sources()
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, Error> {
+        let manifest: Self =
+            serde_json::from_str(text).map_err(|e| Error::new("Couldnt parse json manifest", e))?;
+        manifest.check_sources()
+    }
+
+    /// Fails on the first model with a source that can't be used
+    fn check_sources(self) -> Result<Self, Error> {
+        match self.models.iter().find_map(|v| match &v.source {
+            ManifestSource::Url { url } => Some((&v.id, url)),
+            _ => None,
+        }) {
+            Some((id, url)) => Err(Error::new(
+                format!("Unsupported source of model {}", id),
+                format!("url sources like {} can't be downloaded yet", url),
+            )),
+            None => Ok(self),
+        }
+    }
+
+    /// Checksums of the models that have any
+    pub fn checksums(&self) -> Checksums {
+        self.models
+            .iter()
+            .filter(|v| !v.sha256.is_empty())
+            .map(|v| (v.id.clone(), v.sha256.clone()))
+            .collect()
+    }
+
+    /// Directories of the models with a local source
+    pub fn local_models(&self) -> LocalModels {
+        self.models
+            .iter()
+            .filter_map(|v| match &v.source {
+                ManifestSource::Local { path } => Some((v.id.clone(), path.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Adds the models to the given models, models with the same id are replaced.
+    /// Local models are removed, they are used from their directory.
+    pub fn merge(self, mut models: HashMap<String, Model>) -> HashMap<String, Model> {
+        for model in self.models {
+            match model.into_model() {
+                (id, Some(model)) => models.insert(id, model),
+                (id, None) => models.remove(&id),
+            };
+        }
+        models
+    }
+}
+
+impl ManifestModel {
+    /// Model of the model manager, None for local models
+    fn into_model(self) -> (String, Option<Model>) {
+        let source = match self.source {
+            ManifestSource::Huggingface { repo, commit } => {
+                ModelSource::Huggingface(HuggingfaceModel {
+                    repo,
+                    files: self.files,
+                    commit,
+                })
+            }
+            ManifestSource::Url { .. } | ManifestSource::Local { .. } => return (self.id, None),
+        };
+        (
+            self.id,
+            Some(Model {
+                directory: self.directory,
+                version: self.version,
+                source,
+            }),
+        )
+    }
+}
+
+/// Files of the jparacrawl models, both directions are in subdirectories
+pub const JPARACRAWL_FILES: &[&str] = &[
+    "spm.ja.nopretok.vocab",
+    "spm.ja.nopretok.model",
+    "spm.en.nopretok.vocab",
+    "spm.en.nopretok.model",
+    "ja-en/config.json",
+    "ja-en/model.bin",
+    "ja-en/source_vocabulary.txt",
+    "ja-en/target_vocabulary.txt",
+    "en-ja/config.json",
+    "en-ja/model.bin",
+    "en-ja/source_vocabulary.txt",
+    "en-ja/target_vocabulary.txt",
+];
+
+pub const SUGOI_FILES: &[&str] = &[
+    "source_vocabulary.txt",
+    "target_vocabulary.txt",
+    "spm.ja.nopretok.vocab",
+    "spm.ja.nopretok.model",
+    "model.bin",
+    "config.json",
+];
+
+pub const M2M100_FILES: &[&str] = &[
+    "spm.128k.model",
+    "shared_vocabulary.txt",
+    "model.bin",
+    "config.json",
+];
+
+pub const NLLB_FILES: &[&str] = &[
+    "model.bin",
+    "config.json",
+    "tokenizer_config.json",
+    "tokenizer.json",
+    "special_tokens_map.json",
+    "shared_vocabulary.txt",
+    "sentencepiece.bpe.model",
+];
+
+/// Files of the converted opus-mt models, source and target have their own sentencepiece model
+pub const OPUS_MT_FILES: &[&str] = &[
+    "model.bin",
+    "config.json",
+    "shared_vocabulary.txt",
+    "source.spm",
+    "target.spm",
+];
+
+pub const MBART50_FILES: &[&str] = &[
+    "model.bin",
+    "config.json",
+    "shared_vocabulary.txt",
+    "sentencepiece.bpe.model",
+];
+
+pub const MADLAD400_FILES: &[&str] = &[
+    "model.bin",
+    "config.json",
+    "shared_vocabulary.txt",
+    "spiece.model",
+];
+
+/// Checks that the directory contains all files, the error lists the missing files
+pub fn check_files(directory: &Path, files: &[&str]) -> Result<(), Error> {
+    let missing = files
+        .iter()
+        .filter(|v| !directory.join(v).is_file())
+        .copied()
+        .collect::<Vec<_>>();
+    match missing.is_empty() {
+        true => Ok(()),
+        false => Err(Error::new(
+            format!("Missing model files in {}", directory.display()),
+            missing.join(", "),
+        )),
+    }
+}
+
+/// The built-in models
+pub fn default_models() -> HashMap<String, Model> {
     let mut models = HashMap::new();
-    let jpara = [
-        "spm.ja.nopretok.vocab",
-        "spm.ja.nopretok.model",
-        "spm.en.nopretok.vocab",
-        "spm.en.nopretok.model",
-        "ja-en/config.json",
-        "ja-en/model.bin",
-        "ja-en/source_vocabulary.txt",
-        "ja-en/target_vocabulary.txt",
-        "en-ja/config.json",
-        "en-ja/model.bin",
-        "en-ja/source_vocabulary.txt",
-        "en-ja/target_vocabulary.txt",
-    ]
-    .iter()
-    .map(|x| x.to_string())
-    .collect::<Vec<_>>();
-    let sugoi = [
-        "source_vocabulary.txt",
-        "target_vocabulary.txt",
-        "spm.ja.nopretok.vocab",
-        "spm.ja.nopretok.model",
-        "model.bin",
-        "config.json",
-    ]
-    .iter()
-    .map(|x| x.to_string())
-    .collect::<Vec<_>>();
-    let m2m100 = [
-        "spm.128k.model",
-        "shared_vocabulary.txt",
-        "model.bin",
-        "config.json",
-    ]
-    .iter()
-    .map(|x| x.to_string())
-    .collect::<Vec<_>>();
-    let nllb = [
-        "model.bin",
-        "config.json",
-        "tokenizer_config.json",
-        "tokenizer.json",
-        "special_tokens_map.json",
-        "shared_vocabulary.txt",
-        "sentencepiece.bpe.model",
-    ]
-    .iter()
-    .map(|x| x.to_string())
-    .collect::<Vec<_>>();
+    let files = |v: &[&str]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let jpara = files(JPARACRAWL_FILES);
+    let sugoi = files(SUGOI_FILES);
+    let m2m100 = files(M2M100_FILES);
+    let nllb = files(NLLB_FILES);
     models.insert(
         "jparacrawl-small-ct2".to_string(),
         Model {
@@ -416,6 +727,27 @@ pub fn register(mm: &mut ModelManager) {
             }),
         },
     );
+    #[cfg(feature = "opus-mt")]
+    for model in OPUS_MT_MODELS {
+        for extra in ["", "-float16", "-int8"] {
+            let ident = format!("opus-mt-{}-ct2{}", model.name, extra);
+            models.insert(ident.clone(), huggingface_model(&ident, OPUS_MT_FILES));
+        }
+    }
+
+    models
+}
 
-    mm.register_models(models);
+/// Model in translators/{ident}, downloaded from the JustFrederik/{ident} repo
+#[cfg(feature = "opus-mt")]
+fn huggingface_model(ident: &str, files: &[&str]) -> Model {
+    Model {
+        directory: PathBuf::from("translators").join(ident),
+        version: "1.0".to_string(),
+        source: ModelSource::Huggingface(HuggingfaceModel {
+            repo: format!("JustFrederik/{}", ident),
+            files: files.iter().map(|x| x.to_string()).collect(),
+            commit: None,
+        }),
+    }
 }
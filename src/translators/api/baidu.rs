@@ -2,6 +2,7 @@
 
 use crate::error::Error;
 use crate::languages::Language;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorNoContext,
 };
@@ -24,6 +25,7 @@ impl TranslatorNoContext for BaiduApiTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         let form = Form::new(
             &self.app_id,
@@ -54,6 +56,7 @@ impl TranslatorNoContext for BaiduApiTranslator {
                 .collect::<Vec<_>>()
                 .join("\n"),
             lang: Language::from_str(&resp.from).unwrap_or(Language::Unknown),
+            metadata: Default::default(),
         })
     }
 
@@ -63,11 +66,13 @@ impl TranslatorNoContext for BaiduApiTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
-        let v = self.translate(client, &query.join("\n"), from, to)?;
+        let v = self.translate(client, &query.join("\n"), from, to, options)?;
         Ok(TranslationVecOutput {
             text: v.text.split('\n').map(|v| v.to_string()).collect(),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
 }
@@ -13,7 +13,8 @@ use reqwest::{blocking::Client, Url};
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::chatbot;
-use crate::translators::context::{get_gpt_context, Context};
+use crate::translators::context::Context;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorContext,
 };
@@ -61,11 +62,13 @@ impl TranslatorContext for ChatGPTTranslator {
         from: Option<Language>,
         to: &Language,
         context: &[Context],
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
-        let v = self.translate_vec(client, &[query.to_string()], from, to, context)?;
+        let v = self.translate_vec(client, &[query.to_string()], from, to, context, options)?;
         Ok(TranslationOutput {
             text: v.text.join("\n"),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
 
@@ -76,6 +79,7 @@ impl TranslatorContext for ChatGPTTranslator {
         _: Option<Language>,
         to: &Language,
         context: &[Context],
+        _: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let time = {
             self.last_request
@@ -87,8 +91,7 @@ impl TranslatorContext for ChatGPTTranslator {
         if wait > 0 {
             std::thread::sleep(Duration::from_secs(wait as u64));
         }
-        let con = get_gpt_context(context);
-        let q_s = chatbot::generate_query(query, &to.to_name_str()?, con)?;
+        let q_s = chatbot::generate_query(query, &to.to_name_str()?, context)?;
 
         let response: CompletionResponse = block_on(async {
             self.client
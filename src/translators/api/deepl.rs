@@ -4,10 +4,14 @@ use std::str::FromStr;
 
 use crate::error::Error;
 use crate::languages::Language;
+use crate::translators::context::{
+    get_formality, get_gpt_context, get_history, Context, Formality,
+};
 use crate::translators::helpers::option_error;
+use crate::translators::options::{TextFormat, TranslationOption, TranslationOptions};
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
-    TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
+    TranslationOutput, TranslationVecOutput, TranslatorContext, TranslatorLanguages,
 };
 
 pub struct DeeplTranslator {
@@ -52,19 +56,23 @@ impl TranslatorLanguages for DeeplTranslator {
     }
 }
 
-impl TranslatorNoContext for DeeplTranslator {
+impl TranslatorContext for DeeplTranslator {
     fn translate(
         &self,
         client: &Client,
         query: &str,
         from: Option<Language>,
         to: &Language,
+        context: &[Context],
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         let response = self.request(
             client,
             query,
             option_error(from.map(|v| v.to_deepl_str()))?,
             &to.to_deepl_str()?,
+            context,
+            options,
         )?;
         let mut output = String::new();
         let mut language = String::new();
@@ -75,6 +83,7 @@ impl TranslatorNoContext for DeeplTranslator {
         Ok(TranslationOutput {
             text: output,
             lang: Language::from_str(&language)?,
+            metadata: Default::default(),
         })
     }
 
@@ -84,6 +93,8 @@ impl TranslatorNoContext for DeeplTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        context: &[Context],
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let query = query.join("\n");
         let response = self.request(
@@ -91,6 +102,8 @@ impl TranslatorNoContext for DeeplTranslator {
             &query,
             option_error(from.map(|v| v.to_deepl_str()))?,
             &to.to_deepl_str()?,
+            context,
+            options,
         )?;
         let mut output: Vec<String> = Vec::new();
         let mut language = String::new();
@@ -105,8 +118,18 @@ impl TranslatorNoContext for DeeplTranslator {
                 .map(|v| v.to_string())
                 .collect(),
             lang: Language::from_str(&language)?,
+            metadata: Default::default(),
         })
     }
+
+    fn supported_options(&self) -> &'static [TranslationOption] {
+        &[
+            TranslationOption::Formality,
+            TranslationOption::SplitSentences,
+            TranslationOption::PreserveFormatting,
+            TranslationOption::Format,
+        ]
+    }
 }
 
 impl DeeplTranslator {
@@ -125,8 +148,10 @@ impl DeeplTranslator {
         query: &str,
         from: Option<String>,
         target: &str,
+        context: &[Context],
+        options: &TranslationOptions,
     ) -> Result<TranslationResponse, Error> {
-        let form = match from {
+        let mut form = match from {
             Some(f) => vec![
                 ("text", query.to_string()),
                 ("target_lang", target.to_string()),
@@ -137,6 +162,35 @@ impl DeeplTranslator {
                 ("target_lang", target.to_string()),
             ],
         };
+        if let Some(formality) = options.formality.or_else(|| get_formality(context)) {
+            if formality != Formality::Default {
+                form.push(("formality", formality.to_deepl_str().to_string()));
+            }
+        }
+        if let Some(split_sentences) = options.split_sentences {
+            form.push((
+                "split_sentences",
+                split_sentences.to_deepl_str().to_string(),
+            ));
+        }
+        if let Some(preserve_formatting) = options.preserve_formatting {
+            form.push((
+                "preserve_formatting",
+                match preserve_formatting {
+                    true => "1",
+                    false => "0",
+                }
+                .to_string(),
+            ));
+        }
+        match options.format {
+            Some(TextFormat::Html) => form.push(("tag_handling", "html".to_string())),
+            Some(TextFormat::Xml) => form.push(("tag_handling", "xml".to_string())),
+            Some(TextFormat::Text) | None => {}
+        }
+        if let Some(context) = Self::generate_context(context) {
+            form.push(("context", context));
+        }
         let request = client
             .post(&self.host)
             .header("Authorization", format!("DeepL-Auth-Key {}", self.auth))
@@ -155,6 +209,21 @@ impl DeeplTranslator {
             .map_err(|e| Error::new("Failed to deserialize", e))?;
         Ok(json)
     }
+
+    /// Combines the context into the deepl context parameter.
+    /// Deepl expects text in the source language, so only the chatgpt context and the sources of the history are used.
+    /// The domain is a free-form description and not sent.
+    fn generate_context(context: &[Context]) -> Option<String> {
+        let mut items = vec![];
+        if let Some(v) = get_gpt_context(context) {
+            items.push(v.to_string());
+        }
+        items.extend(get_history(context).iter().map(|v| v.source.to_string()));
+        match items.is_empty() {
+            true => None,
+            false => Some(items.join("\n")),
+        }
+    }
 }
 
 /// Translation response of a single element
@@ -4,6 +4,8 @@
 use reqwest::blocking::Client;
 use serde::Serialize;
 
+use crate::translators::options::TextFormat;
+
 #[allow(dead_code)]
 #[derive(Serialize)]
 struct GoogleRequest {
@@ -24,11 +26,21 @@ struct GoogleRequest {
 
 #[allow(dead_code)]
 impl GoogleRequest {
-    fn new(key: &str, from: Option<String>, to: String, queries: Vec<String>) -> Self {
+    fn new(
+        key: &str,
+        from: Option<String>,
+        to: String,
+        queries: Vec<String>,
+        format: Option<TextFormat>,
+    ) -> Self {
         Self {
             q: queries,
             target: to,
-            format: "text".to_string(), //html possible
+            format: match format {
+                Some(TextFormat::Html) | Some(TextFormat::Xml) => "html",
+                Some(TextFormat::Text) | None => "text",
+            }
+            .to_string(),
             source: from,
             model: Some("base".to_string()),
             key: key.to_string(),
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::helpers::option_error;
+use crate::translators::options::{TextFormat, TranslationOption, TranslationOptions};
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
@@ -25,13 +26,18 @@ impl TranslatorNoContext for LibreTranslateTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         let data = LibreRequest {
             q: query.to_string(),
             source: option_error(from.map(|v| v.to_libretranslate_str()))?
                 .unwrap_or_else(|| "auto".to_string()),
             target: to.to_libretranslate_str()?,
-            format: String::from("text"),
+            format: match options.format {
+                // Libretranslate only differentiates between text and html
+                Some(TextFormat::Html) | Some(TextFormat::Xml) => String::from("html"),
+                Some(TextFormat::Text) | None => String::from("text"),
+            },
             api_key: self.api_key.clone().unwrap_or_default(),
         };
 
@@ -49,10 +55,12 @@ impl TranslatorNoContext for LibreTranslateTranslator {
             TranslationResponses::WithDetectedLanguage(req) => TranslationOutput {
                 text: req.translated_text,
                 lang: Language::from_str(&req.detected_language.language)?,
+                metadata: Default::default(),
             },
             TranslationResponses::WithoutDetectedLanguage(req) => TranslationOutput {
                 text: req.translated_text,
                 lang: Language::Unknown,
+                metadata: Default::default(),
             },
         })
     }
@@ -63,13 +71,19 @@ impl TranslatorNoContext for LibreTranslateTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
-        let v = self.translate(client, &query.join("\n"), from, to)?;
+        let v = self.translate(client, &query.join("\n"), from, to, options)?;
         Ok(TranslationVecOutput {
             text: v.text.split('\n').map(|v| v.to_string()).collect(),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
+
+    fn supported_options(&self) -> &'static [TranslationOption] {
+        &[TranslationOption::Format]
+    }
 }
 
 #[cfg(feature = "fetch_languages")]
@@ -123,7 +137,7 @@ struct LibreRequest {
     source: String,
     /// target language
     target: String,
-    /// text format text
+    /// text format text or html
     format: String,
     /// api key(Optional)
     api_key: String,
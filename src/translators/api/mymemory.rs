@@ -8,6 +8,7 @@ use serde_json::Value;
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::helpers::input_limit_checker;
+use crate::translators::options::TranslationOptions;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
@@ -36,6 +37,7 @@ impl TranslatorNoContext for MyMemoryTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         input_limit_checker(query, self.input_limit)?;
         let url = format!(
@@ -80,6 +82,7 @@ impl TranslatorNoContext for MyMemoryTranslator {
         Ok(TranslationOutput {
             text,
             lang: Language::Unknown,
+            metadata: Default::default(),
         })
     }
 
@@ -89,9 +92,10 @@ impl TranslatorNoContext for MyMemoryTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let v = self
-            .translate(client, &query.join("_._._"), from, to)?
+            .translate(client, &query.join("_._._"), from, to, options)?
             .text
             .split("_._._")
             .map(|v| v.to_string())
@@ -99,6 +103,7 @@ impl TranslatorNoContext for MyMemoryTranslator {
         Ok(TranslationVecOutput {
             text: v,
             lang: Language::Unknown,
+            metadata: Default::default(),
         })
     }
 }
@@ -1,5 +1,6 @@
 use crate::error::Error;
 use crate::languages::Language;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorNoContext,
 };
@@ -20,6 +21,7 @@ impl TranslatorNoContext for PapagoApiTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> std::result::Result<TranslationOutput, Error> {
         let data = PapagoRequest {
             source: from
@@ -45,6 +47,7 @@ impl TranslatorNoContext for PapagoApiTranslator {
         Ok(TranslationOutput {
             text: res.message.result.translated_text,
             lang: Language::from_str(&res.message.result.src_lang_type)?,
+            metadata: Default::default(),
         })
     }
 
@@ -54,11 +57,13 @@ impl TranslatorNoContext for PapagoApiTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> std::result::Result<TranslationVecOutput, Error> {
-        let v = self.translate(client, &query.join("\n"), from, to)?;
+        let v = self.translate(client, &query.join("\n"), from, to, options)?;
         Ok(TranslationVecOutput {
             text: v.text.split('\n').map(|v| v.to_string()).collect(),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
 }
@@ -8,6 +8,7 @@ use serde_json::Value;
 
 use crate::error::Error;
 use crate::languages::Language;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorNoContext,
 };
@@ -25,6 +26,7 @@ impl TranslatorNoContext for YouDaoApiTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         if query.is_empty() {
             return Err(Error::new_option("Empty query"));
@@ -53,6 +55,7 @@ impl TranslatorNoContext for YouDaoApiTranslator {
                     .unwrap_or(&"unknown"),
             )
             .unwrap_or(Language::Unknown),
+            metadata: Default::default(),
         })
     }
 
@@ -62,11 +65,13 @@ impl TranslatorNoContext for YouDaoApiTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
-        let v = self.translate(client, &query.join("\n"), from, to)?;
+        let v = self.translate(client, &query.join("\n"), from, to, options)?;
         Ok(TranslationVecOutput {
             text: v.text.split('\n').map(|v| v.to_string()).collect(),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
 }
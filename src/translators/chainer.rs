@@ -6,6 +6,7 @@ use reqwest::blocking::Client;
 
 use crate::error::Error;
 use crate::languages::Language;
+use crate::translators::post_processing::PostProcessing;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_initilized::TranslatorInitialized;
 use crate::translators::Translator;
@@ -60,6 +61,7 @@ impl TranslatorSelectorInitilized {
                     v,
                     tokens,
                     &default.to,
+                    &default.post_processing,
                     client,
                     #[cfg(feature = "ctranslate_req")]
                     model_manager,
@@ -141,6 +143,7 @@ fn convert_selective_hashmap(
     translator_info_map: HashMap<Language, Translator>,
     tokens: &Tokens,
     to: &Language,
+    post_processing: &[PostProcessing],
     client: &Client,
     #[cfg(feature = "ctranslate_req")] model_manager: &ModelManager,
 ) -> Result<HashMap<Language, TranslatorInitialized>, Error> {
@@ -152,6 +155,7 @@ fn convert_selective_hashmap(
                 TranslatorInfo {
                     translator: value,
                     to: *to,
+                    post_processing: post_processing.to_vec(),
                 },
                 tokens,
                 client,
@@ -190,4 +194,6 @@ pub struct TranslatorInfo {
     pub translator: Translator,
     /// Target language
     pub to: Language,
+    /// Stages that are applied to every translation, like `PostProcessing::Typography`
+    pub post_processing: Vec<PostProcessing>,
 }
@@ -2,6 +2,10 @@ use std::collections::HashMap;
 
 use crate::error::Error;
 use crate::languages::Language;
+use crate::translators::context::{
+    get_domain, get_formality, get_glossary, get_gpt_context, get_history, get_notes, get_speakers,
+    Context,
+};
 use crate::translators::translator_structure::TranslationVecOutput;
 
 /// extract translations from response
@@ -39,6 +43,7 @@ pub fn process_result(s: String, queries: &[String]) -> Result<TranslationVecOut
     Ok(TranslationVecOutput {
         text: result,
         lang: Language::Unknown,
+        metadata: Default::default(),
     })
 }
 
@@ -46,11 +51,70 @@ pub fn process_result(s: String, queries: &[String]) -> Result<TranslationVecOut
 pub fn generate_query(
     queries: &[String],
     target: &str,
-    context: Option<&str>,
+    context_data: &[Context],
 ) -> Result<String, Error> {
-    let mut query = format!("{}Can you translate these sentences to {}? Please keep the numbering! The output formatting should be the same as the input: ", context.map(|v|format!("{}. ", v)).unwrap_or_default(), target);
+    let mut query = generate_context(context_data);
+    query.push_str(&format!("Can you translate these sentences to {}? Please keep the numbering! The output formatting should be the same as the input: ", target));
     for (i, q) in queries.iter().enumerate() {
         query.push_str(&format!("\n{}: {}", i + 1, q));
     }
     Ok(query)
 }
+
+/// Renders the context into text that is put in front of the query
+fn generate_context(context_data: &[Context]) -> String {
+    let mut context = String::new();
+    if let Some(v) = get_gpt_context(context_data) {
+        context.push_str(&format!("{}. ", v));
+    }
+    if let Some(v) = get_domain(context_data) {
+        context.push_str(&format!("The text is from the domain: {}. ", v));
+    }
+    if let Some(v) = get_formality(context_data).and_then(|v| v.to_prompt_str()) {
+        context.push_str(&format!("Use {} language. ", v));
+    }
+    let glossary = get_glossary(context_data);
+    if !glossary.is_empty() {
+        context.push_str("Always use these translations for the following terms: ");
+        context.push_str(
+            &glossary
+                .iter()
+                .map(|v| format!("\"{}\" => \"{}\"", v.source, v.target))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        context.push_str(". ");
+    }
+    let history = get_history(context_data);
+    if !history.is_empty() {
+        context.push_str("The previous sentences were translated like this:");
+        for v in history {
+            context.push_str(&format!("\n{} => {}", v.source, v.translation));
+        }
+        context.push('\n');
+    }
+    if let Some(speakers) = get_speakers(context_data) {
+        let speakers = speakers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| v.as_ref().map(|v| format!("{}: {}", i + 1, v)))
+            .collect::<Vec<_>>();
+        if !speakers.is_empty() {
+            context.push_str(&format!(
+                "The sentences are spoken by these characters: {}. ",
+                speakers.join(", ")
+            ));
+        }
+    }
+    if let Some(notes) = get_notes(context_data) {
+        let notes = notes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| v.as_ref().map(|v| format!("\n{}: {}", i + 1, v)))
+            .collect::<String>();
+        if !notes.is_empty() {
+            context.push_str(&format!("Notes for the sentences:{}\n", notes));
+        }
+    }
+    context
+}
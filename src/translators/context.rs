@@ -1,20 +1,140 @@
+#[derive(Clone, Debug)]
 pub enum Context {
     /// Aditional info for chatgpt for enhance the translation.
     /// Example: The following text is a conversation between two people.
     ChatGPT(String),
+    /// Previously translated text, like the previous pages of a manga.
+    /// Oldest entry first.
+    History(Vec<HistoryEntry>),
+    /// Speaker of each line. The index matches the index of the query,
+    /// None when the speaker is unknown.
+    Speakers(Vec<Option<String>>),
+    /// Notes for each line, like the context or comments of an ui string.
+    /// The index matches the index of the query, None when there is no note.
+    Notes(Vec<Option<String>>),
+    /// Domain or genre of the text.
+    /// Example: Fantasy manga, medical report
+    Domain(String),
+    /// How formal the translation should be
+    Formality(Formality),
+    /// Fixed translations for terms like names or places
+    Glossary(Vec<GlossaryEntry>),
+}
+
+/// A source text and its translation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// original text
+    pub source: String,
+    /// translated text
+    pub translation: String,
+}
+
+/// A term and the translation that should always be used for it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlossaryEntry {
+    /// term in the source language
+    pub source: String,
+    /// term in the target language
+    pub target: String,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Formality {
+    #[default]
+    Default,
+    /// Formal language, error if the translator doesnt support it
+    More,
+    /// Informal language, error if the translator doesnt support it
+    Less,
+    /// Formal language if available
+    PreferMore,
+    /// Informal language if available
+    PreferLess,
+}
+
+impl Formality {
+    /// Value of the deepl formality parameter
+    pub fn to_deepl_str(&self) -> &'static str {
+        match self {
+            Formality::Default => "default",
+            Formality::More => "more",
+            Formality::Less => "less",
+            Formality::PreferMore => "prefer_more",
+            Formality::PreferLess => "prefer_less",
+        }
+    }
+
+    /// Description for chatbots, None when the default is used
+    pub fn to_prompt_str(&self) -> Option<&'static str> {
+        match self {
+            Formality::Default => None,
+            Formality::More | Formality::PreferMore => Some("formal"),
+            Formality::Less | Formality::PreferLess => Some("informal"),
+        }
+    }
 }
 
 /// Extracts the context for chatgpt from an array of Contexts
 pub fn get_gpt_context(context_data: &[Context]) -> Option<&str> {
-    let mut context = None;
-    for c in context_data {
-        context = match c {
-            Context::ChatGPT(v) => Some(v.as_ref()),
+    context_data.iter().find_map(|c| match c {
+        Context::ChatGPT(v) => Some(v.as_ref()),
+        _ => None,
+    })
+}
+
+/// Extracts the history from an array of Contexts. Multiple histories are appended.
+pub fn get_history(context_data: &[Context]) -> Vec<&HistoryEntry> {
+    context_data
+        .iter()
+        .filter_map(|c| match c {
+            Context::History(v) => Some(v),
             _ => None,
-        };
-        if context.is_some() {
-            break;
-        }
-    }
-    context
+        })
+        .flatten()
+        .collect()
+}
+
+/// Extracts the speakers from an array of Contexts
+pub fn get_speakers(context_data: &[Context]) -> Option<&[Option<String>]> {
+    context_data.iter().find_map(|c| match c {
+        Context::Speakers(v) => Some(v.as_slice()),
+        _ => None,
+    })
+}
+
+/// Extracts the notes from an array of Contexts
+pub fn get_notes(context_data: &[Context]) -> Option<&[Option<String>]> {
+    context_data.iter().find_map(|c| match c {
+        Context::Notes(v) => Some(v.as_slice()),
+        _ => None,
+    })
+}
+
+/// Extracts the domain from an array of Contexts
+pub fn get_domain(context_data: &[Context]) -> Option<&str> {
+    context_data.iter().find_map(|c| match c {
+        Context::Domain(v) => Some(v.as_ref()),
+        _ => None,
+    })
+}
+
+/// Extracts the formality from an array of Contexts
+pub fn get_formality(context_data: &[Context]) -> Option<Formality> {
+    context_data.iter().find_map(|c| match c {
+        Context::Formality(v) => Some(*v),
+        _ => None,
+    })
+}
+
+/// Extracts the glossary from an array of Contexts. Multiple glossaries are appended.
+pub fn get_glossary(context_data: &[Context]) -> Vec<&GlossaryEntry> {
+    context_data
+        .iter()
+        .filter_map(|c| match c {
+            Context::Glossary(v) => Some(v),
+            _ => None,
+        })
+        .flatten()
+        .collect()
 }
@@ -7,6 +7,10 @@ use crate::languages::Language;
 use crate::translators::api::deepl::DeeplTranslator;
 use crate::translators::api::libretranslate::LibreTranslateTranslator;
 use crate::translators::api::mymemory::MyMemoryTranslator;
+#[cfg(feature = "argos")]
+use crate::translators::offline::argos::ArgosPackage;
+#[cfg(feature = "opus-mt")]
+use crate::translators::offline::opus_mt::OpusMtModel;
 use crate::translators::scrape::baidu::BaiduTranslator;
 use crate::translators::scrape::bing::BingTranslator;
 use crate::translators::scrape::google::GoogleTranslator;
@@ -48,6 +52,27 @@ pub async fn get_languages(translator: &Translator, tokens: &Tokens) -> Result<V
         Translator::JParaCrawl(_, _, _) => unimplemented!(),
         #[cfg(feature = "sugoi")]
         Translator::Sugoi(_, _) => unimplemented!(),
+        #[cfg(feature = "opus-mt")]
+        Translator::OpusMt(_, _, _, _) => Ok(OpusMtModel::supported_languages()
+            .iter()
+            .map(|v| v.to_6391_str())
+            .collect::<Result<_, _>>()?),
+        #[cfg(feature = "mbart50")]
+        Translator::MBart50(_, _) => Ok(Language::get_supported_mbart50()
+            .iter()
+            .map(|v| v.to_mbart50_str())
+            .collect::<Result<_, _>>()?),
+        #[cfg(feature = "madlad400")]
+        Translator::Madlad400(_, _, _) => Ok(Language::get_supported_madlad400()
+            .iter()
+            .map(|v| v.to_madlad400_str())
+            .collect::<Result<_, _>>()?),
+        #[cfg(feature = "argos")]
+        Translator::Argos(_, path) => {
+            let package = ArgosPackage::open(path)?;
+            Ok(vec![package.metadata.from_code, package.metadata.to_code])
+        }
+        Translator::Transliterate => Ok(vec![]),
     }
 }
 
@@ -78,6 +103,15 @@ pub async fn get_csv_errors(
             Translator::JParaCrawl(_, _, _) => v.to_jparacrawl_str(),
             #[cfg(feature = "sugoi")]
             Translator::Sugoi(_, _) => v.to_sugoi_str(),
+            #[cfg(feature = "opus-mt")]
+            Translator::OpusMt(_, _, _, _) => v.to_6391_str(),
+            #[cfg(feature = "mbart50")]
+            Translator::MBart50(_, _) => v.to_mbart50_str(),
+            #[cfg(feature = "madlad400")]
+            Translator::Madlad400(_, _, _) => v.to_madlad400_str(),
+            #[cfg(feature = "argos")]
+            Translator::Argos(_, _) => v.to_6391_str(),
+            Translator::Transliterate => v.to_name_str(),
         }
     };
 
@@ -110,6 +144,17 @@ pub async fn get_csv_errors(
         Translator::JParaCrawl(_, _, _) => Language::get_supported_jparacrawl(),
         #[cfg(feature = "sugoi")]
         Translator::Sugoi(_, _) => Language::get_supported_sugoi(),
+        #[cfg(feature = "opus-mt")]
+        Translator::OpusMt(_, _, _, _) => OpusMtModel::supported_languages(),
+        #[cfg(feature = "mbart50")]
+        Translator::MBart50(_, _) => Language::get_supported_mbart50(),
+        #[cfg(feature = "madlad400")]
+        Translator::Madlad400(_, _, _) => Language::get_supported_madlad400(),
+        #[cfg(feature = "argos")]
+        Translator::Argos(_, ref path) => ArgosPackage::open(path)
+            .map(|v| vec![v.from, v.to])
+            .unwrap_or_default(),
+        Translator::Transliterate => vec![],
     };
 
     let missing = langs
@@ -1,39 +1,44 @@
 use std::fmt::Formatter;
+#[cfg(feature = "argos")]
+use std::path::PathBuf;
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::vec;
 
-use log::info;
+use log::{info, warn};
 #[cfg(feature = "ctranslate_req")]
 use model_manager::model_manager::ModelManager;
 use reqwest::blocking::Client;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-use crate::detector::{detect_language, Detectors};
+use crate::detector::{detect_language, detect_language_confidence, Detectors};
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::api::chatgpt::ChatGPTModel;
 use crate::translators::chainer::{TranslatorSelectorInfo, TranslatorSelectorInitilized};
 use crate::translators::context::Context;
+use crate::translators::normalization::{has_kana, has_kanji, JapaneseNormalization};
 #[cfg(feature = "ctranslate_req")]
-use crate::translators::offline::ctranslate2::model_management::{
-    CTranslateModels, TokenizerModels,
-};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
 #[cfg(feature = "ctranslate_req")]
-use crate::translators::offline::ctranslate2::Device;
+use crate::translators::offline::ctranslate2::DeviceConfig;
 #[cfg(feature = "jparacrawl")]
 use crate::translators::offline::jparacrawl::JParaCrawlModelType;
 #[cfg(feature = "m2m100")]
 use crate::translators::offline::m2m100::M2M100ModelType;
+#[cfg(feature = "madlad400")]
+use crate::translators::offline::madlad400::Madlad400ModelType;
 #[cfg(feature = "nllb")]
 use crate::translators::offline::nllb::NllbModelType;
 #[cfg(feature = "ctranslate_req")]
 use crate::translators::offline::ModelFormat;
+use crate::translators::options::{TextFormat, TranslationOption, TranslationOptions};
+use crate::translators::post_processing::post_process;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_initilized::TranslatorInitialized;
 use crate::translators::translator_structure::{
-    TranslationOutput, TranslationVecOutput, TranslatorDyn,
+    TranslationMetadata, TranslationOutput, TranslationVecOutput, TranslatorDyn,
 };
 
 pub mod api;
@@ -42,11 +47,15 @@ mod chatbot;
 pub mod context;
 pub mod dev;
 mod helpers;
+pub mod normalization;
 pub mod offline;
+pub mod options;
+pub mod post_processing;
 pub mod scrape;
 pub mod tokens;
 mod translator_initilized;
 pub mod translator_structure;
+pub mod transliteration;
 
 #[derive(Default, PartialEq, Eq, Clone, Debug)]
 pub enum ConversationStyleClone {
@@ -119,13 +128,25 @@ pub enum Translator {
     #[cfg(any(feature = "baidu-scrape", feature = "baidu"))]
     Baidu(TranslatorKind),
     #[cfg(feature = "nllb")]
-    Nllb(Device, ModelFormat, NllbModelType),
+    Nllb(DeviceConfig, ModelFormat, NllbModelType),
     #[cfg(feature = "m2m100")]
-    M2M100(Device, ModelFormat, M2M100ModelType),
+    M2M100(DeviceConfig, ModelFormat, M2M100ModelType),
     #[cfg(feature = "jparacrawl")]
-    JParaCrawl(Device, ModelFormat, JParaCrawlModelType),
+    JParaCrawl(DeviceConfig, ModelFormat, JParaCrawlModelType),
     #[cfg(feature = "sugoi")]
-    Sugoi(Device, ModelFormat),
+    Sugoi(DeviceConfig, ModelFormat),
+    /// Opus-mt model that translates the first language to the second
+    #[cfg(feature = "opus-mt")]
+    OpusMt(DeviceConfig, ModelFormat, Language, Language),
+    #[cfg(feature = "mbart50")]
+    MBart50(DeviceConfig, ModelFormat),
+    #[cfg(feature = "madlad400")]
+    Madlad400(DeviceConfig, ModelFormat, Madlad400ModelType),
+    /// Argos translate package, the directory of the package or the .argosmodel file
+    #[cfg(feature = "argos")]
+    Argos(DeviceConfig, PathBuf),
+    /// Romanization of the source text, like romaji for japanese
+    Transliterate,
 }
 
 impl std::fmt::Display for Translator {
@@ -159,6 +180,15 @@ impl std::fmt::Display for Translator {
             Translator::JParaCrawl(_, _, _) => write!(f, "JparaCrawl"),
             #[cfg(feature = "sugoi")]
             Translator::Sugoi(_, _) => write!(f, "Sugui"),
+            #[cfg(feature = "opus-mt")]
+            Translator::OpusMt(_, _, _, _) => write!(f, "OpusMt"),
+            #[cfg(feature = "mbart50")]
+            Translator::MBart50(_, _) => write!(f, "MBart50"),
+            #[cfg(feature = "madlad400")]
+            Translator::Madlad400(_, _, _) => write!(f, "Madlad400"),
+            #[cfg(feature = "argos")]
+            Translator::Argos(_, _) => write!(f, "Argos"),
+            Translator::Transliterate => write!(f, "Transliterate"),
         }
     }
 }
@@ -213,28 +243,52 @@ impl FromStr for Translator {
             }
             #[cfg(feature = "nllb")]
             "nllb" => {
-                let d = Device::gpu(s.remove(0).parse().map_err(|_| ())?);
+                let d = DeviceConfig::from_str(s.remove(0))?;
                 let mf = ModelFormat::Compact;
                 let mtype = NllbModelType::from_str(s.remove(0)).map_err(|_| ())?;
                 Self::Nllb(d, mf, mtype)
             }
             #[cfg(feature = "m2m100")]
             "m2m100" => {
-                let d = Device::gpu(s.remove(0).parse().map_err(|_| ())?);
+                let d = DeviceConfig::from_str(s.remove(0))?;
                 let mtype = M2M100ModelType::from_str(s.remove(0)).map_err(|_| ())?;
                 Self::M2M100(d, ModelFormat::Compact, mtype)
             }
             #[cfg(feature = "jparacrawl")]
             "jparacrawl" => {
-                let d = Device::gpu(s.remove(0).parse().map_err(|_| ())?);
+                let d = DeviceConfig::from_str(s.remove(0))?;
                 let mtype = JParaCrawlModelType::from_str(s.remove(0)).map_err(|_| ())?;
                 Self::JParaCrawl(d, ModelFormat::Compact, mtype)
             }
             #[cfg(feature = "sugoi")]
             "sugoi" => {
-                let d = Device::gpu(s.remove(0).parse().map_err(|_| ())?);
+                let d = DeviceConfig::from_str(s.remove(0))?;
                 Self::Sugoi(d, ModelFormat::Compact)
             }
+            #[cfg(feature = "opus-mt")]
+            "opusmt" => {
+                let d = DeviceConfig::from_str(s.remove(0))?;
+                let from = Language::from_str(s.remove(0)).map_err(|_| ())?;
+                let to = Language::from_str(s.remove(0)).map_err(|_| ())?;
+                Self::OpusMt(d, ModelFormat::Compact, from, to)
+            }
+            #[cfg(feature = "mbart50")]
+            "mbart50" => {
+                let d = DeviceConfig::from_str(s.remove(0))?;
+                Self::MBart50(d, ModelFormat::Compact)
+            }
+            #[cfg(feature = "madlad400")]
+            "madlad400" => {
+                let d = DeviceConfig::from_str(s.remove(0))?;
+                let mtype = Madlad400ModelType::from_str(s.remove(0)).map_err(|_| ())?;
+                Self::Madlad400(d, ModelFormat::Compact, mtype)
+            }
+            #[cfg(feature = "argos")]
+            "argos" => {
+                let d = DeviceConfig::from_str(s.remove(0))?;
+                Self::Argos(d, PathBuf::from(s.remove(0)))
+            }
+            "transliterate" => Self::Transliterate,
             _ => return Err(()),
         })
     }
@@ -330,6 +384,11 @@ pub struct Translators {
     /// Reqwest client. This is used for all requests except for the chatgpt translator
     pub client: Client,
     pub max_sim_conn: usize,
+    /// Clean up of japanese text before the detection and the translation
+    pub normalization: Option<JapaneseNormalization>,
+    /// Loaded models of the offline translators
+    #[cfg(feature = "ctranslate_req")]
+    pub model_pool: ModelPool,
 }
 
 impl Translators {
@@ -375,6 +434,9 @@ impl Translators {
             tokens,
             client,
             max_sim_conn: 5,
+            normalization: None,
+            #[cfg(feature = "ctranslate_req")]
+            model_pool: Default::default(),
         })
     }
 
@@ -406,6 +468,15 @@ impl Translators {
                 Translator::JParaCrawl(_, _, _) => lang.to_jparacrawl_str(),
                 #[cfg(feature = "sugoi")]
                 Translator::Sugoi(_, _) => lang.to_sugoi_str(),
+                #[cfg(feature = "opus-mt")]
+                Translator::OpusMt(_, _, _, _) => lang.to_6391_str(),
+                #[cfg(feature = "mbart50")]
+                Translator::MBart50(_, _) => lang.to_mbart50_str(),
+                #[cfg(feature = "madlad400")]
+                Translator::Madlad400(_, _, _) => lang.to_madlad400_str(),
+                #[cfg(feature = "argos")]
+                Translator::Argos(_, _) => lang.to_6391_str(),
+                Translator::Transliterate => lang.to_name_str(),
             }
         };
         match &selector {
@@ -448,11 +519,11 @@ impl Translators {
         text: String,
         from: Option<Language>,
         context_data: &[Context],
-        #[cfg(feature = "ctranslate_req")] translator_models: &mut CTranslateModels,
-        #[cfg(feature = "ctranslate_req")] tokenizer_models: &mut TokenizerModels,
+        options: &TranslationOptions,
     ) -> Result<Vec<TranslationOutput>, Error> {
+        let text = self.normalize(text, from);
         let add_from_lang = from.is_some();
-        let lang = self.get_lang(from, &text)?;
+        let (lang, confidence) = self.get_lang(from, &text)?;
         let from = match add_from_lang {
             true => Some(lang),
             false => None,
@@ -462,7 +533,12 @@ impl Translators {
             return Err(Error::new_option("No translator found"));
         }
 
-        let mut translations: Vec<TranslationOutput> = vec![TranslationOutput { text, lang }];
+        let metadata = Self::source_metadata(lang, confidence, text.chars().count());
+        let mut translations: Vec<TranslationOutput> = vec![TranslationOutput {
+            text,
+            lang,
+            metadata,
+        }];
 
         match &self.translators {
             TranslatorSelectorInitilized::List(items) => {
@@ -471,9 +547,7 @@ impl Translators {
                         let mut v = translations.first_mut().ok_or_else(|| {
                             Error::new_option("initial translation value not set")
                         })?;
-                        if v.lang == Language::Unknown {
-                            v.lang = detect_language(&v.text, &self.detector)?;
-                        }
+                        self.detect_source(&mut v.lang, &mut v.metadata, &v.text)?;
                         (v.text.to_string(), Some(v.lang))
                     }
                     false => (
@@ -485,19 +559,17 @@ impl Translators {
                         from,
                     ),
                 };
+                let confidence = translations.first().and_then(|v| v.metadata.confidence);
 
                 //TODO: replace with multithreaded version
                 let mut v = vec![];
                 for item in items {
                     v.push(self.translate_fetch(
                         &queries,
-                        from,
+                        (from, confidence),
                         context_data,
+                        options,
                         item,
-                        #[cfg(feature = "ctranslate_req")]
-                        translator_models,
-                        #[cfg(feature = "ctranslate_req")]
-                        tokenizer_models,
                     ));
                 }
                 for value in v {
@@ -506,41 +578,39 @@ impl Translators {
             }
             _ => {
                 for translator in chain {
-                    let (query, from) = match &translator.translator {
+                    let source = translations.len() == 1;
+                    let (query, from, confidence) = match &translator.translator {
                         Translator::MyMemory => {
                             let mut v = translations.last_mut().ok_or_else(|| {
                                 Error::new_option("initial translation value not set")
                             })?;
-                            if v.lang == Language::Unknown {
-                                v.lang = detect_language(&v.text, &self.detector)?;
-                            }
-                            (v.text.to_string(), Some(v.lang))
+                            let detected =
+                                self.detect_source(&mut v.lang, &mut v.metadata, &v.text)?;
+                            let confidence = v.metadata.confidence.filter(|_| source || detected);
+                            (v.text.to_string(), Some(v.lang), confidence)
                         }
-                        _ => (
-                            translations
+                        _ => {
+                            let v = translations
                                 .last()
-                                .ok_or_else(|| Error::new_option("No translation value set"))?
-                                .text
-                                .to_string(),
-                            from,
-                        ),
+                                .ok_or_else(|| Error::new_option("No translation value set"))?;
+                            let confidence = v.metadata.confidence.filter(|_| source);
+                            (v.text.to_string(), from, confidence)
+                        }
                     };
-                    #[cfg(feature = "ctranslate_req")]
                     let text = self.translate_fetch(
                         &query,
-                        from,
+                        (from, confidence),
                         context_data,
+                        options,
                         translator,
-                        translator_models,
-                        tokenizer_models,
                     )?;
-
-                    #[cfg(not(feature = "ctranslate_req"))]
-                    let text = self.translate_fetch(&query, from, context_data, translator)?;
                     if translations.len() == 1 {
                         if let Some(v) = translations.last_mut() {
-                            if v.lang == Language::Unknown && text.lang != Language::Unknown {
-                                v.lang = text.lang;
+                            if let (Language::Unknown, Some(detected)) =
+                                (v.lang, text.metadata.detected_lang)
+                            {
+                                v.lang = detected;
+                                v.metadata.detected_lang = Some(detected);
                             }
                         }
                     }
@@ -553,28 +623,38 @@ impl Translators {
         Ok(translations)
     }
 
+    /// `source` is the source language and the confidence of the detector when it was detected
     fn translate_fetch(
         &self,
         query: &str,
-        from: Option<Language>,
+        source: (Option<Language>, Option<f64>),
         context_data: &[Context],
+        options: &TranslationOptions,
         translator: &TranslatorInitialized,
-        #[cfg(feature = "ctranslate_req")] translator_models: &mut CTranslateModels,
-        #[cfg(feature = "ctranslate_req")] tokenizer_models: &mut TokenizerModels,
     ) -> Result<TranslationOutput, Error> {
         info!(
             "Translate \"{}\" with {}",
             query,
             translator.translator.to_string()
         );
+        Self::warn_ignored_options(translator, options);
+        let (from, confidence) = source;
+        let start = Instant::now();
+        let mut attempts = 0;
         let text = match &translator.data {
             TranslatorDyn::WC(v) => {
                 let mut temp;
-                let mut retry = 0;
                 loop {
-                    temp = v.translate(&self.client, query, from, &translator.to, context_data);
-                    retry += 1;
-                    if temp.is_ok() || retry > self.retry_count.unwrap_or(3) {
+                    temp = v.translate(
+                        &self.client,
+                        query,
+                        from,
+                        &translator.to,
+                        context_data,
+                        options,
+                    );
+                    attempts += 1;
+                    if temp.is_ok() || attempts > self.retry_count.unwrap_or(3) {
                         break;
                     }
                 }
@@ -582,29 +662,38 @@ impl Translators {
             }
             TranslatorDyn::NC(v) => {
                 let mut temp;
-                let mut retry = 0;
                 loop {
-                    temp = v.translate(&self.client, query, from, &translator.to);
-                    retry += 1;
-                    if temp.is_ok() || retry > self.retry_count.unwrap_or(3) {
+                    temp = v.translate(&self.client, query, from, &translator.to, options);
+                    attempts += 1;
+                    if temp.is_ok() || attempts > self.retry_count.unwrap_or(3) {
                         break;
                     }
                 }
                 temp?
             }
             #[cfg(feature = "ctranslate_req")]
-            TranslatorDyn::Of(v) => v.translate(
-                translator_models,
-                tokenizer_models,
-                query,
-                from,
-                &translator.to,
-            )?,
+            TranslatorDyn::Of(v) => {
+                attempts = 1;
+                v.translate(&self.model_pool, query, from, &translator.to, options)?
+            }
         };
 
         Ok(TranslationOutput {
-            text: text.text,
+            text: post_process(
+                &translator.post_processing,
+                text.text,
+                translator.to,
+                Self::is_markup(options),
+            ),
             lang: translator.to,
+            metadata: Self::fetch_metadata(
+                translator,
+                text.lang,
+                confidence,
+                start,
+                attempts,
+                query.chars().count(),
+            ),
         })
     }
 
@@ -614,11 +703,27 @@ impl Translators {
         queries: Vec<String>,
         from: Option<Language>,
         context_data: &[Context],
-        #[cfg(feature = "ctranslate_req")] translator_models: &mut CTranslateModels,
-        #[cfg(feature = "ctranslate_req")] tokenizer_models: &mut TokenizerModels,
+        options: &TranslationOptions,
+    ) -> Result<Vec<TranslationVecOutput>, Error> {
+        self.translate_vec_to(queries, from, None, context_data, options)
+    }
+
+    /// Like translate_vec, but the last translator translates into `to` instead of its own target language.
+    /// With the List mode every translator translates into `to`.
+    pub fn translate_vec_to(
+        &self,
+        queries: Vec<String>,
+        from: Option<Language>,
+        to: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
     ) -> Result<Vec<TranslationVecOutput>, Error> {
+        let queries = queries
+            .into_iter()
+            .map(|v| self.normalize(v, from))
+            .collect::<Vec<_>>();
         let add_from_lang = from.is_some();
-        let lang = self.get_lang(from, &queries.join("\n"))?;
+        let (lang, confidence) = self.get_lang(from, &queries.join("\n"))?;
         let from = match add_from_lang {
             true => Some(lang),
             false => None,
@@ -628,43 +733,45 @@ impl Translators {
             return Err(Error::new_option("No translator found"));
         }
 
+        let metadata = Self::source_metadata(
+            lang,
+            confidence,
+            queries.iter().map(|v| v.chars().count()).sum(),
+        );
         let mut translations: Vec<TranslationVecOutput> = vec![TranslationVecOutput {
             text: queries,
             lang,
+            metadata,
         }];
 
         match &self.translators {
             TranslatorSelectorInitilized::List(items) => {
-                let (queries, from) = match Self::need_lang(items) {
+                let (queries, from, confidence) = match Self::need_lang(items) {
                     true => {
                         let mut v = translations.first_mut().ok_or_else(|| {
                             Error::new_option("initial translation value not set")
                         })?;
-                        if v.lang == Language::Unknown {
-                            v.lang = detect_language(&v.text.join("\n"), &self.detector)?;
-                        }
-                        (&v.text, Some(v.lang))
+                        self.detect_source(&mut v.lang, &mut v.metadata, &v.text.join("\n"))?;
+                        (&v.text, Some(v.lang), v.metadata.confidence)
+                    }
+                    false => {
+                        let v = translations.first().ok_or_else(|| {
+                            Error::new_option("initial translation value not set")
+                        })?;
+                        (&v.text, from, v.metadata.confidence)
                     }
-                    false => (
-                        &translations
-                            .first()
-                            .ok_or_else(|| Error::new_option("initial translation value not set"))?
-                            .text,
-                        from,
-                    ),
                 };
                 //TODO: replace with multithreaded version
                 let mut v = vec![];
                 for item in items {
+                    let item_to = to.unwrap_or(item.to);
                     v.push(self.translate_vec_fetch(
                         queries,
-                        from,
+                        (from, confidence),
                         item,
+                        item_to,
                         context_data,
-                        #[cfg(feature = "ctranslate_req")]
-                        translator_models,
-                        #[cfg(feature = "ctranslate_req")]
-                        tokenizer_models,
+                        options,
                     ));
                 }
                 for value in v {
@@ -672,41 +779,48 @@ impl Translators {
                 }
             }
             _ => {
-                for translator in chain {
-                    let (queries, from) = match &translator.translator {
+                let last = chain.len() - 1;
+                for (i, translator) in chain.into_iter().enumerate() {
+                    let translator_to = match i == last {
+                        true => to.unwrap_or(translator.to),
+                        false => translator.to,
+                    };
+                    let source = translations.len() == 1;
+                    let (queries, from, confidence) = match &translator.translator {
                         Translator::MyMemory => {
                             let mut v = translations.last_mut().ok_or_else(|| {
                                 Error::new_option("initial translation value not set")
                             })?;
-                            if v.lang == Language::Unknown {
-                                v.lang = detect_language(&v.text.join("\n"), &self.detector)?;
-                            }
-                            (&v.text, Some(v.lang))
+                            let detected = self.detect_source(
+                                &mut v.lang,
+                                &mut v.metadata,
+                                &v.text.join("\n"),
+                            )?;
+                            let confidence = v.metadata.confidence.filter(|_| source || detected);
+                            (&v.text, Some(v.lang), confidence)
+                        }
+                        _ => {
+                            let v = translations.last().ok_or_else(|| {
+                                Error::new_option("initial translation value not set")
+                            })?;
+                            (&v.text, from, v.metadata.confidence.filter(|_| source))
                         }
-                        _ => (
-                            &translations
-                                .last()
-                                .ok_or_else(|| {
-                                    Error::new_option("initial translation value not set")
-                                })?
-                                .text,
-                            from,
-                        ),
                     };
                     let text = self.translate_vec_fetch(
                         queries,
-                        from,
+                        (from, confidence),
                         translator,
+                        translator_to,
                         context_data,
-                        #[cfg(feature = "ctranslate_req")]
-                        translator_models,
-                        #[cfg(feature = "ctranslate_req")]
-                        tokenizer_models,
+                        options,
                     )?;
                     if translations.len() == 1 {
                         if let Some(v) = translations.last_mut() {
-                            if v.lang == Language::Unknown && text.lang != Language::Unknown {
-                                v.lang = text.lang;
+                            if let (Language::Unknown, Some(detected)) =
+                                (v.lang, text.metadata.detected_lang)
+                            {
+                                v.lang = detected;
+                                v.metadata.detected_lang = Some(detected);
                             }
                         }
                     }
@@ -718,29 +832,88 @@ impl Translators {
         Ok(translations)
     }
 
+    /// Translates the queries and returns only the result of the last translator.
+    /// Fails when the number of translations differs from the number of queries,
+    /// so a dropped or merged line can't misalign the segments.
+    pub fn translate_segments(
+        &self,
+        queries: Vec<String>,
+        from: Option<Language>,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<Vec<String>, Error> {
+        if queries.is_empty() {
+            return Ok(vec![]);
+        }
+        let count = queries.len();
+        let mut translations = self.translate_vec(queries, from, context_data, options)?;
+        let translation = translations
+            .pop()
+            .ok_or_else(|| Error::new_option("No translation found"))?;
+        Self::check_segments(count, translation)
+    }
+
+    /// Like translate_segments, but translates into `to` instead of the target language of the translators.
+    pub fn translate_segments_to(
+        &self,
+        queries: Vec<String>,
+        from: Option<Language>,
+        to: Language,
+        context_data: &[Context],
+        options: &TranslationOptions,
+    ) -> Result<Vec<String>, Error> {
+        if queries.is_empty() {
+            return Ok(vec![]);
+        }
+        let count = queries.len();
+        let mut translations =
+            self.translate_vec_to(queries, from, Some(to), context_data, options)?;
+        let translation = translations
+            .pop()
+            .ok_or_else(|| Error::new_option("No translation found"))?;
+        Self::check_segments(count, translation)
+    }
+
+    fn check_segments(
+        count: usize,
+        translation: TranslationVecOutput,
+    ) -> Result<Vec<String>, Error> {
+        if translation.text.len() != count {
+            return Err(Error::new_option(format!(
+                "Expected {} segments, got {}",
+                count,
+                translation.text.len()
+            )));
+        }
+        Ok(translation.text)
+    }
+
+    /// `source` is the source language and the confidence of the detector when it was detected
     fn translate_vec_fetch(
         &self,
         queries: &[String],
-        from: Option<Language>,
+        source: (Option<Language>, Option<f64>),
         translator: &TranslatorInitialized,
+        to: Language,
         context_data: &[Context],
-        #[cfg(feature = "ctranslate_req")] translator_models: &mut CTranslateModels,
-        #[cfg(feature = "ctranslate_req")] tokenizer_models: &mut TokenizerModels,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         info!(
             "Translate {:?} with {}",
             queries,
             translator.translator.to_string()
         );
-        match &translator.data {
+        Self::warn_ignored_options(translator, options);
+        let (from, confidence) = source;
+        let start = Instant::now();
+        let mut attempts = 0;
+        let output = match &translator.data {
             TranslatorDyn::WC(v) => {
                 let mut temp;
-                let mut retry = 0;
                 loop {
-                    temp =
-                        v.translate_vec(&self.client, queries, from, &translator.to, context_data);
-                    retry += 1;
-                    if temp.is_ok() || retry > self.retry_count.unwrap_or(3) {
+                    temp = v.translate_vec(&self.client, queries, from, &to, context_data, options);
+                    attempts += 1;
+                    if temp.is_ok() || attempts > self.retry_count.unwrap_or(3) {
                         break;
                     }
                 }
@@ -748,24 +921,127 @@ impl Translators {
             }
             TranslatorDyn::NC(v) => {
                 let mut temp;
-                let mut retry = 0;
                 loop {
-                    temp = v.translate_vec(&self.client, queries, from, &translator.to);
-                    retry += 1;
-                    if temp.is_ok() || retry > self.retry_count.unwrap_or(3) {
+                    temp = v.translate_vec(&self.client, queries, from, &to, options);
+                    attempts += 1;
+                    if temp.is_ok() || attempts > self.retry_count.unwrap_or(3) {
                         break;
                     }
                 }
                 temp
             }
             #[cfg(feature = "ctranslate_req")]
-            TranslatorDyn::Of(v) => v.translate_vec(
-                translator_models,
-                tokenizer_models,
-                queries,
-                from,
-                &translator.to,
+            TranslatorDyn::Of(v) => {
+                attempts = 1;
+                v.translate_vec(&self.model_pool, queries, from, &to, options)
+            }
+        }?;
+        let markup = Self::is_markup(options);
+        Ok(TranslationVecOutput {
+            text: output
+                .text
+                .into_iter()
+                .map(|v| post_process(&translator.post_processing, v, to, markup))
+                .collect(),
+            lang: to,
+            metadata: Self::fetch_metadata(
+                translator,
+                output.lang,
+                confidence,
+                start,
+                attempts,
+                queries.iter().map(|v| v.chars().count()).sum(),
             ),
+        })
+    }
+
+    /// Text sent with the html or xml format contains tags
+    fn is_markup(options: &TranslationOptions) -> bool {
+        options.format.is_some_and(|v| v != TextFormat::Text)
+    }
+
+    /// Metadata of the source text
+    fn source_metadata(
+        lang: Language,
+        confidence: Option<f64>,
+        chars: usize,
+    ) -> TranslationMetadata {
+        TranslationMetadata {
+            detected_lang: Some(lang).filter(|v| *v != Language::Unknown),
+            confidence,
+            chars,
+            ..Default::default()
+        }
+    }
+
+    /// Metadata of a translation. The detected language is only set when the translator reported it,
+    /// the confidence is the one of the detector for the source text.
+    fn fetch_metadata(
+        translator: &TranslatorInitialized,
+        reported: Language,
+        confidence: Option<f64>,
+        start: Instant,
+        attempts: u32,
+        chars: usize,
+    ) -> TranslationMetadata {
+        TranslationMetadata {
+            translator: Some(translator.translator.to_string()),
+            detected_lang: Some(reported).filter(|v| *v != Language::Unknown),
+            confidence,
+            latency: start.elapsed(),
+            attempts,
+            chars,
+        }
+    }
+
+    /// Detects the language of the source text when it is unknown, returns if it was detected
+    fn detect_source(
+        &self,
+        lang: &mut Language,
+        metadata: &mut TranslationMetadata,
+        text: &str,
+    ) -> Result<bool, Error> {
+        if *lang != Language::Unknown {
+            return Ok(false);
+        }
+        let (detected, confidence) = detect_language_confidence(text, &self.detector)?;
+        *lang = detected;
+        metadata.detected_lang = Some(detected);
+        metadata.confidence = Some(confidence);
+        Ok(true)
+    }
+
+    /// Returns the options that are ignored by each translator
+    pub fn get_ignored_options(
+        &self,
+        options: &TranslationOptions,
+    ) -> Vec<(Translator, Vec<TranslationOption>)> {
+        let translators: Vec<&TranslatorInitialized> = match &self.translators {
+            TranslatorSelectorInitilized::Chain(v) | TranslatorSelectorInitilized::List(v) => {
+                v.iter().collect()
+            }
+            TranslatorSelectorInitilized::Selective(v)
+            | TranslatorSelectorInitilized::SelectiveChain(v) => v.values().collect(),
+        };
+        translators
+            .into_iter()
+            .map(|v| {
+                (
+                    v.translator.clone(),
+                    options.ignored(v.data.supported_options()),
+                )
+            })
+            .filter(|v| !v.1.is_empty())
+            .collect()
+    }
+
+    fn warn_ignored_options(translator: &TranslatorInitialized, options: &TranslationOptions) {
+        let ignored = options.ignored(translator.data.supported_options());
+        if !ignored.is_empty() {
+            warn!(
+                "{} ignores the options {:?}",
+                translator.translator, ignored
+            );
         }
     }
 
@@ -806,14 +1082,41 @@ impl Translators {
         Ok(res)
     }
 
-    fn get_lang(&self, from: Option<Language>, text: &str) -> Result<Language, Error> {
+    /// Applies the japanese normalization, text with another source language is not changed.
+    /// Without a source language only text with kana or text detected as japanese is normalized.
+    fn normalize(&self, text: String, from: Option<Language>) -> String {
+        let normalization = match &self.normalization {
+            Some(v) => v,
+            None => return text,
+        };
+        let japanese = match from {
+            Some(v) => v == Language::Japanese,
+            None => {
+                has_kana(&text)
+                    || (has_kanji(&text)
+                        && detect_language(&text, &self.detector)
+                            .is_ok_and(|v| v == Language::Japanese))
+            }
+        };
+        match japanese {
+            true => normalization.normalize(&text),
+            false => text,
+        }
+    }
+
+    /// Returns the source language and the confidence when it was detected
+    fn get_lang(
+        &self,
+        from: Option<Language>,
+        text: &str,
+    ) -> Result<(Language, Option<f64>), Error> {
         match from {
-            Some(v) => Ok(v),
+            Some(v) => Ok((v, None)),
             None => match self.translators {
                 TranslatorSelectorInitilized::Chain(_) | TranslatorSelectorInitilized::List(_) => {
-                    Ok(Language::Unknown)
+                    Ok((Language::Unknown, None))
                 }
-                _ => detect_language(text, &self.detector),
+                _ => detect_language_confidence(text, &self.detector).map(|v| (v.0, Some(v.1))),
             },
         }
     }
@@ -0,0 +1,174 @@
+use crate::translators::post_processing::{ellipsis, Script};
+use unicode_normalization::UnicodeNormalization;
+
+/// Full width characters that are kept by nfkc, because they belong to japanese punctuation
+const NFKC_EXCEPTIONS: &str = "！？（）：；～…‥";
+
+/// Punctuation where a run of the same character is reduced to one
+const COLLAPSED: &str = "「」『』、。，！？!?ー～〜";
+
+/// Rules to clean up japanese text from ocr, applied before the language detection and the translation
+#[derive(Clone, Debug)]
+pub struct JapaneseNormalization {
+    /// Unicode nfkc, full width punctuation like ！ and ？ is kept
+    pub nfkc: bool,
+    /// Removes line breaks between japanese characters, like the lines of a speech bubble
+    pub join_lines: bool,
+    /// Removes furigana after kanji like 漢字(かんじ) or 漢字《かんじ》
+    pub strip_ruby: bool,
+    /// Reduces repeated punctuation like 「「, ！！！ or ーーー to one character
+    pub collapse_punctuation: bool,
+    /// Maps ‥, ・・・, 。。。 and ... to …
+    pub ellipsis: bool,
+}
+
+impl Default for JapaneseNormalization {
+    fn default() -> Self {
+        Self {
+            nfkc: true,
+            join_lines: true,
+            strip_ruby: true,
+            collapse_punctuation: true,
+            ellipsis: true,
+        }
+    }
+}
+
+impl JapaneseNormalization {
+    pub fn normalize(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if self.nfkc {
+            text = nfkc(&text);
+        }
+        if self.strip_ruby {
+            text = strip_ruby(&text);
+        }
+        if self.join_lines {
+            text = join_lines(&text);
+        }
+        if self.ellipsis {
+            text = ellipsis(&text, Script::Japanese, 2);
+        }
+        if self.collapse_punctuation {
+            text = collapse_punctuation(&text);
+        }
+        text
+    }
+}
+
+/// Text with kana is japanese, text with only kanji can also be chinese
+pub fn has_kana(text: &str) -> bool {
+    text.chars().any(is_kana)
+}
+
+/// Text with kanji, it needs the language detection to tell japanese from chinese
+pub fn has_kanji(text: &str) -> bool {
+    text.chars().any(is_kanji)
+}
+
+fn is_kanji(c: char) -> bool {
+    matches!(c as u32, 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF) || c == '々'
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c as u32, 0x3041..=0x30FF)
+}
+
+fn is_japanese(c: char) -> bool {
+    matches!(c as u32, 0x3000..=0x30FF | 0xFF00..=0xFFEF) || is_kanji(c)
+}
+
+fn nfkc(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut run = String::new();
+    for c in text.chars() {
+        match NFKC_EXCEPTIONS.contains(c) {
+            true => {
+                result.extend(run.nfkc());
+                run.clear();
+                result.push(c);
+            }
+            false => run.push(c),
+        }
+    }
+    result.extend(run.nfkc());
+    result
+}
+
+fn strip_ruby(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let close = match c {
+            '(' => Some(')'),
+            '（' => Some('）'),
+            '《' => Some('》'),
+            _ => None,
+        };
+        let ruby = close
+            .filter(|_| result.chars().last().is_some_and(is_kanji))
+            .and_then(|close| {
+                let end = chars[index + 1..]
+                    .iter()
+                    .take(20)
+                    .position(|v| *v == close)?;
+                let ruby = &chars[index + 1..index + 1 + end];
+                match !ruby.is_empty() && ruby.iter().all(|v| is_kana(*v)) {
+                    true => Some(end),
+                    false => None,
+                }
+            });
+        match ruby {
+            Some(end) => {
+                if let Some(start) = result.rfind(['｜', '|']) {
+                    if result[start..].chars().skip(1).all(is_kanji) {
+                        result.remove(start);
+                    }
+                }
+                index += end + 2;
+            }
+            None => {
+                result.push(c);
+                index += 1;
+            }
+        }
+    }
+    result
+}
+
+fn join_lines(text: &str) -> String {
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            let last = result.trim_end().chars().last();
+            let next = line.trim_start().chars().next();
+            match (last, next) {
+                (Some(a), Some(b)) if is_japanese(a) && is_japanese(b) => {
+                    result.truncate(result.trim_end().len());
+                    result.push_str(line.trim_start());
+                    continue;
+                }
+                _ => result.push('\n'),
+            }
+        }
+        result.push_str(line);
+    }
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+fn collapse_punctuation(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if COLLAPSED.contains(c) && result.ends_with(c) {
+            continue;
+        }
+        result.push(c);
+    }
+    result
+}
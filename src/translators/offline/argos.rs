@@ -0,0 +1,174 @@
+use crate::error::Error;
+use crate::languages::Language;
+use crate::model_register::check_files;
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::offline::ctranslate2::{strip_prefix, translate_batch, DeviceConfig};
+use crate::translators::options::TranslationOptions;
+use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
+use log::info;
+use serde::Deserialize;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Files of an installed argos package, the ctranslate2 model is in the model directory
+pub const ARGOS_FILES: &[&str] = &[
+    "metadata.json",
+    "sentencepiece.model",
+    "model/model.bin",
+    "model/config.json",
+];
+
+/// metadata.json of an argos package
+#[derive(Clone, Debug, Deserialize)]
+pub struct ArgosMetadata {
+    #[serde(default)]
+    pub package_version: String,
+    #[serde(default)]
+    pub argos_version: String,
+    pub from_code: String,
+    #[serde(default)]
+    pub from_name: String,
+    pub to_code: String,
+    #[serde(default)]
+    pub to_name: String,
+    /// Token the decoder starts with, used by packages of multilingual models
+    #[serde(default)]
+    pub target_prefix: String,
+}
+
+/// An extracted .argosmodel package
+#[derive(Clone, Debug)]
+pub struct ArgosPackage {
+    pub directory: PathBuf,
+    pub metadata: ArgosMetadata,
+    pub from: Language,
+    pub to: Language,
+}
+
+impl ArgosPackage {
+    /// Reads the metadata of an extracted package
+    pub fn open(directory: impl Into<PathBuf>) -> Result<Self, Error> {
+        let directory = directory.into();
+        check_files(&directory, ARGOS_FILES)?;
+        let path = directory.join("metadata.json");
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| Error::new(format!("Couldnt read {}", path.display()), e))?;
+        let metadata: ArgosMetadata = serde_json::from_str(&text)
+            .map_err(|e| Error::new(format!("Invalid argos metadata {}", path.display()), e))?;
+        Ok(Self {
+            from: Self::parse_code(&metadata.from_code)?,
+            to: Self::parse_code(&metadata.to_code)?,
+            directory,
+            metadata,
+        })
+    }
+
+    /// Extracts the .argosmodel archive into the directory and opens the package.
+    /// A package that is already extracted isn't extracted again.
+    pub fn install(archive: &Path, directory: &Path) -> Result<Self, Error> {
+        let file = File::open(archive)
+            .map_err(|e| Error::new(format!("Couldnt open {}", archive.display()), e))?;
+        let mut zip = zip::ZipArchive::new(file)
+            .map_err(|e| Error::new(format!("Invalid argos package {}", archive.display()), e))?;
+        let package = zip
+            .file_names()
+            .find(|v| v.ends_with("metadata.json"))
+            .and_then(|v| Path::new(v).parent())
+            .map(|v| directory.join(v))
+            .ok_or_else(|| {
+                Error::new_option(format!("No metadata.json in {}", archive.display()))
+            })?;
+        if !package.join("metadata.json").is_file() {
+            info!("Installing argos package {}", archive.display());
+            zip.extract(directory)
+                .map_err(|e| Error::new(format!("Couldnt extract {}", archive.display()), e))?;
+        }
+        Self::open(package)
+    }
+
+    /// Opens the package directory, .argosmodel files are installed next to the archive
+    pub fn open_or_install(path: &Path) -> Result<Self, Error> {
+        match path.extension().and_then(|v| v.to_str()) {
+            Some("argosmodel") => Self::install(path, path.parent().unwrap_or(Path::new("."))),
+            _ => Self::open(path),
+        }
+    }
+
+    fn parse_code(code: &str) -> Result<Language, Error> {
+        // argos uses zt for traditional chinese
+        let code = match code {
+            "zt" => "zh-TW",
+            v => v,
+        };
+        Language::from_str(code)
+            .map_err(|_| Error::new_option(format!("Unknown argos language {}", code)))
+    }
+}
+
+/// Translates with an argos translate package, like a libretranslate server without the server
+pub struct ArgosTranslator {
+    device: DeviceConfig,
+    package: ArgosPackage,
+}
+
+impl TranslatorCTranslate for ArgosTranslator {
+    fn translate_vec(
+        &self,
+        pool: &ModelPool,
+        query: &[String],
+        from: Option<Language>,
+        to: &Language,
+        _options: &TranslationOptions,
+    ) -> Result<TranslationVecOutput, Error> {
+        let package = &self.package;
+        if from.filter(|v| v != &package.from).is_some() || to != &package.to {
+            return Err(Error::new_option(format!(
+                "The argos package translates {:?} to {:?}",
+                package.from, package.to
+            )));
+        }
+        let ident = package.directory.display().to_string();
+        let tokenizer =
+            pool.get_tokenizer(&ident, package.directory.join("sentencepiece.model"))?;
+        let tokens = tokenizer.tokenize(query)?;
+        let prefix = &package.metadata.target_prefix;
+        let target = match prefix.is_empty() {
+            true => None,
+            false => Some(vec![prefix.clone(); query.len()]),
+        };
+        let translator =
+            pool.get_translator(&ident, package.directory.join("model"), &self.device)?;
+        let mut translated = translate_batch(&translator, tokens, target)?;
+        if !prefix.is_empty() {
+            strip_prefix(&mut translated, prefix);
+        }
+        let sentences = tokenizer.detokenize(translated)?;
+        Ok(TranslationVecOutput {
+            text: sentences,
+            lang: package.from,
+            metadata: Default::default(),
+        })
+    }
+}
+
+impl ArgosTranslator {
+    /// Uses the package directory or installs the .argosmodel file
+    pub fn new(path: &Path, device: &DeviceConfig) -> Result<Self, Error> {
+        Ok(Self {
+            device: device.resolve(),
+            package: ArgosPackage::open_or_install(path)?,
+        })
+    }
+
+    pub fn from_package(package: ArgosPackage, device: &DeviceConfig) -> Self {
+        Self {
+            device: device.resolve(),
+            package,
+        }
+    }
+
+    pub fn package(&self) -> &ArgosPackage {
+        &self.package
+    }
+}
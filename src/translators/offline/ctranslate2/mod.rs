@@ -1,3 +1,13 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+use rustyctranslate2::{BatchType, CTranslator};
+
+use crate::error::Error;
+use crate::translators::offline::ModelFormat;
+
 pub mod model_management;
 pub mod tokenizer;
 
@@ -25,7 +35,7 @@ impl Device {
             Device::CPU
         }
     }
-    #[allow(dead_code)]
+
     fn is_cuda(&self) -> bool {
         match self {
             Device::CPU => false,
@@ -33,9 +43,188 @@ impl Device {
         }
     }
 
-    #[allow(dead_code)]
-    fn auto() -> Self {
-        //TODO: add auto
-        Device::CPU
+    /// Cuda if nvidia-smi finds a gpu, otherwise cpu.
+    /// rustyctranslate2 has to be built with cuda to use the gpu.
+    pub fn auto() -> Self {
+        let found = std::process::Command::new("nvidia-smi")
+            .arg("-L")
+            .output()
+            .map(|v| v.status.success() && !v.stdout.is_empty())
+            .unwrap_or(false);
+        Self::gpu(found)
+    }
+}
+
+/// Type the weights are converted to when the model is loaded.
+/// The pinned rustyctranslate2 only has a flag that loads the weights compressed.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeType {
+    /// Int8 for the compact model format, otherwise the type the model was converted with
+    #[default]
+    Auto,
+    /// Keeps the type the model was converted with
+    Default,
+    /// Loads the weights compressed
+    Int8,
+}
+
+impl FromStr for ComputeType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" | "auto" => Ok(Self::Auto),
+            "default" => Ok(Self::Default),
+            "int8" => Ok(Self::Int8),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Device, compute type and number of parallel translators of the CTranslate2 models.
+/// The thread counts of CTranslate2 can't be set with the pinned rustyctranslate2.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct DeviceConfig {
+    /// None selects the device with Device::auto
+    pub device: Option<Device>,
+    pub compute_type: ComputeType,
+    /// Copies of the model that translate in parallel, 0 and 1 load a single copy
+    pub translators: usize,
+}
+
+impl From<Device> for DeviceConfig {
+    fn from(device: Device) -> Self {
+        Self {
+            device: Some(device),
+            ..Default::default()
+        }
+    }
+}
+
+impl FromStr for DeviceConfig {
+    type Err = ();
+
+    /// Parses auto, cpu, cuda or a bool that selects the gpu
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::default()),
+            "cpu" => Ok(Device::CPU.into()),
+            "cuda" => Ok(Device::CUDA.into()),
+            _ => Ok(Device::gpu(s.parse().map_err(|_| ())?).into()),
+        }
+    }
+}
+
+impl DeviceConfig {
+    /// Returns the config with the auto device resolved
+    pub fn resolve(&self) -> Self {
+        Self {
+            device: Some(self.device()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the config with the auto compute type resolved for the model format
+    pub fn with_format(self, model_format: &ModelFormat) -> Self {
+        let compute_type = match (self.compute_type, model_format) {
+            (ComputeType::Auto, ModelFormat::Compact) => ComputeType::Int8,
+            (ComputeType::Auto, ModelFormat::Normal | ModelFormat::Directory(_)) => {
+                ComputeType::Default
+            }
+            (v, _) => v,
+        };
+        Self {
+            compute_type,
+            ..self
+        }
+    }
+
+    pub fn device(&self) -> Device {
+        self.device.unwrap_or_else(Device::auto)
+    }
+
+    /// Name of the loaded model, models with different settings are loaded separately
+    pub fn ident(&self, model: &str) -> String {
+        let ident = format!(
+            "{}-{}-{:?}",
+            model,
+            self.device().to_string(),
+            self.compute_type
+        );
+        match self.translators > 1 {
+            true => format!("{}-x{}", ident, self.translators),
+            false => ident,
+        }
+    }
+
+    /// Loads the model with the settings, once for every parallel translator
+    pub fn load(&self, path: PathBuf) -> Result<ModelReplicas, Error> {
+        let compressed = self.compute_type == ComputeType::Int8;
+        let cuda = self.device().is_cuda();
+        let replicas = (0..self.translators.max(1))
+            .map(|_| {
+                CTranslator::new(path.clone(), cuda, compressed)
+                    .map(Mutex::new)
+                    .map_err(Error::new_option)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ModelReplicas {
+            replicas,
+            next: AtomicUsize::new(0),
+        })
+    }
+}
+
+/// Copies of a loaded model, a translation uses a copy that isnt translating
+pub struct ModelReplicas {
+    replicas: Vec<Mutex<CTranslator>>,
+    next: AtomicUsize,
+}
+
+impl ModelReplicas {
+    /// Locks a free copy, waits for the copies in turn when all are busy
+    fn lock(&self) -> Result<MutexGuard<'_, CTranslator>, Error> {
+        for replica in &self.replicas {
+            match replica.try_lock() {
+                Ok(v) => return Ok(v),
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Poisoned(_)) => {
+                    return Err(Error::new_option("CTranslate2 model poisoned"))
+                }
+            }
+        }
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.replicas.len().max(1);
+        self.replicas
+            .get(index)
+            .ok_or_else(|| Error::new_option("CTranslate2 model has no copies"))?
+            .lock()
+            .map_err(|_| Error::new_option("CTranslate2 model poisoned"))
+    }
+}
+
+/// Removes the target prefix from the translations that start with it
+pub fn strip_prefix(translations: &mut [Vec<String>], prefix: &str) {
+    for tokens in translations.iter_mut() {
+        if tokens.first().map(|v| v == prefix).unwrap_or(false) {
+            tokens.remove(0);
+        }
+    }
+}
+
+/// Translates the tokens and returns the best hypothesis for every sentence.
+/// The target prefix contains one token that is put in front of every translation.
+/// The pinned rustyctranslate2 only translates with the CTranslate2 decoding defaults.
+pub fn translate_batch(
+    translator: &ModelReplicas,
+    tokens: Vec<Vec<String>>,
+    target_prefix: Option<Vec<String>>,
+) -> Result<Vec<Vec<String>>, Error> {
+    let mut translator = translator.lock()?;
+    match target_prefix {
+        Some(target) => {
+            translator.translate_batch_target(tokens, None, BatchType::Example, None, target)
+        }
+        None => translator.translate_batch(tokens, None, None, BatchType::Example),
     }
+    .map_err(Error::new_option)
 }
@@ -1,107 +1,245 @@
 use crate::error::Error;
 use crate::translators::offline::ctranslate2::tokenizer::Tokenizer;
-use crate::translators::offline::ctranslate2::Device;
+use crate::translators::offline::ctranslate2::{DeviceConfig, ModelReplicas};
 use log::info;
-use rustyctranslate2::CTranslator;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
-#[derive(PartialEq, Eq)]
-pub enum ModelLifetime {
-    Dispose,
-    KeepAlive,
+/// Limits of the loaded models, models over the limits are unloaded least recently used first
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Maximum number of loaded CTranslate2 models, None for no limit
+    pub max_models: Option<usize>,
+    /// Maximum size in bytes of the loaded models and tokenizers, measured by their files
+    pub max_memory: Option<u64>,
+    /// Models that weren't used for this duration are unloaded.
+    /// There is no background thread, idle models are unloaded when the next model is loaded or by `ModelPool::unload_idle`.
+    pub idle_ttl: Option<Duration>,
 }
-pub struct TokenizerModels {
-    pub mode: ModelLifetime,
-    pub tokenizers: HashMap<String, Tokenizer>,
+
+impl PoolConfig {
+    /// Unloads every model as soon as another one is loaded
+    pub fn single() -> Self {
+        Self {
+            max_models: Some(1),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone)]
+enum PoolModel {
+    Translator(Arc<ModelReplicas>),
+    Tokenizer(Arc<Tokenizer>),
+}
+
+struct PoolEntry {
+    model: PoolModel,
+    size: u64,
+    last_used: Instant,
 }
 
-pub struct CTranslateModels {
-    pub mode: ModelLifetime,
-    pub ctranslate2_models: HashMap<String, CTranslator>,
+/// Loaded CTranslate2 models and tokenizers shared by the offline translators.
+/// The pool is synchronised internally and can be used from multiple threads.
+/// Models are loaded without holding the lock of the pool, only loads of the same model wait for each other.
+#[derive(Default)]
+pub struct ModelPool {
+    pub config: PoolConfig,
+    entries: Mutex<HashMap<String, PoolEntry>>,
+    /// Lock for every model that is being loaded
+    loading: Mutex<HashMap<String, Arc<Mutex<()>>>>,
 }
 
-impl CTranslateModels {
-    pub fn new(model_lifetime: ModelLifetime) -> Self {
+impl ModelPool {
+    pub fn new(config: PoolConfig) -> Self {
         Self {
-            mode: model_lifetime,
-            ctranslate2_models: HashMap::new(),
+            config,
+            entries: Default::default(),
+            loading: Default::default(),
         }
     }
+
+    /// Returns the loaded translator or loads it.
+    /// Every copy of the model translates one request at a time, `DeviceConfig::translators` sets the number of copies.
+    /// Translations with different models or devices run in parallel.
     pub fn get_translator(
-        &mut self,
+        &self,
         ident: &str,
         path: PathBuf,
-        device: &Device,
-        compressed: bool,
-    ) -> Result<&mut CTranslator, Error> {
-        let v = self.ctranslate2_models.get(ident);
-        if v.is_none() {
+        config: &DeviceConfig,
+    ) -> Result<Arc<ModelReplicas>, Error> {
+        let key = format!("ctranslate2/{}", config.ident(ident));
+        let size = || dir_size(&path) * config.translators.max(1) as u64;
+        let model = self.get_or_load(&key, size, true, || {
             info!("Loading ctranslate2 model {}", ident);
-            let ctranslate2_model =
-                CTranslator::new(path, device.is_cuda(), compressed).map_err(Error::new_option)?;
-            self.ctranslate2_models
-                .insert(ident.to_string(), ctranslate2_model);
-            return self
-                .ctranslate2_models
-                .get_mut(ident)
-                .ok_or_else(|| Error::new_option("CTranslate2 model not found"));
+            Ok(PoolModel::Translator(Arc::new(config.load(path.clone())?)))
+        })?;
+        match model {
+            PoolModel::Translator(v) => Ok(v),
+            PoolModel::Tokenizer(_) => Err(Error::new_option(format!("{} isnt a model", key))),
         }
-        self.ctranslate2_models
-            .get_mut(ident)
-            .ok_or_else(|| Error::new_option("CTranslate2 not found"))
     }
-    pub fn cleanup(&mut self) {
-        //TODO: remove only model that was created(multithreaded)
-        if self.mode == ModelLifetime::Dispose {
-            info!("Unload ctranslate2 models");
-            self.ctranslate2_models.clear();
+
+    /// Returns the loaded tokenizer or loads it
+    pub fn get_tokenizer(&self, ident: &str, path: PathBuf) -> Result<Arc<Tokenizer>, Error> {
+        let key = format!("tokenizer/{}", ident);
+        let size = || std::fs::metadata(&path).map(|v| v.len()).unwrap_or(0);
+        let model = self.get_or_load(&key, size, false, || {
+            info!("Loading tokenizer {}", ident);
+            Ok(PoolModel::Tokenizer(Arc::new(Tokenizer::open(
+                &path,
+                ident.to_string(),
+            )?)))
+        })?;
+        match model {
+            PoolModel::Tokenizer(v) => Ok(v),
+            PoolModel::Translator(_) => Err(Error::new_option(format!("{} isnt a tokenizer", key))),
         }
     }
-}
 
-impl Default for TokenizerModels {
-    fn default() -> Self {
-        Self::new(ModelLifetime::KeepAlive)
+    /// Returns the model of the key or loads it while only the lock of the key is held
+    fn get_or_load(
+        &self,
+        key: &str,
+        size: impl FnOnce() -> u64,
+        translator: bool,
+        load: impl FnOnce() -> Result<PoolModel, Error>,
+    ) -> Result<PoolModel, Error> {
+        if let Some(v) = Self::touch(&mut *self.lock()?, key) {
+            return Ok(v.clone());
+        }
+        let slot = self
+            .loading
+            .lock()
+            .map_err(|_| Error::new_option("Model pool poisoned"))?
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        let _remove = LoadingSlot { pool: self, key };
+        let _loading = slot
+            .lock()
+            .map_err(|_| Error::new_option("Model pool poisoned"))?;
+        // another thread could have loaded the model while this one waited
+        if let Some(v) = Self::touch(&mut *self.lock()?, key) {
+            return Ok(v.clone());
+        }
+        let size = size();
+        let model = load()?;
+        let mut entries = self.lock()?;
+        self.evict(&mut entries, size, translator);
+        entries.insert(
+            key.to_string(),
+            PoolEntry {
+                model: model.clone(),
+                size,
+                last_used: Instant::now(),
+            },
+        );
+        Ok(model)
     }
-}
 
-impl Default for CTranslateModels {
-    fn default() -> Self {
-        Self::new(ModelLifetime::KeepAlive)
+    /// Unloads the models that were idle longer than the ttl
+    pub fn unload_idle(&self) -> Result<(), Error> {
+        let mut entries = self.lock()?;
+        self.evict(&mut entries, 0, false);
+        Ok(())
     }
-}
 
-impl TokenizerModels {
-    pub fn new(model_lifetime: ModelLifetime) -> Self {
-        Self {
-            mode: model_lifetime,
-            tokenizers: HashMap::new(),
-        }
+    /// Unloads all models. Models that are used by a running translation are dropped after it finished.
+    pub fn clear(&self) -> Result<(), Error> {
+        info!("Unload all models");
+        self.lock()?.clear();
+        Ok(())
     }
 
-    pub fn get_tokenizer(&mut self, ident: &str, path: PathBuf) -> Result<&Tokenizer, Error> {
-        let v = self.tokenizers.get(ident);
-        if v.is_none() {
-            info!("Loading tokenizer {}", ident);
-            let tokenizer = Tokenizer::new(&path, ident.to_string());
-            self.tokenizers.insert(ident.to_string(), tokenizer);
-            return self
-                .tokenizers
-                .get(ident)
-                .ok_or_else(|| Error::new_option("Tokenizer not found"));
+    /// Idents of the loaded models and tokenizers
+    pub fn loaded(&self) -> Result<Vec<String>, Error> {
+        Ok(self.lock()?.keys().cloned().collect())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<String, PoolEntry>>, Error> {
+        self.entries
+            .lock()
+            .map_err(|_| Error::new_option("Model pool poisoned"))
+    }
+
+    fn touch<'a>(entries: &'a mut HashMap<String, PoolEntry>, key: &str) -> Option<&'a PoolModel> {
+        let entry = entries.get_mut(key)?;
+        entry.last_used = Instant::now();
+        Some(&entry.model)
+    }
+
+    /// Unloads idle models and then the least recently used ones until the new model fits
+    fn evict(&self, entries: &mut HashMap<String, PoolEntry>, size: u64, translator: bool) {
+        if let Some(ttl) = self.config.idle_ttl {
+            entries.retain(|k, v| {
+                let keep = v.last_used.elapsed() < ttl;
+                if !keep {
+                    info!("Unload idle model {}", k);
+                }
+                keep
+            });
+        }
+        loop {
+            let models = entries
+                .values()
+                .filter(|v| matches!(v.model, PoolModel::Translator(_)))
+                .count();
+            let memory = entries.values().map(|v| v.size).sum::<u64>();
+            let over_models = translator
+                && self
+                    .config
+                    .max_models
+                    .filter(|max| models + 1 > *max)
+                    .is_some();
+            let over_memory = self
+                .config
+                .max_memory
+                .filter(|max| memory + size > *max)
+                .is_some();
+            if !over_models && !over_memory {
+                break;
+            }
+            let oldest = entries
+                .iter()
+                .filter(|(_, v)| !over_models || matches!(v.model, PoolModel::Translator(_)))
+                .min_by_key(|(_, v)| v.last_used)
+                .map(|(k, _)| k.clone());
+            match oldest {
+                Some(k) => {
+                    info!("Unload model {}", k);
+                    entries.remove(&k);
+                }
+                None => break,
+            }
         }
-        //FIXME: duplicate get due to borrow checker
-        self.tokenizers
-            .get(ident)
-            .ok_or_else(|| Error::new_option("Tokenizer not found"))
     }
+}
 
-    pub fn cleanup(&mut self) {
-        //TODO: remove only model that was created(multithreaded)
-        if self.mode == ModelLifetime::Dispose {
-            info!("Unload tokenizers");
-            self.tokenizers.clear();
+/// Removes the lock of a model that is being loaded when the load returns
+struct LoadingSlot<'a> {
+    pool: &'a ModelPool,
+    key: &'a str,
+}
+
+impl Drop for LoadingSlot<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut loading) = self.pool.loading.lock() {
+            loading.remove(self.key);
         }
     }
 }
+
+/// Size of the files in the directory
+fn dir_size(path: &Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|v| {
+            v.filter_map(|v| v.ok()?.metadata().ok())
+                .filter(|v| v.is_file())
+                .map(|v| v.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
@@ -1,38 +1,185 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use sentencepiece::SentencePieceProcessor;
+use serde::Deserialize;
 
 use crate::error::Error;
 
+/// Special tokens of a model, read from the special_tokens_map.json next to the tokenizer
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpecialTokens {
+    pub bos: Option<String>,
+    pub eos: Option<String>,
+    pub unk: Option<String>,
+    pub pad: Option<String>,
+    /// Language tokens and other added tokens
+    pub additional: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SpecialToken {
+    Text(String),
+    Added { content: String },
+}
+
+impl SpecialToken {
+    fn content(self) -> String {
+        match self {
+            SpecialToken::Text(v) => v,
+            SpecialToken::Added { content } => content,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SpecialTokensMap {
+    bos_token: Option<SpecialToken>,
+    eos_token: Option<SpecialToken>,
+    unk_token: Option<SpecialToken>,
+    pad_token: Option<SpecialToken>,
+    #[serde(default)]
+    additional_special_tokens: Vec<SpecialToken>,
+}
+
+impl SpecialTokens {
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::new(format!("Couldnt read {}", path.display()), e))?;
+        let map: SpecialTokensMap = serde_json::from_str(&text)
+            .map_err(|e| Error::new(format!("Invalid special tokens {}", path.display()), e))?;
+        Ok(Self {
+            bos: map.bos_token.map(SpecialToken::content),
+            eos: map.eos_token.map(SpecialToken::content),
+            unk: map.unk_token.map(SpecialToken::content),
+            pad: map.pad_token.map(SpecialToken::content),
+            additional: map
+                .additional_special_tokens
+                .into_iter()
+                .map(SpecialToken::content)
+                .collect(),
+        })
+    }
+
+    /// Tokens that are removed before detokenizing, unk is kept
+    pub fn is_special(&self, token: &str) -> bool {
+        [&self.bos, &self.eos, &self.pad]
+            .iter()
+            .any(|v| v.as_deref() == Some(token))
+            || self.additional.iter().any(|v| v == token)
+    }
+
+    /// End of sentence token, </s> if the model doesnt define one
+    pub fn eos(&self) -> &str {
+        self.eos.as_deref().unwrap_or("</s>")
+    }
+}
+
+enum Backend {
+    SentencePiece(SentencePieceProcessor),
+    #[cfg(feature = "hf-tokenizer")]
+    HuggingFace(Box<tokenizers::Tokenizer>),
+}
+
+/// SentencePiece model or huggingface tokenizer.json.
+/// Special tokens are never added by tokenize, the translators add the language tokens themselves.
 pub struct Tokenizer {
-    spp: SentencePieceProcessor,
+    backend: Backend,
+    pub special_tokens: SpecialTokens,
     pub ident: String,
 }
 
 impl Tokenizer {
-    pub fn new(path: &PathBuf, ident: String) -> Self {
-        let spp = SentencePieceProcessor::open(path).unwrap();
-        Self { spp, ident }
+    /// Loads a tokenizer.json with the huggingface tokenizer and every other file as SentencePiece model.
+    /// The special_tokens_map.json in the same directory is loaded if it exists.
+    pub fn open(path: &Path, ident: String) -> Result<Self, Error> {
+        let backend = match path.extension().and_then(|v| v.to_str()) {
+            #[cfg(feature = "hf-tokenizer")]
+            Some("json") => {
+                Backend::HuggingFace(Box::new(tokenizers::Tokenizer::from_file(path).map_err(
+                    |e| Error::new(format!("Couldnt load tokenizer {}", path.display()), e),
+                )?))
+            }
+            _ => Backend::SentencePiece(SentencePieceProcessor::open(path).map_err(|e| {
+                Error::new(format!("Couldnt load sentencepiece {}", path.display()), e)
+            })?),
+        };
+        let special_path = path.with_file_name("special_tokens_map.json");
+        let special_tokens = match special_path.is_file() {
+            true => SpecialTokens::from_file(&special_path)?,
+            false => SpecialTokens::default(),
+        };
+        Ok(Self {
+            backend,
+            special_tokens,
+            ident,
+        })
+    }
+
+    /// tokenizer.json in the directory if it exists and the hf-tokenizer feature is enabled,
+    /// otherwise the SentencePiece model
+    pub fn find(directory: &Path, sentencepiece: &str) -> PathBuf {
+        let json = directory.join("tokenizer.json");
+        match cfg!(feature = "hf-tokenizer") && json.is_file() {
+            true => json,
+            false => directory.join(sentencepiece),
+        }
     }
 
     pub fn tokenize(&self, text: &[String]) -> Result<Vec<Vec<String>>, Error> {
         text.iter()
-            .map(|v| {
-                self.spp
+            .map(|v| match &self.backend {
+                Backend::SentencePiece(spp) => spp
                     .encode(v)
                     .map(|v| v.iter().map(|v| v.piece.to_string()).collect::<Vec<_>>())
-                    .map_err(|e| Error::new("Sentencepiecerror", e))
+                    .map_err(|e| Error::new("Sentencepiecerror", e)),
+                #[cfg(feature = "hf-tokenizer")]
+                Backend::HuggingFace(tokenizer) => tokenizer
+                    .encode(v.as_str(), false)
+                    .map(|v| v.get_tokens().to_vec())
+                    .map_err(|e| Error::new("Tokenizer error", e)),
             })
             .collect()
     }
 
+    /// Tokenizes the text and adds the prefix and suffix tokens to every sentence
+    pub fn encode(
+        &self,
+        text: &[String],
+        prefix: &[String],
+        suffix: &[String],
+    ) -> Result<Vec<Vec<String>>, Error> {
+        Ok(self
+            .tokenize(text)?
+            .into_iter()
+            .map(|v| [prefix, &v, suffix].concat())
+            .collect())
+    }
+
+    /// Removes the special tokens and joins the pieces
     pub fn detokenize(&self, tokens: Vec<Vec<String>>) -> Result<Vec<String>, Error> {
         tokens
             .into_iter()
             .map(|v| {
-                self.spp
-                    .decode_pieces(&v)
-                    .map_err(|e| Error::new("Sentencepiecerror", e))
+                let v = v
+                    .into_iter()
+                    .filter(|t| !self.special_tokens.is_special(t))
+                    .collect::<Vec<_>>();
+                match &self.backend {
+                    Backend::SentencePiece(spp) => spp
+                        .decode_pieces(&v)
+                        .map_err(|e| Error::new("Sentencepiecerror", e)),
+                    #[cfg(feature = "hf-tokenizer")]
+                    Backend::HuggingFace(tokenizer) => {
+                        let ids = v
+                            .iter()
+                            .filter_map(|t| tokenizer.token_to_id(t))
+                            .collect::<Vec<_>>();
+                        tokenizer
+                            .decode(&ids, true)
+                            .map_err(|e| Error::new("Tokenizer error", e))
+                    }
+                }
             })
             .collect()
     }
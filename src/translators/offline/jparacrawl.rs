@@ -3,15 +3,14 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use model_manager::model_manager::ModelManager;
-use rustyctranslate2::BatchType;
 
 use crate::error::Error;
 use crate::languages::Language;
-use crate::translators::offline::ctranslate2::model_management::{
-    CTranslateModels, TokenizerModels,
-};
-use crate::translators::offline::ctranslate2::Device;
+use crate::model_register::{check_files, get_model, JPARACRAWL_FILES};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::offline::ctranslate2::{translate_batch, Device, DeviceConfig};
 use crate::translators::offline::ModelFormat;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -37,21 +36,20 @@ impl FromStr for JParaCrawlModelType {
 }
 
 pub struct JParaCrawlTranslator {
-    device: Device,
+    device: DeviceConfig,
     model_path: PathBuf,
     tokenizer_filenames: HashMap<Language, String>,
     ident: String,
-    model_format: ModelFormat,
 }
 
 impl TranslatorCTranslate for JParaCrawlTranslator {
     fn translate_vec(
         &self,
-        translator_models: &mut CTranslateModels,
-        tokenizer_models: &mut TokenizerModels,
+        pool: &ModelPool,
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        _options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let from = Self::get_from(from, to)?;
         let tokenizer_path = self.model_path.join(
@@ -60,12 +58,12 @@ impl TranslatorCTranslate for JParaCrawlTranslator {
                 .ok_or_else(|| Error::new_option("Tokenizer not found"))?,
         );
         let translator_path = Self::get_translator_model_path(&self.model_path, from, to)?;
-        let tokenizer = tokenizer_models.get_tokenizer(
-            &format!("jparacrawl-{}", from.to_jparacrawl_str()?),
+        let tokenizer = pool.get_tokenizer(
+            &format!("{}-{}", self.ident, from.to_jparacrawl_str()?),
             tokenizer_path,
         )?;
         let tokens = tokenizer.tokenize(query)?;
-        let translator = translator_models.get_translator(
+        let translator = pool.get_translator(
             &format!(
                 "{}-{}-{}",
                 self.ident,
@@ -74,38 +72,50 @@ impl TranslatorCTranslate for JParaCrawlTranslator {
             ),
             translator_path,
             &self.device,
-            self.model_format.is_compressed(),
         )?;
-        let translated = translator
-            .translate_batch(tokens, None, None, BatchType::Example)
-            .map_err(Error::new_option)?;
+        let translated = translate_batch(&translator, tokens, None)?;
         let sentences = tokenizer.detokenize(translated)?;
-        translator_models.cleanup();
-        tokenizer_models.cleanup();
         Ok(TranslationVecOutput {
             text: sentences,
             lang: from,
+            metadata: Default::default(),
         })
     }
 }
 
 impl JParaCrawlTranslator {
     pub fn new(
-        device: &Device,
+        device: &DeviceConfig,
         model_format: &ModelFormat,
         model_type: &JParaCrawlModelType,
         model_manager: &ModelManager,
     ) -> Result<Self, Error> {
-        let ident = Self::get_ident(device, model_format, model_type);
-        let model = model_manager
-            .get_model(&ident)
-            .map_err(|_| Error::new_option("couldnt get model".to_string()))?;
+        if let ModelFormat::Directory(directory) = model_format {
+            return Self::from_directory(directory.clone(), device);
+        }
+        let device = device.resolve().with_format(model_format);
+        let ident = Self::get_ident(device.device(), model_format, model_type);
+        let model_path = get_model(model_manager, &ident)?;
         Ok(Self {
-            device: *device,
-            model_path: model.0.join(&model.1.directory),
+            device,
+            model_path,
             tokenizer_filenames: Self::get_tokenizer_filenames(),
             ident,
-            model_format: *model_format,
+        })
+    }
+
+    /// Uses the model in the directory instead of the model manager, fails if a model file is missing
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        device: &DeviceConfig,
+    ) -> Result<Self, Error> {
+        let model_path = directory.into();
+        check_files(&model_path, JPARACRAWL_FILES)?;
+        Ok(Self {
+            device: device.resolve(),
+            ident: model_path.display().to_string(),
+            tokenizer_filenames: Self::get_tokenizer_filenames(),
+            model_path,
         })
     }
 
@@ -146,17 +156,14 @@ impl JParaCrawlTranslator {
     }
 
     fn get_ident(
-        device: &Device,
+        device: Device,
         model_format: &ModelFormat,
         model_type: &JParaCrawlModelType,
     ) -> String {
-        let ending = match model_format {
-            ModelFormat::Compact => match device {
-                Device::CPU => "-int8",
-                Device::CUDA => "-float16",
-            },
-            ModelFormat::Normal => "",
-        };
+        let ending = model_format
+            .quantization(device)
+            .map(|v| format!("-{}", v))
+            .unwrap_or_default();
         match model_type {
             JParaCrawlModelType::Small => format!("jparacrawl-small-ct2{}", ending),
             JParaCrawlModelType::Base => format!("jparacrawl-base-ct2{}", ending),
@@ -1,13 +1,14 @@
 use crate::error::Error;
 use crate::languages::Language;
-use crate::translators::offline::ctranslate2::model_management::{
-    CTranslateModels, TokenizerModels,
+use crate::model_register::{check_files, get_model, M2M100_FILES};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::offline::ctranslate2::{
+    strip_prefix, translate_batch, Device, DeviceConfig,
 };
-use crate::translators::offline::ctranslate2::Device;
 use crate::translators::offline::ModelFormat;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
 use model_manager::model_manager::ModelManager;
-use rustyctranslate2::BatchType;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -32,80 +33,88 @@ impl FromStr for M2M100ModelType {
 }
 
 pub struct M2M100Translator {
-    device: Device,
+    device: DeviceConfig,
     base_path: PathBuf,
     ident: String,
-    model_format: ModelFormat,
 }
 
 impl TranslatorCTranslate for M2M100Translator {
     fn translate_vec(
         &self,
-        translator_models: &mut CTranslateModels,
-        tokenizer_model: &mut TokenizerModels,
+        pool: &ModelPool,
         query: &[String],
-        _from: Option<Language>,
+        from: Option<Language>,
         to: &Language,
+        _options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let model_path = self.base_path.join("spm.128k.model");
-        let tokenizer = tokenizer_model.get_tokenizer("m2m100", model_path)?;
-        let tokens = tokenizer.tokenize(query)?;
+        let tokenizer = pool.get_tokenizer(&format!("{}-tokenizer", self.ident), model_path)?;
+        let prefix = match from {
+            Some(from) => vec![Self::language_token(&from.to_m2m100_str()?)],
+            None => vec![],
+        };
+        let tokens = tokenizer.encode(query, &prefix, &["</s>".to_string()])?;
         let lang_str = to.to_m2m100_str()?;
         let target = Self::generate_target_prefix(&lang_str, query.len());
-        let translator = translator_models.get_translator(
-            &self.ident,
-            self.base_path.clone(),
-            &self.device,
-            self.model_format.is_compressed(),
-        )?;
-        let translated = translator
-            .translate_batch_target(tokens, None, BatchType::Example, None, target)
-            .map_err(Error::new_option)?;
+        let translator = pool.get_translator(&self.ident, self.base_path.clone(), &self.device)?;
+        let mut translated = translate_batch(&translator, tokens, Some(target))?;
+        strip_prefix(&mut translated, &Self::language_token(&lang_str));
         let sentences = tokenizer
             .detokenize(translated)?
-            .iter()
-            .map(|x| x[lang_str.len() + 5..].to_string())
-            .collect::<Vec<_>>();
-        tokenizer_model.cleanup();
-        translator_models.cleanup();
+            .into_iter()
+            .map(|x| x.trim().to_string())
+            .collect();
         Ok(TranslationVecOutput {
             text: sentences,
             lang: Language::Unknown,
+            metadata: Default::default(),
         })
     }
 }
 
 impl M2M100Translator {
     pub fn new(
-        device: &Device,
+        device: &DeviceConfig,
         model_format: &ModelFormat,
         model_type: &M2M100ModelType,
         model_manager: &ModelManager,
     ) -> Result<Self, Error> {
-        let ident = Self::get_model_name(device, model_format, model_type);
-        let model = model_manager
-            .get_model(&ident)
-            .map_err(|_| Error::new_option("couldnt get model".to_string()))?;
+        if let ModelFormat::Directory(directory) = model_format {
+            return Self::from_directory(directory.clone(), device);
+        }
+        let device = device.resolve().with_format(model_format);
+        let ident = Self::get_model_name(device.device(), model_format, model_type);
+        let base_path = get_model(model_manager, &ident)?;
         Ok(Self {
-            base_path: model.0.join(&model.1.directory),
-            device: *device,
+            base_path,
+            device,
             ident,
-            model_format: ModelFormat::Normal,
+        })
+    }
+
+    /// Uses the model in the directory instead of the model manager, fails if a model file is missing
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        device: &DeviceConfig,
+    ) -> Result<Self, Error> {
+        let base_path = directory.into();
+        check_files(&base_path, M2M100_FILES)?;
+        Ok(Self {
+            device: device.resolve(),
+            ident: base_path.display().to_string(),
+            base_path,
         })
     }
 
     fn get_model_name(
-        device: &Device,
+        device: Device,
         model_format: &ModelFormat,
         model_type: &M2M100ModelType,
     ) -> String {
-        let extra = match model_format {
-            ModelFormat::Compact => match device {
-                Device::CPU => "_int8",
-                Device::CUDA => "_float16",
-            },
-            ModelFormat::Normal => "",
-        };
+        let extra = model_format
+            .quantization(device)
+            .map(|v| format!("_{}", v))
+            .unwrap_or_default();
         format!(
             "m2m_100_{}_ct2{}",
             match model_type {
@@ -119,8 +128,12 @@ impl M2M100Translator {
     fn generate_target_prefix(lang: &str, ammount: usize) -> Vec<String> {
         let mut target_prefix = Vec::new();
         for _ in 0..ammount {
-            target_prefix.push(format!("__{}__", lang));
+            target_prefix.push(Self::language_token(lang));
         }
         target_prefix
     }
+
+    fn language_token(lang: &str) -> String {
+        format!("__{}__", lang)
+    }
 }
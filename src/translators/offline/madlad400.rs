@@ -0,0 +1,120 @@
+use crate::error::Error;
+use crate::languages::Language;
+use crate::model_register::{check_files, get_model, MADLAD400_FILES};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::offline::ctranslate2::{translate_batch, Device, DeviceConfig};
+use crate::translators::offline::ModelFormat;
+use crate::translators::options::TranslationOptions;
+use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
+use model_manager::model_manager::ModelManager;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum Madlad400ModelType {
+    #[default]
+    Mt3B,
+    Mt7B,
+    Mt10B,
+}
+
+impl FromStr for Madlad400ModelType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Ok(Self::Mt3B),
+            "Mt3B" => Ok(Self::Mt3B),
+            "Mt7B" => Ok(Self::Mt7B),
+            "Mt10B" => Ok(Self::Mt10B),
+            _ => Err(()),
+        }
+    }
+}
+
+/// MADLAD-400, the target language is selected with a <2xx> token in front of the source
+pub struct Madlad400Translator {
+    device: DeviceConfig,
+    base_path: PathBuf,
+    ident: String,
+}
+
+impl TranslatorCTranslate for Madlad400Translator {
+    fn translate_vec(
+        &self,
+        pool: &ModelPool,
+        query: &[String],
+        _from: Option<Language>,
+        to: &Language,
+        _options: &TranslationOptions,
+    ) -> Result<TranslationVecOutput, Error> {
+        let to_str = to.to_madlad400_str()?;
+        let tokenizer = pool.get_tokenizer(
+            &format!("{}-spiece", self.ident),
+            self.base_path.join("spiece.model"),
+        )?;
+        let tokens = tokenizer.encode(query, &[to_str], &["</s>".to_string()])?;
+        let translator = pool.get_translator(&self.ident, self.base_path.clone(), &self.device)?;
+        let translated = translate_batch(&translator, tokens, None)?;
+        let sentences = tokenizer.detokenize(translated)?;
+        Ok(TranslationVecOutput {
+            text: sentences,
+            lang: Language::Unknown,
+            metadata: Default::default(),
+        })
+    }
+}
+
+impl Madlad400Translator {
+    pub fn new(
+        device: &DeviceConfig,
+        model_format: &ModelFormat,
+        model_type: &Madlad400ModelType,
+        model_manager: &ModelManager,
+    ) -> Result<Self, Error> {
+        if let ModelFormat::Directory(directory) = model_format {
+            return Self::from_directory(directory.clone(), device);
+        }
+        let device = device.resolve().with_format(model_format);
+        let ident = Self::get_ident(device.device(), model_format, model_type);
+        let base_path = get_model(model_manager, &ident)?;
+        Ok(Self {
+            device,
+            base_path,
+            ident,
+        })
+    }
+
+    /// Uses the model in the directory instead of the model manager, fails if a model file is missing
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        device: &DeviceConfig,
+    ) -> Result<Self, Error> {
+        let base_path = directory.into();
+        check_files(&base_path, MADLAD400_FILES)?;
+        Ok(Self {
+            device: device.resolve(),
+            ident: base_path.display().to_string(),
+            base_path,
+        })
+    }
+
+    fn get_ident(
+        device: Device,
+        model_format: &ModelFormat,
+        model_type: &Madlad400ModelType,
+    ) -> String {
+        format!(
+            "madlad400-{}-mt-ct2{}",
+            match model_type {
+                Madlad400ModelType::Mt3B => "3b",
+                Madlad400ModelType::Mt7B => "7b",
+                Madlad400ModelType::Mt10B => "10b",
+            },
+            model_format
+                .quantization(device)
+                .map(|v| format!("-{}", v))
+                .unwrap_or_default()
+        )
+    }
+}
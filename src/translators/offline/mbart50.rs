@@ -0,0 +1,93 @@
+use crate::error::Error;
+use crate::languages::Language;
+use crate::model_register::{check_files, get_model, MBART50_FILES};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::offline::ctranslate2::{
+    strip_prefix, translate_batch, Device, DeviceConfig,
+};
+use crate::translators::offline::ModelFormat;
+use crate::translators::options::TranslationOptions;
+use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
+use model_manager::model_manager::ModelManager;
+use std::path::PathBuf;
+
+/// mBART-50 many-to-many, the languages are selected with xx_XX tokens
+pub struct MBart50Translator {
+    device: DeviceConfig,
+    base_path: PathBuf,
+    ident: String,
+}
+
+impl TranslatorCTranslate for MBart50Translator {
+    fn translate_vec(
+        &self,
+        pool: &ModelPool,
+        query: &[String],
+        from: Option<Language>,
+        to: &Language,
+        _options: &TranslationOptions,
+    ) -> Result<TranslationVecOutput, Error> {
+        let from = from.ok_or_else(|| Error::new_option("mBART-50 needs the source language"))?;
+        let from_str = from.to_mbart50_str()?;
+        let to_str = to.to_mbart50_str()?;
+        let tokenizer = pool.get_tokenizer(
+            &format!("{}-sentencepiece", self.ident),
+            self.base_path.join("sentencepiece.bpe.model"),
+        )?;
+        let tokens = tokenizer.encode(query, &[from_str], &["</s>".to_string()])?;
+        let target = vec![to_str.clone(); query.len()];
+        let translator = pool.get_translator(&self.ident, self.base_path.clone(), &self.device)?;
+        let mut translated = translate_batch(&translator, tokens, Some(target))?;
+        strip_prefix(&mut translated, &to_str);
+        let sentences = tokenizer.detokenize(translated)?;
+        Ok(TranslationVecOutput {
+            text: sentences,
+            lang: from,
+            metadata: Default::default(),
+        })
+    }
+}
+
+impl MBart50Translator {
+    pub fn new(
+        device: &DeviceConfig,
+        model_format: &ModelFormat,
+        model_manager: &ModelManager,
+    ) -> Result<Self, Error> {
+        if let ModelFormat::Directory(directory) = model_format {
+            return Self::from_directory(directory.clone(), device);
+        }
+        let device = device.resolve().with_format(model_format);
+        let ident = Self::get_ident(device.device(), model_format);
+        let base_path = get_model(model_manager, &ident)?;
+        Ok(Self {
+            device,
+            base_path,
+            ident,
+        })
+    }
+
+    /// Uses the model in the directory instead of the model manager, fails if a model file is missing
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        device: &DeviceConfig,
+    ) -> Result<Self, Error> {
+        let base_path = directory.into();
+        check_files(&base_path, MBART50_FILES)?;
+        Ok(Self {
+            device: device.resolve(),
+            ident: base_path.display().to_string(),
+            base_path,
+        })
+    }
+
+    fn get_ident(device: Device, model_format: &ModelFormat) -> String {
+        format!(
+            "mbart-large-50-many-to-many-mmt-ct2{}",
+            model_format
+                .quantization(device)
+                .map(|v| format!("-{}", v))
+                .unwrap_or_default()
+        )
+    }
+}
@@ -1,27 +1,47 @@
 #[cfg(feature = "ctranslate_req")]
+use crate::translators::offline::ctranslate2::Device;
+#[cfg(feature = "ctranslate_req")]
+use std::path::PathBuf;
+
+#[cfg(feature = "argos")]
+pub mod argos;
+#[cfg(feature = "ctranslate_req")]
 pub mod ctranslate2;
 #[cfg(feature = "jparacrawl")]
 pub mod jparacrawl;
 #[cfg(feature = "m2m100")]
 pub mod m2m100;
+#[cfg(feature = "madlad400")]
+pub mod madlad400;
+#[cfg(feature = "mbart50")]
+pub mod mbart50;
 #[cfg(feature = "nllb")]
 pub mod nllb;
+#[cfg(feature = "opus-mt")]
+pub mod opus_mt;
 #[cfg(feature = "sugoi")]
 pub mod sugoi;
 
-#[derive(PartialEq, Eq, Copy, Clone, Default, Debug)]
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
 #[cfg(feature = "ctranslate_req")]
 pub enum ModelFormat {
     #[default]
     Compact,
     Normal,
+    /// Model files in the directory, the model manager isnt used.
+    /// The files are checked when the translator is created, the error lists the missing files.
+    Directory(PathBuf),
 }
 #[cfg(feature = "ctranslate_req")]
 impl ModelFormat {
-    fn is_compressed(&self) -> bool {
-        if self == &ModelFormat::Compact {
-            return true;
+    /// Quantization of the downloaded files, the compute type is set in DeviceConfig
+    fn quantization(&self, device: Device) -> Option<&'static str> {
+        match self {
+            ModelFormat::Compact => Some(match device {
+                Device::CPU => "int8",
+                Device::CUDA => "float16",
+            }),
+            ModelFormat::Normal | ModelFormat::Directory(_) => None,
         }
-        false
     }
 }
@@ -1,14 +1,15 @@
 use crate::error::Error;
 use crate::languages::Language;
-use crate::translators::offline::ctranslate2::model_management::{
-    CTranslateModels, TokenizerModels,
-};
+use crate::model_register::{check_files, get_model, NLLB_FILES};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
 use crate::translators::offline::ctranslate2::tokenizer::Tokenizer;
-use crate::translators::offline::ctranslate2::Device;
+use crate::translators::offline::ctranslate2::{
+    strip_prefix, translate_batch, Device, DeviceConfig,
+};
 use crate::translators::offline::ModelFormat;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
 use model_manager::model_manager::ModelManager;
-use rustyctranslate2::BatchType;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -37,101 +38,95 @@ impl FromStr for NllbModelType {
 }
 
 pub struct NllbTranslator {
-    device: Device,
+    device: DeviceConfig,
     base_path: PathBuf,
     ident: String,
-    model_format: ModelFormat,
 }
 
 impl TranslatorCTranslate for NllbTranslator {
     fn translate_vec(
         &self,
-        translator_models: &mut CTranslateModels,
-        tokenizer_models: &mut TokenizerModels,
+        pool: &ModelPool,
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        _options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
-        let model_path = self.base_path.join("sentencepiece.bpe.model");
-        let tokenizer = tokenizer_models.get_tokenizer("m2m100", model_path)?;
-        let tokens = Self::tokenize(from, query, tokenizer)?;
+        let model_path = Tokenizer::find(&self.base_path, "sentencepiece.bpe.model");
+        let tokenizer = pool.get_tokenizer(&format!("{}-tokenizer", self.ident), model_path)?;
+        let tokens = Self::tokenize(from, query, &tokenizer)?;
         let lang_str = to.to_nllb_str()?;
         let target = Self::generate_target_prefix(&lang_str, query.len());
-        let translator = translator_models.get_translator(
-            &self.ident,
-            self.base_path.clone(),
-            &self.device,
-            self.model_format.is_compressed(),
-        )?;
-        let translated = translator
-            .translate_batch_target(tokens, None, BatchType::Example, None, target)
-            .map_err(Error::new_option)?;
-        let to = to.to_nllb_str()?;
+        let translator = pool.get_translator(&self.ident, self.base_path.clone(), &self.device)?;
+        let mut translated = translate_batch(&translator, tokens, Some(target))?;
+        strip_prefix(&mut translated, &lang_str);
         let sentences = tokenizer
             .detokenize(translated)?
-            .iter()
-            .map(|x| x[to.len() + 1..].to_string())
-            .collect::<Vec<_>>();
-        tokenizer_models.cleanup();
-        translator_models.cleanup();
+            .into_iter()
+            .map(|x| x.trim().to_string())
+            .collect();
         Ok(TranslationVecOutput {
             text: sentences,
             lang: Language::Unknown,
+            metadata: Default::default(),
         })
     }
 }
 
 impl NllbTranslator {
     pub fn new(
-        device: &Device,
+        device: &DeviceConfig,
         model_format: &ModelFormat,
         model_type: &NllbModelType,
         model_manager: &ModelManager,
     ) -> Result<Self, Error> {
-        let ident = Self::get_ident(device, model_format, model_type);
-        let model = model_manager
-            .get_model(&ident)
-            .map_err(|_| Error::new_option("couldnt get model".to_string()))?;
-        let device = *device;
+        if let ModelFormat::Directory(directory) = model_format {
+            return Self::from_directory(directory.clone(), device);
+        }
+        let device = device.resolve().with_format(model_format);
+        let ident = Self::get_ident(device.device(), model_format, model_type);
+        let base_path = get_model(model_manager, &ident)?;
         Ok(Self {
             device,
-            base_path: model.0.join(&model.1.directory),
+            base_path,
             ident,
-            model_format: *model_format,
         })
     }
 
+    /// Uses the model in the directory instead of the model manager, fails if a model file is missing
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        device: &DeviceConfig,
+    ) -> Result<Self, Error> {
+        let base_path = directory.into();
+        check_files(&base_path, NLLB_FILES)?;
+        Ok(Self {
+            device: device.resolve(),
+            ident: base_path.display().to_string(),
+            base_path,
+        })
+    }
+
+    /// Source tokens with the source language token in front, if the language is known
     pub fn tokenize(
         from: Option<Language>,
         query: &[String],
         tokenizer: &Tokenizer,
     ) -> Result<Vec<Vec<String>>, Error> {
         let source_sentences: Vec<String> = query.iter().map(|s| s.trim().to_string()).collect();
-        let tokenized = tokenizer.tokenize(&source_sentences)?;
-        let from = from.map(|s| s.to_nllb_str()).unwrap_or(Ok(String::new()))?;
-        Ok(tokenized
-            .into_iter()
-            .map(|s| {
-                let mut s = s;
-                s.insert(0, from.clone());
-                s.push("</s>".to_string());
-                s
-            })
-            .collect())
+        let prefix = match from {
+            Some(from) => vec![from.to_nllb_str()?],
+            None => vec![],
+        };
+        let suffix = [tokenizer.special_tokens.eos().to_string()];
+        tokenizer.encode(&source_sentences, &prefix, &suffix)
     }
 
-    fn get_ident(
-        device: &Device,
-        model_format: &ModelFormat,
-        model_type: &NllbModelType,
-    ) -> String {
-        let extra = match model_format {
-            ModelFormat::Compact => match device {
-                Device::CPU => "-int8",
-                Device::CUDA => "-float16",
-            },
-            ModelFormat::Normal => "",
-        };
+    fn get_ident(device: Device, model_format: &ModelFormat, model_type: &NllbModelType) -> String {
+        let extra = model_format
+            .quantization(device)
+            .map(|v| format!("-{}", v))
+            .unwrap_or_default();
         format!(
             "nllb-{}-ct2{}",
             match model_type {
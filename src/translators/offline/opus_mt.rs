@@ -0,0 +1,265 @@
+use crate::error::Error;
+use crate::languages::Language;
+use crate::model_register::{check_files, get_model, OPUS_MT_FILES};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::offline::ctranslate2::{translate_batch, Device, DeviceConfig};
+use crate::translators::offline::ModelFormat;
+use crate::translators::options::TranslationOptions;
+use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
+use model_manager::model_manager::ModelManager;
+use std::path::PathBuf;
+
+/// A converted Helsinki-NLP opus-mt model
+#[derive(Debug, PartialEq, Eq)]
+pub struct OpusMtModel {
+    /// Name of the model, opus-mt-{name}
+    pub name: &'static str,
+    /// ISO 639-1 codes of the source languages
+    pub sources: &'static [&'static str],
+    /// ISO 639-1 codes of the target languages, models with several targets need a >>xx<< token
+    pub targets: &'static [&'static str],
+}
+
+const ROMANCE: &[&str] = &["fr", "es", "it", "pt", "ro", "ca", "gl"];
+
+/// Supported models, the first model that supports a pair is used
+pub const OPUS_MT_MODELS: &[OpusMtModel] = &[
+    OpusMtModel {
+        name: "en-de",
+        sources: &["en"],
+        targets: &["de"],
+    },
+    OpusMtModel {
+        name: "de-en",
+        sources: &["de"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "en-fr",
+        sources: &["en"],
+        targets: &["fr"],
+    },
+    OpusMtModel {
+        name: "fr-en",
+        sources: &["fr"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "en-es",
+        sources: &["en"],
+        targets: &["es"],
+    },
+    OpusMtModel {
+        name: "es-en",
+        sources: &["es"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "en-it",
+        sources: &["en"],
+        targets: &["it"],
+    },
+    OpusMtModel {
+        name: "it-en",
+        sources: &["it"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "en-nl",
+        sources: &["en"],
+        targets: &["nl"],
+    },
+    OpusMtModel {
+        name: "nl-en",
+        sources: &["nl"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "en-ru",
+        sources: &["en"],
+        targets: &["ru"],
+    },
+    OpusMtModel {
+        name: "ru-en",
+        sources: &["ru"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "en-zh",
+        sources: &["en"],
+        targets: &["zh"],
+    },
+    OpusMtModel {
+        name: "zh-en",
+        sources: &["zh"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "ja-en",
+        sources: &["ja"],
+        targets: &["en"],
+    },
+    OpusMtModel {
+        name: "en-ROMANCE",
+        sources: &["en"],
+        targets: ROMANCE,
+    },
+    OpusMtModel {
+        name: "ROMANCE-en",
+        sources: ROMANCE,
+        targets: &["en"],
+    },
+];
+
+impl OpusMtModel {
+    /// Returns the model that translates from the source to the target language
+    pub fn find(from: Language, to: Language) -> Result<&'static Self, Error> {
+        let (from_str, to_str) = (from.to_6391_str()?, to.to_6391_str()?);
+        OPUS_MT_MODELS
+            .iter()
+            .find(|v| {
+                v.sources.contains(&from_str.as_str()) && v.targets.contains(&to_str.as_str())
+            })
+            .ok_or_else(|| {
+                Error::new_option(format!("No opus-mt model for {:?} to {:?}", from, to))
+            })
+    }
+
+    pub fn is_multi_target(&self) -> bool {
+        self.targets.len() > 1
+    }
+
+    /// All language pairs of the models
+    pub fn supported_pairs() -> Vec<(Language, Language)> {
+        let mut pairs = vec![];
+        for model in OPUS_MT_MODELS {
+            for from in model.sources {
+                for to in model.targets {
+                    if let (Ok(from), Ok(to)) = (from.parse(), to.parse()) {
+                        if !pairs.contains(&(from, to)) {
+                            pairs.push((from, to));
+                        }
+                    }
+                }
+            }
+        }
+        pairs
+    }
+
+    /// All languages that are a source or a target of a model
+    pub fn supported_languages() -> Vec<Language> {
+        let mut languages = vec![];
+        for (from, to) in Self::supported_pairs() {
+            for lang in [from, to] {
+                if !languages.contains(&lang) {
+                    languages.push(lang);
+                }
+            }
+        }
+        languages
+    }
+}
+
+pub struct OpusMtTranslator {
+    device: DeviceConfig,
+    base_path: PathBuf,
+    ident: String,
+    model: &'static OpusMtModel,
+    from: Language,
+    to: Language,
+}
+
+impl TranslatorCTranslate for OpusMtTranslator {
+    fn translate_vec(
+        &self,
+        pool: &ModelPool,
+        query: &[String],
+        from: Option<Language>,
+        to: &Language,
+        _options: &TranslationOptions,
+    ) -> Result<TranslationVecOutput, Error> {
+        if from.filter(|v| v != &self.from).is_some() || to != &self.to {
+            return Err(Error::new_option(format!(
+                "The opus-mt model translates {:?} to {:?}",
+                self.from, self.to
+            )));
+        }
+        let source_tokenizer = pool.get_tokenizer(
+            &format!("{}-source", self.ident),
+            self.base_path.join("source.spm"),
+        )?;
+        let target_token = match self.model.is_multi_target() {
+            true => vec![format!(">>{}<<", to.to_6391_str()?)],
+            false => vec![],
+        };
+        let tokens = source_tokenizer.encode(query, &target_token, &["</s>".to_string()])?;
+        let translator = pool.get_translator(&self.ident, self.base_path.clone(), &self.device)?;
+        let translated = translate_batch(&translator, tokens, None)?;
+        let target_tokenizer = pool.get_tokenizer(
+            &format!("{}-target", self.ident),
+            self.base_path.join("target.spm"),
+        )?;
+        let sentences = target_tokenizer.detokenize(translated)?;
+        Ok(TranslationVecOutput {
+            text: sentences,
+            lang: self.from,
+            metadata: Default::default(),
+        })
+    }
+}
+
+impl OpusMtTranslator {
+    pub fn new(
+        device: &DeviceConfig,
+        model_format: &ModelFormat,
+        from: &Language,
+        to: &Language,
+        model_manager: &ModelManager,
+    ) -> Result<Self, Error> {
+        if let ModelFormat::Directory(directory) = model_format {
+            return Self::from_directory(directory.clone(), device, from, to);
+        }
+        let device = device.resolve().with_format(model_format);
+        let model = OpusMtModel::find(*from, *to)?;
+        let ident = Self::get_ident(device.device(), model_format, model);
+        let base_path = get_model(model_manager, &ident)?;
+        Ok(Self {
+            device,
+            base_path,
+            ident,
+            model,
+            from: *from,
+            to: *to,
+        })
+    }
+
+    /// Uses the model in the directory instead of the model manager, fails if a model file is missing
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        device: &DeviceConfig,
+        from: &Language,
+        to: &Language,
+    ) -> Result<Self, Error> {
+        let base_path = directory.into();
+        check_files(&base_path, OPUS_MT_FILES)?;
+        Ok(Self {
+            device: device.resolve(),
+            ident: base_path.display().to_string(),
+            base_path,
+            model: OpusMtModel::find(*from, *to)?,
+            from: *from,
+            to: *to,
+        })
+    }
+
+    fn get_ident(device: Device, model_format: &ModelFormat, model: &OpusMtModel) -> String {
+        format!(
+            "opus-mt-{}-ct2{}",
+            model.name,
+            model_format
+                .quantization(device)
+                .map(|v| format!("-{}", v))
+                .unwrap_or_default()
+        )
+    }
+}
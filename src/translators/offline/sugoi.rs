@@ -1,70 +1,76 @@
 use crate::error::Error;
 use crate::languages::Language;
-use crate::translators::offline::ctranslate2::model_management::{
-    CTranslateModels, TokenizerModels,
-};
-use crate::translators::offline::ctranslate2::Device;
+use crate::model_register::{check_files, get_model, SUGOI_FILES};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::offline::ctranslate2::{translate_batch, Device, DeviceConfig};
 use crate::translators::offline::ModelFormat;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{TranslationVecOutput, TranslatorCTranslate};
 use model_manager::model_manager::ModelManager;
-use rustyctranslate2::BatchType;
 use std::path::PathBuf;
 
 pub struct SugoiTranslator {
-    device: Device,
+    device: DeviceConfig,
     base_path: PathBuf,
-    model_format: ModelFormat,
     ident: String,
 }
 
 impl TranslatorCTranslate for SugoiTranslator {
     fn translate_vec(
         &self,
-        translator_models: &mut CTranslateModels,
-        tokenizer_model: &mut TokenizerModels,
+        pool: &ModelPool,
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        _options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let from = Self::get_from(from, to)?;
         let model_path = self.base_path.join("spm.ja.nopretok.model");
-        let tokenizer = tokenizer_model.get_tokenizer("sugoi", model_path)?;
+        let tokenizer = pool.get_tokenizer(&format!("{}-tokenizer", self.ident), model_path)?;
         let (query, query_split_sizes) = Self::pre_tokenize(query);
         let tokens = tokenizer.tokenize(&query)?;
-        let translator = translator_models.get_translator(
-            &self.ident,
-            self.base_path.clone(),
-            &self.device,
-            self.model_format.is_compressed(),
-        )?;
-        let translated = translator
-            .translate_batch(tokens, None, None, BatchType::Example)
-            .map_err(Error::new_option)?;
+        let translator = pool.get_translator(&self.ident, self.base_path.clone(), &self.device)?;
+        let translated = translate_batch(&translator, tokens, None)?;
         let sentences = tokenizer.detokenize(translated)?;
         let sentences = Self::post_detokenize(sentences, query_split_sizes);
-        tokenizer_model.cleanup();
         Ok(TranslationVecOutput {
             text: sentences,
             lang: from,
+            metadata: Default::default(),
         })
     }
 }
 
 impl SugoiTranslator {
     pub fn new(
-        device: &Device,
+        device: &DeviceConfig,
         model_format: &ModelFormat,
         mm: &ModelManager,
     ) -> Result<Self, Error> {
-        let ident = Self::get_model_name(device, model_format);
-        let model = mm
-            .get_model(&ident)
-            .map_err(|_| Error::new_option("couldnt get model".to_string()))?;
+        if let ModelFormat::Directory(directory) = model_format {
+            return Self::from_directory(directory.clone(), device);
+        }
+        let device = device.resolve().with_format(model_format);
+        let ident = Self::get_model_name(device.device(), model_format);
+        let base_path = get_model(mm, &ident)?;
         Ok(Self {
             ident,
-            device: *device,
-            base_path: model.0.join(&model.1.directory),
-            model_format: *model_format,
+            device,
+            base_path,
+        })
+    }
+
+    /// Uses the model in the directory instead of the model manager, fails if a model file is missing
+    pub fn from_directory(
+        directory: impl Into<PathBuf>,
+        device: &DeviceConfig,
+    ) -> Result<Self, Error> {
+        let base_path = directory.into();
+        check_files(&base_path, SUGOI_FILES)?;
+        Ok(Self {
+            device: device.resolve(),
+            ident: base_path.display().to_string(),
+            base_path,
         })
     }
 
@@ -115,16 +121,13 @@ impl SugoiTranslator {
         new_translations
     }
 
-    fn get_model_name(device: &Device, model_format: &ModelFormat) -> String {
+    fn get_model_name(device: Device, model_format: &ModelFormat) -> String {
         format!(
             "sugoi-ja-en-ct2{}",
-            match model_format {
-                ModelFormat::Compact => match device {
-                    Device::CPU => "-int8",
-                    Device::CUDA => "-float16",
-                },
-                ModelFormat::Normal => "",
-            }
+            model_format
+                .quantization(device)
+                .map(|v| format!("-{}", v))
+                .unwrap_or_default()
         )
     }
 }
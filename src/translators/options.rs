@@ -0,0 +1,96 @@
+use crate::translators::context::Formality;
+
+/// Provider specific settings for a single request.
+/// Translators only use the options they support, the other options are reported as ignored.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct TranslationOptions {
+    /// Deepl: formality
+    pub formality: Option<Formality>,
+    /// Deepl: split_sentences
+    pub split_sentences: Option<SplitSentences>,
+    /// Deepl: preserve_formatting
+    pub preserve_formatting: Option<bool>,
+    /// Format of the text.
+    /// Deepl: tag_handling, Libretranslate: format, Google api: format
+    pub format: Option<TextFormat>,
+    /// CTranslate2: beam_size.
+    /// Ignored by the offline translators, the pinned rustyctranslate2 decodes with the CTranslate2 defaults.
+    pub beam_size: Option<usize>,
+    /// CTranslate2: max_decoding_length, ignored like beam_size
+    pub max_decoding_length: Option<usize>,
+}
+
+/// Identifier of a single option in TranslationOptions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranslationOption {
+    Formality,
+    SplitSentences,
+    PreserveFormatting,
+    Format,
+    BeamSize,
+    MaxDecodingLength,
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitSentences {
+    /// No splitting, the input is one sentence
+    None,
+    /// Split on punctuation and newlines
+    #[default]
+    All,
+    /// Split on punctuation only
+    NoNewlines,
+}
+
+impl SplitSentences {
+    /// Value of the deepl split_sentences parameter
+    pub fn to_deepl_str(&self) -> &'static str {
+        match self {
+            SplitSentences::None => "0",
+            SplitSentences::All => "1",
+            SplitSentences::NoNewlines => "nonewlines",
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextFormat {
+    #[default]
+    Text,
+    Html,
+    Xml,
+}
+
+impl TranslationOptions {
+    /// Returns all options that are set
+    pub fn used(&self) -> Vec<TranslationOption> {
+        let mut used = vec![];
+        if self.formality.is_some() {
+            used.push(TranslationOption::Formality);
+        }
+        if self.split_sentences.is_some() {
+            used.push(TranslationOption::SplitSentences);
+        }
+        if self.preserve_formatting.is_some() {
+            used.push(TranslationOption::PreserveFormatting);
+        }
+        if self.format.is_some() {
+            used.push(TranslationOption::Format);
+        }
+        if self.beam_size.is_some() {
+            used.push(TranslationOption::BeamSize);
+        }
+        if self.max_decoding_length.is_some() {
+            used.push(TranslationOption::MaxDecodingLength);
+        }
+        used
+    }
+
+    /// Returns all options that are set, but not in the supported list
+    pub fn ignored(&self, supported: &[TranslationOption]) -> Vec<TranslationOption> {
+        self.used()
+            .into_iter()
+            .filter(|v| !supported.contains(v))
+            .collect()
+    }
+}
@@ -0,0 +1,314 @@
+use crate::formats::subtitles::is_cjk;
+use crate::languages::Language;
+use crate::translators::transliteration::transliterate;
+
+/// Stages that change the result of a translator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostProcessing {
+    /// Quotes, spacing, punctuation and ellipsis of the target language
+    Typography,
+    /// Romanization of the target language, like romaji for japanese
+    Transliteration,
+}
+
+impl PostProcessing {
+    pub fn apply(&self, text: &str, to: Language) -> String {
+        match self {
+            PostProcessing::Typography => typography(text, to),
+            PostProcessing::Transliteration => transliterate(text, to),
+        }
+    }
+}
+
+/// Applies all stages in order.
+/// The tags of markup text are hidden from the stages, so quotes in attributes stay ascii.
+pub(crate) fn post_process(
+    stages: &[PostProcessing],
+    text: String,
+    to: Language,
+    markup: bool,
+) -> String {
+    if stages.is_empty() {
+        return text;
+    }
+    let (text, tags) = match markup {
+        true => mask_tags(&text),
+        false => (text, vec![]),
+    };
+    let text = stages
+        .iter()
+        .fold(text, |text, stage| stage.apply(&text, to));
+    unmask_tags(&text, &tags)
+}
+
+/// First private use character, every tag is replaced by one of them
+const TAG_MASK: u32 = 0xE000;
+
+/// Replaces every tag like <b class="a"> with a private use character
+fn mask_tags(text: &str) -> (String, Vec<String>) {
+    let mut tags = vec![];
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>').map(|v| start + v + 1) else {
+            break;
+        };
+        let Some(mask) =
+            char::from_u32(TAG_MASK + tags.len() as u32).filter(|v| (*v as u32) < 0xF900)
+        else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        result.push(mask);
+        tags.push(rest[start..end].to_string());
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    (result, tags)
+}
+
+/// Private use characters used by mask_tags
+fn is_tag_mask(c: char) -> bool {
+    (TAG_MASK..0xF900).contains(&(c as u32))
+}
+
+fn unmask_tags(text: &str, tags: &[String]) -> String {
+    if tags.is_empty() {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match tags.get((c as u32).wrapping_sub(TAG_MASK) as usize) {
+            Some(tag) => result.push_str(tag),
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+/// Punctuation style of the target language
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Script {
+    Latin,
+    Japanese,
+    Chinese,
+}
+
+impl Script {
+    fn of(language: Language) -> Self {
+        match language {
+            Language::Japanese => Script::Japanese,
+            Language::Chinese
+            | Language::ChineseClassical
+            | Language::ChineseHakka
+            | Language::ChineseTaiwanese
+            | Language::ChineseYue => Script::Chinese,
+            _ => Script::Latin,
+        }
+    }
+}
+
+/// Opening and closing quote of the language
+fn quote_pair(language: Language) -> (&'static str, &'static str) {
+    match language {
+        Language::French => ("«\u{a0}", "\u{a0}»"),
+        Language::Spanish
+        | Language::Italian
+        | Language::Portuguese
+        | Language::Russian
+        | Language::Ukrainian
+        | Language::Norwegian
+        | Language::Greek => ("«", "»"),
+        Language::German | Language::Czech => ("„", "“"),
+        Language::Polish | Language::Romanian | Language::Hungarian => ("„", "”"),
+        Language::Swedish | Language::Finnish => ("”", "”"),
+        Language::Japanese | Language::ChineseTaiwanese => ("「", "」"),
+        _ => ("“", "”"),
+    }
+}
+
+/// Applies the typographic rules of the target language
+pub fn typography(text: &str, language: Language) -> String {
+    let script = Script::of(language);
+    let text = ellipsis(text, script, 3);
+    let text = match script {
+        Script::Latin => latin_punctuation(&text),
+        _ => cjk_punctuation(&text, script),
+    };
+    let text = quotes(&text, language);
+    match language {
+        Language::French => french_spacing(&text),
+        _ => text,
+    }
+}
+
+/// Maps ... and runs of at least `cjk_len` 。 or ・ to …, chinese uses ……
+pub(crate) fn ellipsis(text: &str, script: Script, cjk_len: usize) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let len = chars[index..].iter().take_while(|v| **v == c).count();
+        let ellipsis = match c {
+            '…' | '‥' => true,
+            '.' => len >= 3,
+            '。' | '・' => len >= cjk_len,
+            _ => false,
+        };
+        match ellipsis {
+            true => {
+                index += len;
+                while chars.get(index).is_some_and(|v| *v == '…' || *v == '‥') {
+                    index += 1;
+                }
+                result.push_str(match script {
+                    Script::Chinese => "……",
+                    _ => "…",
+                });
+            }
+            false => {
+                result.push(c);
+                index += 1;
+            }
+        }
+    }
+    result
+}
+
+/// Replaces full width punctuation with ascii punctuation and adds the missing spaces
+pub(crate) fn latin_punctuation(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    for (i, c) in chars.iter().enumerate() {
+        let mapped = match c {
+            '。' | '．' => '.',
+            '、' | '，' => ',',
+            '！' => '!',
+            '？' => '?',
+            '：' => ':',
+            '；' => ';',
+            '（' => '(',
+            '）' => ')',
+            _ => {
+                result.push(*c);
+                continue;
+            }
+        };
+        if mapped == '(' && result.ends_with(|v: char| v.is_alphanumeric()) {
+            result.push(' ');
+        }
+        result.push(mapped);
+        let next = chars.get(i + 1);
+        if mapped != '(' && next.is_some_and(|v| v.is_alphanumeric() || is_cjk(*v)) {
+            result.push(' ');
+        }
+    }
+    result
+}
+
+/// Replaces ascii punctuation after cjk text with full width punctuation
+fn cjk_punctuation(text: &str, script: Script) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    let mut skip_spaces = false;
+    for (i, c) in chars.iter().enumerate() {
+        let after_cjk = result.trim_end().chars().last().is_some_and(is_cjk);
+        let next = chars.get(i + 1).copied();
+        let mapped = match c {
+            ',' if after_cjk => Some(match script {
+                Script::Japanese => '、',
+                _ => '，',
+            }),
+            '.' if after_cjk && !next.is_some_and(|v| v.is_ascii_alphanumeric()) => Some('。'),
+            '!' if after_cjk => Some('！'),
+            '?' if after_cjk => Some('？'),
+            ':' if after_cjk && !next.is_some_and(|v| v.is_ascii_digit()) => Some('：'),
+            ';' if after_cjk => Some('；'),
+            '(' if after_cjk || next.is_some_and(is_cjk) => Some('（'),
+            ')' if after_cjk => Some('）'),
+            _ => None,
+        };
+        match mapped {
+            Some(v) => {
+                result.truncate(result.trim_end_matches(' ').len());
+                result.push(v);
+                skip_spaces = true;
+            }
+            None if skip_spaces && *c == ' ' => {}
+            None => {
+                skip_spaces = false;
+                result.push(*c);
+            }
+        }
+    }
+    result
+}
+
+/// Replaces all quotes with the quotes of the language
+fn quotes(text: &str, language: Language) -> String {
+    let (open, close) = quote_pair(language);
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    let mut skip_spaces = false;
+    for (i, c) in chars.iter().enumerate() {
+        let opening = match c {
+            '「' | '『' | '„' | '«' => Some(true),
+            '」' | '』' | '»' => Some(false),
+            '"' | '“' | '”' => {
+                let before = result.chars().rev().find(|v| !is_tag_mask(*v));
+                let after = chars.get(i + 1);
+                let start = before.is_none()
+                    || before.is_some_and(|v| v.is_whitespace() || "([{—–-/".contains(v));
+                Some(start && after.is_some_and(|v| !v.is_whitespace()))
+            }
+            _ => None,
+        };
+        match opening {
+            Some(true) => {
+                result.push_str(open);
+                skip_spaces = true;
+            }
+            Some(false) => {
+                result.truncate(result.trim_end_matches(' ').len());
+                result.push_str(close);
+                skip_spaces = false;
+            }
+            None if skip_spaces && *c == ' ' => {}
+            None => {
+                skip_spaces = false;
+                result.push(*c);
+            }
+        }
+    }
+    result
+}
+
+/// Adds non breaking spaces before : and narrow non breaking spaces before ; ! and ?
+fn french_spacing(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    for (i, c) in chars.iter().enumerate() {
+        let space = match c {
+            ';' | '!' | '?' => Some('\u{202f}'),
+            ':' => Some('\u{a0}'),
+            _ => None,
+        };
+        let boundary = chars
+            .get(i + 1)
+            .filter(|v| !v.is_whitespace() && !"!?;:»\"')".contains(**v))
+            .is_none();
+        if let (Some(space), true) = (space, boundary) {
+            match result.chars().last() {
+                Some(' ' | '\u{a0}' | '\u{202f}') => {
+                    result.pop();
+                    result.push(space);
+                }
+                Some(v) if !"!?;:".contains(v) && !v.is_whitespace() => result.push(space),
+                _ => {}
+            }
+        }
+        result.push(*c);
+    }
+    result
+}
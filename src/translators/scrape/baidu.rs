@@ -10,6 +10,7 @@ use serde_json::Value;
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::helpers::option_error;
+use crate::translators::options::TranslationOptions;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
@@ -89,11 +90,13 @@ impl TranslatorNoContext for BaiduTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
-        self.translate_vec(client, &[query.to_string()], from, to)
+        self.translate_vec(client, &[query.to_string()], from, to, options)
             .map(|v| TranslationOutput {
                 lang: v.lang,
                 text: v.text.join("\n"),
+                metadata: Default::default(),
             })
     }
 
@@ -103,6 +106,7 @@ impl TranslatorNoContext for BaiduTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let query = TranslateResult {
             from: option_error(from.map(|v| v.to_baidu_str()))?
@@ -131,6 +135,7 @@ impl TranslatorNoContext for BaiduTranslator {
         Ok(TranslationVecOutput {
             text: v.data.iter().map(|v| v.dst.clone()).collect(),
             lang: Language::from_str(&v.from)?,
+            metadata: Default::default(),
         })
     }
 }
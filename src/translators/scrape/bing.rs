@@ -12,6 +12,7 @@ use serde_json::Value;
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::helpers::{input_limit_checker, option_error};
+use crate::translators::options::TranslationOptions;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
@@ -44,6 +45,7 @@ impl TranslatorNoContext for BingTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         input_limit_checker(query, self.input_limit)?;
         let api_url = format!(
@@ -94,6 +96,7 @@ impl TranslatorNoContext for BingTranslator {
                 .text
                 .to_string(),
             lang: Language::from_str(&temp.detected_language.language)?,
+            metadata: Default::default(),
         })
     }
 
@@ -103,12 +106,14 @@ impl TranslatorNoContext for BingTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let query = query.join("\n");
-        let trans = self.translate(client, &query, from, to)?;
+        let trans = self.translate(client, &query, from, to, options)?;
         Ok(TranslationVecOutput {
             text: trans.text.split('\n').map(|v| v.to_string()).collect(),
             lang: trans.lang,
+            metadata: Default::default(),
         })
     }
 }
@@ -1,7 +1,8 @@
 use crate::error::Error;
 use crate::languages::Language;
-use crate::translators::context::{get_gpt_context, Context};
+use crate::translators::context::Context;
 use crate::translators::helpers::input_limit_checker;
+use crate::translators::options::TranslationOptions;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorContext,
 };
@@ -24,11 +25,13 @@ impl TranslatorContext for EdgeGpt {
         from: Option<Language>,
         to: &Language,
         context: &[Context],
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
-        let v = self.translate_vec(client, &[query.to_string()], from, to, context)?;
+        let v = self.translate_vec(client, &[query.to_string()], from, to, context, options)?;
         Ok(TranslationOutput {
             text: v.text.join("\n"),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
 
@@ -39,9 +42,9 @@ impl TranslatorContext for EdgeGpt {
         _: Option<Language>,
         to: &Language,
         context: &[Context],
+        _: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
-        let con = get_gpt_context(context);
-        let q_s = chatbot::generate_query(query, &to.to_name_str()?, con)?;
+        let q_s = chatbot::generate_query(query, &to.to_name_str()?, context)?;
         let message = self.fetch(&q_s)?;
         println!("{}", message);
         chatbot::process_result(message, query)
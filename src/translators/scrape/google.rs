@@ -7,6 +7,7 @@ use serde_json::Value;
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::helpers::option_error;
+use crate::translators::options::TranslationOptions;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
@@ -61,11 +62,13 @@ impl TranslatorNoContext for GoogleTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
-        let v = self.translate_vec(client, &[query.to_string()], from, to)?;
+        let v = self.translate_vec(client, &[query.to_string()], from, to, options)?;
         Ok(TranslationOutput {
             text: v.text.join("\\n"),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
 
@@ -75,6 +78,7 @@ impl TranslatorNoContext for GoogleTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
         let vv = self.fetch(client, from, to, &query.join("\\n"))?;
         let language = vv
@@ -99,6 +103,7 @@ impl TranslatorNoContext for GoogleTranslator {
         Ok(TranslationVecOutput {
             text: res,
             lang: Language::from_str(&language[1..language.len() - 1])?,
+            metadata: Default::default(),
         })
     }
 }
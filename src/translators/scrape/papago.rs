@@ -13,6 +13,7 @@ use serde::Serialize;
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::helpers::option_error;
+use crate::translators::options::TranslationOptions;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
@@ -57,6 +58,7 @@ impl TranslatorNoContext for PapagoTranslator {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         let url = format!("{}/apis/n2mt/translate", self.host);
         let auth_key = self.get_auth_key(client)?;
@@ -107,6 +109,7 @@ impl TranslatorNoContext for PapagoTranslator {
         Ok(TranslationOutput {
             text: res.translated_text,
             lang: Language::from_str(&res.src_lang_type)?,
+            metadata: Default::default(),
         })
     }
 
@@ -116,11 +119,13 @@ impl TranslatorNoContext for PapagoTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
-        let v = self.translate(client, &query.join("\n"), from, to)?;
+        let v = self.translate(client, &query.join("\n"), from, to, options)?;
         Ok(TranslationVecOutput {
             text: v.text.split('\n').map(|v| v.to_string()).collect(),
             lang: v.lang,
+            metadata: Default::default(),
         })
     }
 }
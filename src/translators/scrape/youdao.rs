@@ -11,6 +11,7 @@ use serde_json::Value;
 
 use crate::error::Error;
 use crate::languages::Language;
+use crate::translators::options::TranslationOptions;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::{
     TranslationOutput, TranslationVecOutput, TranslatorLanguages, TranslatorNoContext,
@@ -78,6 +79,7 @@ impl TranslatorNoContext for YoudaoTranslator {
         query: &str,
         _from: Option<Language>,
         to: &Language,
+        _: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
         to.to_youdao_str()?;
         let data = YoudaoRequest {
@@ -117,6 +119,7 @@ impl TranslatorNoContext for YoudaoTranslator {
         Ok(TranslationOutput {
             text: r.join("._._._."),
             lang: Language::Unknown,
+            metadata: Default::default(),
         })
     }
 
@@ -126,11 +129,13 @@ impl TranslatorNoContext for YoudaoTranslator {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error> {
-        let v = self.translate(client, &query.join("._._._."), from, to)?;
+        let v = self.translate(client, &query.join("._._._."), from, to, options)?;
         Ok(TranslationVecOutput {
             text: v.text.split("._._._.").map(|v| v.to_string()).collect(),
             lang: Language::Unknown,
+            metadata: Default::default(),
         })
     }
 }
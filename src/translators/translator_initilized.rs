@@ -12,14 +12,23 @@ use crate::translators::api::libretranslate::LibreTranslateTranslator;
 use crate::translators::api::mymemory::MyMemoryTranslator;
 use crate::translators::api::youdao::YouDaoApiTranslator;
 use crate::translators::chainer::TranslatorInfo;
+#[cfg(feature = "argos")]
+use crate::translators::offline::argos::ArgosTranslator;
 #[cfg(feature = "jparacrawl")]
 use crate::translators::offline::jparacrawl::JParaCrawlTranslator;
 #[cfg(feature = "m2m100")]
 use crate::translators::offline::m2m100::M2M100Translator;
+#[cfg(feature = "madlad400")]
+use crate::translators::offline::madlad400::Madlad400Translator;
+#[cfg(feature = "mbart50")]
+use crate::translators::offline::mbart50::MBart50Translator;
 #[cfg(feature = "nllb")]
 use crate::translators::offline::nllb::NllbTranslator;
+#[cfg(feature = "opus-mt")]
+use crate::translators::offline::opus_mt::OpusMtTranslator;
 #[cfg(feature = "sugoi")]
 use crate::translators::offline::sugoi::SugoiTranslator;
+use crate::translators::post_processing::PostProcessing;
 use crate::translators::scrape::baidu::BaiduTranslator;
 use crate::translators::scrape::bing::BingTranslator;
 use crate::translators::scrape::edgegpt::EdgeGpt;
@@ -28,6 +37,7 @@ use crate::translators::scrape::papago::PapagoTranslator;
 use crate::translators::scrape::youdao::YoudaoTranslator;
 use crate::translators::tokens::Tokens;
 use crate::translators::translator_structure::TranslatorDyn;
+use crate::translators::transliteration::Transliterator;
 use crate::translators::{Translator, TranslatorKind};
 
 #[derive(Debug)]
@@ -35,6 +45,7 @@ pub struct TranslatorInitialized {
     pub data: TranslatorDyn,
     pub translator: Translator,
     pub to: Language,
+    pub post_processing: Vec<PostProcessing>,
 }
 
 impl TranslatorInitialized {
@@ -51,7 +62,7 @@ impl TranslatorInitialized {
                     .deepl_token
                     .as_ref()
                     .ok_or_else(|| Error::new_option("No deepl token"))?;
-                TranslatorDyn::NC(Box::new(DeeplTranslator::new(deepl_token)))
+                TranslatorDyn::WC(Box::new(DeeplTranslator::new(deepl_token)))
             }
             Translator::ChatGPT(model, op, p, temp, wait) => {
                 info!("Initializing chatgpt translator");
@@ -165,11 +176,51 @@ impl TranslatorInitialized {
                     model_manager,
                 )?))
             }
+            #[cfg(feature = "opus-mt")]
+            Translator::OpusMt(device, model_format, from, to) => {
+                info!("Initializing opus-mt translator");
+                TranslatorDyn::Of(Box::new(OpusMtTranslator::new(
+                    device,
+                    model_format,
+                    from,
+                    to,
+                    model_manager,
+                )?))
+            }
+            #[cfg(feature = "mbart50")]
+            Translator::MBart50(device, model_format) => {
+                info!("Initializing mbart50 translator");
+                TranslatorDyn::Of(Box::new(MBart50Translator::new(
+                    device,
+                    model_format,
+                    model_manager,
+                )?))
+            }
+            #[cfg(feature = "madlad400")]
+            Translator::Madlad400(device, model_format, model_type) => {
+                info!("Initializing madlad400 translator");
+                TranslatorDyn::Of(Box::new(Madlad400Translator::new(
+                    device,
+                    model_format,
+                    model_type,
+                    model_manager,
+                )?))
+            }
+            #[cfg(feature = "argos")]
+            Translator::Argos(device, path) => {
+                info!("Initializing argos translator");
+                TranslatorDyn::Of(Box::new(ArgosTranslator::new(path, device)?))
+            }
+            Translator::Transliterate => {
+                info!("Initializing transliterator");
+                TranslatorDyn::NC(Box::new(Transliterator::new()))
+            }
         };
         Ok(Self {
             data,
             translator: info.translator,
             to: info.to,
+            post_processing: info.post_processing,
         })
     }
 }
@@ -1,47 +1,48 @@
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 
 use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
 
 use crate::error::Error;
 use crate::languages::Language;
 use crate::translators::context::Context;
 #[cfg(feature = "ctranslate_req")]
-use crate::translators::offline::ctranslate2::model_management::{
-    CTranslateModels, TokenizerModels,
-};
+use crate::translators::offline::ctranslate2::model_management::ModelPool;
+use crate::translators::options::{TranslationOption, TranslationOptions};
 use crate::translators::tokens::Tokens;
 
 #[cfg(feature = "ctranslate_req")]
 pub trait TranslatorCTranslate {
     fn translate(
         &self,
-        translator_models: &mut CTranslateModels,
-        tokenizer_models: &mut TokenizerModels,
+        pool: &ModelPool,
         query: &str,
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error> {
-        let mut temp = self.translate_vec(
-            translator_models,
-            tokenizer_models,
-            &[query.to_string()],
-            from,
-            to,
-        )?;
+        let mut temp = self.translate_vec(pool, &[query.to_string()], from, to, options)?;
         Ok(TranslationOutput {
             text: temp.text.remove(0),
             lang: temp.lang,
+            metadata: temp.metadata,
         })
     }
 
     fn translate_vec(
         &self,
-        translator_models: &mut CTranslateModels,
-        tokenizer_models: &mut TokenizerModels,
+        pool: &ModelPool,
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error>;
+
+    /// Options of TranslationOptions that are used by the translator
+    fn supported_options(&self) -> &'static [TranslationOption] {
+        &[]
+    }
 }
 
 pub trait TranslatorNoContext {
@@ -51,6 +52,7 @@ pub trait TranslatorNoContext {
         query: &str,
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error>;
 
     fn translate_vec(
@@ -59,7 +61,13 @@ pub trait TranslatorNoContext {
         query: &[String],
         from: Option<Language>,
         to: &Language,
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error>;
+
+    /// Options of TranslationOptions that are used by the translator
+    fn supported_options(&self) -> &'static [TranslationOption] {
+        &[]
+    }
 }
 
 pub trait TranslatorLanguages {
@@ -78,6 +86,7 @@ pub trait TranslatorContext {
         from: Option<Language>,
         to: &Language,
         context: &[Context],
+        options: &TranslationOptions,
     ) -> Result<TranslationOutput, Error>;
 
     fn translate_vec(
@@ -87,7 +96,13 @@ pub trait TranslatorContext {
         from: Option<Language>,
         to: &Language,
         context: &[Context],
+        options: &TranslationOptions,
     ) -> Result<TranslationVecOutput, Error>;
+
+    /// Options of TranslationOptions that are used by the translator
+    fn supported_options(&self) -> &'static [TranslationOption] {
+        &[]
+    }
 }
 
 pub enum TranslatorDyn {
@@ -97,26 +112,59 @@ pub enum TranslatorDyn {
     Of(Box<dyn TranslatorCTranslate>),
 }
 
+impl TranslatorDyn {
+    /// Options of TranslationOptions that are used by the translator
+    pub fn supported_options(&self) -> &'static [TranslationOption] {
+        match self {
+            TranslatorDyn::WC(v) => v.supported_options(),
+            TranslatorDyn::NC(v) => v.supported_options(),
+            #[cfg(feature = "ctranslate_req")]
+            TranslatorDyn::Of(v) => v.supported_options(),
+        }
+    }
+}
+
 impl Debug for TranslatorDyn {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "No Debug")
     }
 }
 
+/// Where a translation comes from and how long it took
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranslationMetadata {
+    /// Translator that produced the text, None for the source text
+    pub translator: Option<String>,
+    /// Source language reported by the translator or the detector, None when the translator didnt report one
+    pub detected_lang: Option<Language>,
+    /// Confidence of the detector between 0 and 1 for the source text, None when the language wasn't detected by the detector
+    pub confidence: Option<f64>,
+    /// Time of the request including all retries
+    pub latency: Duration,
+    /// Number of requests, more than 1 when the translator was retried
+    pub attempts: u32,
+    /// Number of characters of the query
+    pub chars: usize,
+}
+
 /// Translation Result containing the translation and the language
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TranslationOutput {
     /// Translation
     pub text: String,
-    /// Text language
+    /// Language of the text, the target language for translations and the source language for the source text
     pub lang: Language,
+    #[serde(default)]
+    pub metadata: TranslationMetadata,
 }
 
 /// Translation Result containing a vector of translations and the language
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TranslationVecOutput {
     /// Translations
     pub text: Vec<String>,
-    /// Language
+    /// Language of the text, the target language for translations and the source language for the source text
     pub lang: Language,
+    #[serde(default)]
+    pub metadata: TranslationMetadata,
 }
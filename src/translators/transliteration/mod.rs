@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use reqwest::blocking::Client;
+
+use crate::error::Error;
+use crate::languages::Language;
+use crate::translators::options::TranslationOptions;
+use crate::translators::post_processing::latin_punctuation;
+use crate::translators::translator_structure::{
+    TranslationOutput, TranslationVecOutput, TranslatorNoContext,
+};
+
+/// Pinyin with the tone as number, followed by the han characters with that reading
+const PINYIN: &str = include_str!("pinyin.txt");
+
+/// Hiragana with the romaji, katakana is mapped to hiragana before the lookup
+const HEPBURN: &[(&str, &str)] = &[
+    ("きゃ", "kya"),
+    ("きゅ", "kyu"),
+    ("きょ", "kyo"),
+    ("ぎゃ", "gya"),
+    ("ぎゅ", "gyu"),
+    ("ぎょ", "gyo"),
+    ("しゃ", "sha"),
+    ("しゅ", "shu"),
+    ("しょ", "sho"),
+    ("しぇ", "she"),
+    ("じゃ", "ja"),
+    ("じゅ", "ju"),
+    ("じょ", "jo"),
+    ("じぇ", "je"),
+    ("ちゃ", "cha"),
+    ("ちゅ", "chu"),
+    ("ちょ", "cho"),
+    ("ちぇ", "che"),
+    ("ぢゃ", "ja"),
+    ("ぢゅ", "ju"),
+    ("ぢょ", "jo"),
+    ("にゃ", "nya"),
+    ("にゅ", "nyu"),
+    ("にょ", "nyo"),
+    ("ひゃ", "hya"),
+    ("ひゅ", "hyu"),
+    ("ひょ", "hyo"),
+    ("びゃ", "bya"),
+    ("びゅ", "byu"),
+    ("びょ", "byo"),
+    ("ぴゃ", "pya"),
+    ("ぴゅ", "pyu"),
+    ("ぴょ", "pyo"),
+    ("みゃ", "mya"),
+    ("みゅ", "myu"),
+    ("みょ", "myo"),
+    ("りゃ", "rya"),
+    ("りゅ", "ryu"),
+    ("りょ", "ryo"),
+    ("ふぁ", "fa"),
+    ("ふぃ", "fi"),
+    ("ふぇ", "fe"),
+    ("ふぉ", "fo"),
+    ("てぃ", "ti"),
+    ("でぃ", "di"),
+    ("とぅ", "tu"),
+    ("どぅ", "du"),
+    ("でゅ", "dyu"),
+    ("つぁ", "tsa"),
+    ("うぃ", "wi"),
+    ("うぇ", "we"),
+    ("うぉ", "wo"),
+    ("いぇ", "ye"),
+    ("ゔぁ", "va"),
+    ("ゔぃ", "vi"),
+    ("ゔぇ", "ve"),
+    ("ゔぉ", "vo"),
+    ("あ", "a"),
+    ("い", "i"),
+    ("う", "u"),
+    ("え", "e"),
+    ("お", "o"),
+    ("か", "ka"),
+    ("き", "ki"),
+    ("く", "ku"),
+    ("け", "ke"),
+    ("こ", "ko"),
+    ("が", "ga"),
+    ("ぎ", "gi"),
+    ("ぐ", "gu"),
+    ("げ", "ge"),
+    ("ご", "go"),
+    ("さ", "sa"),
+    ("し", "shi"),
+    ("す", "su"),
+    ("せ", "se"),
+    ("そ", "so"),
+    ("ざ", "za"),
+    ("じ", "ji"),
+    ("ず", "zu"),
+    ("ぜ", "ze"),
+    ("ぞ", "zo"),
+    ("た", "ta"),
+    ("ち", "chi"),
+    ("つ", "tsu"),
+    ("て", "te"),
+    ("と", "to"),
+    ("だ", "da"),
+    ("ぢ", "ji"),
+    ("づ", "zu"),
+    ("で", "de"),
+    ("ど", "do"),
+    ("な", "na"),
+    ("に", "ni"),
+    ("ぬ", "nu"),
+    ("ね", "ne"),
+    ("の", "no"),
+    ("は", "ha"),
+    ("ひ", "hi"),
+    ("ふ", "fu"),
+    ("へ", "he"),
+    ("ほ", "ho"),
+    ("ば", "ba"),
+    ("び", "bi"),
+    ("ぶ", "bu"),
+    ("べ", "be"),
+    ("ぼ", "bo"),
+    ("ぱ", "pa"),
+    ("ぴ", "pi"),
+    ("ぷ", "pu"),
+    ("ぺ", "pe"),
+    ("ぽ", "po"),
+    ("ま", "ma"),
+    ("み", "mi"),
+    ("む", "mu"),
+    ("め", "me"),
+    ("も", "mo"),
+    ("や", "ya"),
+    ("ゆ", "yu"),
+    ("よ", "yo"),
+    ("ら", "ra"),
+    ("り", "ri"),
+    ("る", "ru"),
+    ("れ", "re"),
+    ("ろ", "ro"),
+    ("わ", "wa"),
+    ("ゐ", "i"),
+    ("ゑ", "e"),
+    ("を", "o"),
+    ("ん", "n"),
+    ("ゔ", "vu"),
+    ("ぁ", "a"),
+    ("ぃ", "i"),
+    ("ぅ", "u"),
+    ("ぇ", "e"),
+    ("ぉ", "o"),
+    ("ゃ", "ya"),
+    ("ゅ", "yu"),
+    ("ょ", "yo"),
+    ("ゎ", "wa"),
+    ("ゕ", "ka"),
+    ("ゖ", "ke"),
+];
+
+/// Initial consonants of a hangul syllable
+const RR_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+
+/// Vowels of a hangul syllable
+const RR_MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+
+/// Final consonants before a consonant or at the end of a word
+const RR_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+/// Final consonants that are carried over to a following syllable without initial consonant
+const RR_LIAISONS: [&str; 28] = [
+    "", "g", "kk", "ks", "n", "nj", "n", "d", "r", "lg", "lm", "lb", "ls", "lt", "lp", "r", "m",
+    "b", "ps", "s", "ss", "ng", "j", "ch", "k", "t", "p", "",
+];
+
+/// Romanization systems
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransliterationScheme {
+    /// Kana to romaji, kanji is kept
+    Hepburn,
+    /// Hangul to latin
+    RevisedRomanization,
+    /// Han characters to pinyin with tone marks
+    Pinyin,
+}
+
+impl TransliterationScheme {
+    /// Scheme that is used for the language
+    pub fn from_language(language: Language) -> Option<Self> {
+        match language {
+            Language::Japanese => Some(Self::Hepburn),
+            Language::Korean => Some(Self::RevisedRomanization),
+            Language::Chinese
+            | Language::ChineseClassical
+            | Language::ChineseHakka
+            | Language::ChineseTaiwanese
+            | Language::ChineseYue => Some(Self::Pinyin),
+            _ => None,
+        }
+    }
+
+    /// Scheme that matches the script of the text, kana takes precedence over han characters
+    pub fn detect(text: &str) -> Option<Self> {
+        if text.chars().any(is_kana) {
+            Some(Self::Hepburn)
+        } else if text.chars().any(is_hangul) {
+            Some(Self::RevisedRomanization)
+        } else if text.chars().any(|v| pinyin_table().contains_key(&v)) {
+            Some(Self::Pinyin)
+        } else {
+            None
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let text = match self {
+            Self::Hepburn => hepburn(text),
+            Self::RevisedRomanization => revised_romanization(text),
+            Self::Pinyin => pinyin(text),
+        };
+        latin_punctuation(&quotes(&text))
+    }
+}
+
+/// Transliterates the text with the scheme of the language, the text is returned unchanged when the language has none
+pub fn transliterate(text: &str, language: Language) -> String {
+    match TransliterationScheme::from_language(language) {
+        Some(scheme) => scheme.apply(text),
+        None => text.to_string(),
+    }
+}
+
+/// Pseudo translator that romanizes the text instead of translating it.
+/// The scheme is chosen by the source language, or by the script of the text when the language is unknown.
+#[derive(Default)]
+pub struct Transliterator;
+
+impl Transliterator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn scheme(query: &str, from: Option<Language>) -> Option<TransliterationScheme> {
+        from.and_then(TransliterationScheme::from_language)
+            .or_else(|| TransliterationScheme::detect(query))
+    }
+}
+
+impl TranslatorNoContext for Transliterator {
+    fn translate(
+        &self,
+        _: &Client,
+        query: &str,
+        from: Option<Language>,
+        _: &Language,
+        _: &TranslationOptions,
+    ) -> Result<TranslationOutput, Error> {
+        let text = match Self::scheme(query, from) {
+            Some(scheme) => scheme.apply(query),
+            None => query.to_string(),
+        };
+        Ok(TranslationOutput {
+            text,
+            lang: from.unwrap_or(Language::Unknown),
+            metadata: Default::default(),
+        })
+    }
+
+    fn translate_vec(
+        &self,
+        _: &Client,
+        query: &[String],
+        from: Option<Language>,
+        _: &Language,
+        _: &TranslationOptions,
+    ) -> Result<TranslationVecOutput, Error> {
+        let scheme = Self::scheme(&query.join("\n"), from);
+        Ok(TranslationVecOutput {
+            text: query
+                .iter()
+                .map(|v| match scheme {
+                    Some(scheme) => scheme.apply(v),
+                    None => v.to_string(),
+                })
+                .collect(),
+            lang: from.unwrap_or(Language::Unknown),
+            metadata: Default::default(),
+        })
+    }
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c as u32, 0x3041..=0x30FA)
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c as u32, 0xAC00..=0xD7A3)
+}
+
+fn pinyin_table() -> &'static HashMap<char, &'static str> {
+    static TABLE: OnceLock<HashMap<char, &'static str>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = HashMap::new();
+        for line in PINYIN.lines() {
+            if let Some((syllable, chars)) = line.split_once(' ') {
+                table.extend(chars.chars().map(|v| (v, syllable)));
+            }
+        }
+        table
+    })
+}
+
+/// Replaces the japanese quotes and separators with latin ones
+fn quotes(text: &str) -> String {
+    text.chars()
+        .map(|v| match v {
+            '「' | '『' => '“',
+            '」' | '』' => '”',
+            '・' => ' ',
+            '〜' | '～' => '~',
+            '　' => ' ',
+            _ => v,
+        })
+        .collect()
+}
+
+fn hepburn(text: &str) -> String {
+    let chars = text
+        .chars()
+        .map(|v| match v as u32 {
+            0x30A1..=0x30F6 => char::from_u32(v as u32 - 0x60).unwrap_or(v),
+            _ => v,
+        })
+        .collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len() * 2);
+    let mut sokuon = false;
+    let mut after_n = false;
+    let mut after_kana = false;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if c == 'っ' {
+            sokuon = true;
+            index += 1;
+            continue;
+        }
+        // ou and uu are long vowels like ー
+        let long_vowel = c == 'ー' || (c == 'う' && after_kana && result.ends_with(['o', 'u']));
+        if long_vowel {
+            if let Some(long) = result.chars().last().and_then(macron) {
+                result.pop();
+                result.push(long);
+            }
+            index += 1;
+            continue;
+        }
+        let romaji = [2, 1].into_iter().find_map(|len| {
+            let kana = chars.get(index..index + len)?.iter().collect::<String>();
+            HEPBURN.iter().find(|v| v.0 == kana).map(|v| (len, v.1))
+        });
+        match romaji {
+            Some((len, romaji)) => {
+                if after_n && romaji.starts_with(['a', 'i', 'u', 'e', 'o', 'y']) {
+                    result.push('\'');
+                }
+                if sokuon {
+                    match romaji.starts_with("ch") {
+                        true => result.push('t'),
+                        false => result.extend(romaji.chars().next()),
+                    }
+                }
+                result.push_str(romaji);
+                after_n = c == 'ん';
+                after_kana = true;
+                index += len;
+            }
+            None => {
+                result.push(c);
+                after_n = false;
+                after_kana = false;
+                index += 1;
+            }
+        }
+        sokuon = false;
+    }
+    result
+}
+
+/// Long vowel of a vowel
+fn macron(c: char) -> Option<char> {
+    match c {
+        'a' => Some('ā'),
+        'i' => Some('ī'),
+        'u' => Some('ū'),
+        'e' => Some('ē'),
+        'o' => Some('ō'),
+        _ => None,
+    }
+}
+
+fn revised_romanization(text: &str) -> String {
+    let syllables = text
+        .chars()
+        .map(|v| match is_hangul(v) {
+            true => {
+                let index = v as usize - 0xAC00;
+                Some((index / 588, index % 588 / 28, index % 28))
+            }
+            false => None,
+        })
+        .collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+    for (i, c) in text.chars().enumerate() {
+        let Some((initial, medial, last)) = syllables[i] else {
+            result.push(c);
+            continue;
+        };
+        let previous = i.checked_sub(1).and_then(|v| syllables[v]);
+        match previous {
+            Some((_, _, 8)) if initial == 5 => result.push_str("ll"),
+            Some((_, _, last)) if initial == 11 => result.push_str(RR_LIAISONS[last]),
+            Some((_, _, last)) => {
+                result.push_str(RR_FINALS[last]);
+                result.push_str(RR_INITIALS[initial]);
+            }
+            None => result.push_str(RR_INITIALS[initial]),
+        }
+        result.push_str(RR_MEDIALS[medial]);
+        if syllables.get(i + 1).copied().flatten().is_none() {
+            result.push_str(RR_FINALS[last]);
+        }
+    }
+    result
+}
+
+fn pinyin(text: &str) -> String {
+    let table = pinyin_table();
+    let mut result = String::with_capacity(text.len() * 2);
+    let mut after_syllable = false;
+    for c in text.chars() {
+        match table.get(&c) {
+            Some(syllable) => {
+                if after_syllable || result.ends_with(|v: char| v.is_alphanumeric()) {
+                    result.push(' ');
+                }
+                result.push_str(&tone_mark(syllable));
+                after_syllable = true;
+            }
+            None => {
+                if after_syllable && c.is_alphanumeric() {
+                    result.push(' ');
+                }
+                result.push(c);
+                after_syllable = false;
+            }
+        }
+    }
+    result
+}
+
+/// Converts a syllable like hao3 or lv4 to hǎo or lǜ
+fn tone_mark(syllable: &str) -> String {
+    let (letters, tone) = syllable.split_at(syllable.len() - 1);
+    let letters = letters.replace('v', "ü");
+    let tone = match tone.parse::<usize>() {
+        Ok(v @ 1..=4) => v - 1,
+        _ => return letters,
+    };
+    let position = letters
+        .find(['a', 'e'])
+        .or_else(|| letters.find("ou"))
+        .or_else(|| letters.rfind(['i', 'o', 'u', 'ü']));
+    let Some(position) = position else {
+        return letters;
+    };
+    let mut chars = letters.chars().collect::<Vec<_>>();
+    let index = letters[..position].chars().count();
+    let marks = match chars[index] {
+        'a' => ['ā', 'á', 'ǎ', 'à'],
+        'e' => ['ē', 'é', 'ě', 'è'],
+        'i' => ['ī', 'í', 'ǐ', 'ì'],
+        'o' => ['ō', 'ó', 'ǒ', 'ò'],
+        'u' => ['ū', 'ú', 'ǔ', 'ù'],
+        _ => ['ǖ', 'ǘ', 'ǚ', 'ǜ'],
+    };
+    chars[index] = marks[tone];
+    chars.into_iter().collect()
+}